@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity ^0.8.20;
+
+/// @title Kreivo Assets API
+/// @notice Facilitates transactions using arbitrary assets between the
+/// contract and other accounts.
+/// @dev Asset ids are passed as the SCALE-encoded `FungibleAssetLocation`.
+/// Failed calls revert with the corresponding `KreivoApisError` code.
+/// @dev There's no `deposit`: the precompile doesn't know the caller of the
+/// contract, and charging the origin of the transaction instead would let any
+/// contract it calls into drain its funds. Callers transfer the assets to the
+/// contract themselves.
+/// @dev The precompile is available at `0x0000000000000000000000000000000000005000`.
+interface IKreivoAssets {
+	/// @notice Returns the balance of an asset account.
+	function balance(bytes calldata asset, address who) external view returns (uint256);
+
+	/// @notice Transfers an `amount` of a certain `asset` from the contract to
+	/// a `beneficiary`.
+	function transfer(bytes calldata asset, uint256 amount, address beneficiary) external returns (uint256);
+}
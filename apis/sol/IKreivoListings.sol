@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity ^0.8.20;
+
+/// @title Kreivo Listings API
+/// @notice Allows the merchant associated to the calling contract to manage
+/// its inventories and items.
+/// @dev Asset ids are passed as the SCALE-encoded `FungibleAssetLocation`.
+/// Failed calls revert with the corresponding `KreivoApisError` code.
+/// @dev The precompile is available at `0x0000000000000000000000000000000000005001`.
+interface IKreivoListings {
+	// Inventories
+
+	/// @notice Returns whether an inventory exists.
+	function inventoryExists(uint32 id) external view returns (bool);
+
+	/// @notice Returns whether an inventory is active.
+	function inventoryIsActive(uint32 id) external view returns (bool);
+
+	/// @notice Returns the value of an inventory attribute, or empty bytes if
+	/// not set.
+	function inventoryAttribute(uint32 id, bytes calldata key) external view returns (bytes memory);
+
+	/// @notice Creates a new inventory, owned by the contract.
+	function createInventory(uint32 id) external;
+
+	/// @notice Archives an active inventory.
+	function archiveInventory(uint32 id) external;
+
+	/// @notice Sets the metadata of an inventory.
+	function setInventoryMetadata(uint32 id, bytes calldata metadata) external;
+
+	/// @notice Clears the metadata of an inventory.
+	function clearInventoryMetadata(uint32 id) external;
+
+	/// @notice Sets an attribute on an inventory.
+	function setInventoryAttribute(uint32 id, bytes calldata key, bytes calldata value) external;
+
+	/// @notice Clears an attribute on an inventory.
+	function clearInventoryAttribute(uint32 id, bytes calldata key) external;
+
+	// Items
+
+	/// @notice Returns the SCALE-encoded item, or empty bytes if the item does
+	/// not exist.
+	function item(uint32 inventoryId, uint64 id) external view returns (bytes memory);
+
+	/// @notice Returns the value of an item attribute, or empty bytes if not
+	/// set.
+	function itemAttribute(uint32 inventoryId, uint64 id, bytes calldata key) external view returns (bytes memory);
+
+	/// @notice Returns whether an item is transferable.
+	function itemTransferable(uint32 inventoryId, uint64 id) external view returns (bool);
+
+	/// @notice Returns whether an item can be resold.
+	function itemCanResell(uint32 inventoryId, uint64 id) external view returns (bool);
+
+	/// @notice Publishes a new item on an active inventory, priced at `amount`
+	/// of `asset`. An empty `asset` publishes the item without a price.
+	function publishItem(
+		uint32 inventoryId,
+		uint64 id,
+		bytes calldata name,
+		bytes calldata asset,
+		uint256 amount
+	) external;
+
+	/// @notice Sets the price of an item.
+	function setItemPrice(uint32 inventoryId, uint64 id, bytes calldata asset, uint256 amount) external;
+
+	/// @notice Clears the price of an item.
+	function clearItemPrice(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Sets the metadata of an item.
+	function setItemMetadata(uint32 inventoryId, uint64 id, bytes calldata metadata) external;
+
+	/// @notice Clears the metadata of an item.
+	function clearItemMetadata(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Enables an item to be resold.
+	function enableItemResell(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Disables an item to be resold.
+	function disableItemResell(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Enables an item to be transferred.
+	function enableItemTransfer(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Disables an item to be transferred.
+	function disableItemTransfer(uint32 inventoryId, uint64 id) external;
+
+	/// @notice Sets an attribute on an item.
+	function setItemAttribute(uint32 inventoryId, uint64 id, bytes calldata key, bytes calldata value) external;
+
+	/// @notice Clears an attribute on an item.
+	function clearItemAttribute(uint32 inventoryId, uint64 id, bytes calldata key) external;
+
+	/// @notice Transfers an item.
+	function transferItem(uint32 inventoryId, uint64 id, address beneficiary) external;
+
+	/// @notice Transfers an item, also marking the beneficiary as its creator.
+	function creatorTransferItem(uint32 inventoryId, uint64 id, address beneficiary) external;
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity ^0.8.20;
+
+/// @title Kreivo Memberships API
+/// @notice Allows managing the memberships of the group associated to the
+/// calling contract.
+/// @dev Failed calls revert with the corresponding `KreivoApisError` code.
+/// @dev The precompile is available at `0x0000000000000000000000000000000000005002`.
+interface IKreivoMemberships {
+	/// @notice Assigns an available membership of the group to `who`.
+	function assignMembership(address who) external;
+
+	/// @notice Returns the first membership of `who` in the group, if any.
+	function membershipOf(address who) external view returns (bool exists, uint32 id);
+
+	/// @notice Returns the SCALE-encoded rank of a membership, or empty bytes
+	/// if the membership is not found.
+	function rankOf(uint32 id) external view returns (bytes memory);
+
+	/// @notice Returns the value of a membership attribute, or empty bytes if
+	/// not set.
+	function attribute(uint32 id, bytes calldata key) external view returns (bytes memory);
+
+	/// @notice Sets an attribute on a membership.
+	function setAttribute(uint32 id, bytes calldata key, bytes calldata value) external;
+
+	/// @notice Clears an attribute on a membership.
+	function clearAttribute(uint32 id, bytes calldata key) external;
+
+	/// @notice Returns the first membership of `who` with an attribute `key`
+	/// matching `value`, if any.
+	function filterMembership(address who, bytes calldata key, bytes calldata value)
+		external
+		view
+		returns (bool exists, uint32 id);
+}
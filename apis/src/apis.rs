@@ -2,11 +2,16 @@ mod assets;
 mod error;
 mod listings;
 mod memberships;
+mod randomness;
 
 pub use assets::*;
 pub use error::*;
 pub use listings::*;
 pub use memberships::*;
+pub use randomness::*;
+
+/// The `func_id` used to dispatch a batch of calls to the [`KreivoAPI`]s.
+pub const BATCH_FUNC_ID: u16 = 0xff00;
 
 /// A set of APIs to interact between applications (like Smart Contracts) and
 /// the Kreivo runtime.
@@ -17,4 +22,6 @@ pub trait KreivoAPI<Ext> {
 	type Listings: ListingsInventoriesAPI<Ext> + ListingsItemsAPI<Ext>;
 	/// Management of group's memberships
 	type Memberships: MembershipsAPI<Ext>;
+	/// Secure randomness
+	type Randomness: RandomnessAPI<Ext>;
 }
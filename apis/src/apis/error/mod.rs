@@ -5,10 +5,12 @@ use scale_info::TypeInfo;
 mod assets;
 mod listings;
 mod memberships;
+mod randomness;
 
 pub use assets::*;
 pub use listings::*;
 pub use memberships::*;
+pub use randomness::*;
 
 #[derive(Encode, Decode, Debug, Clone, Copy)]
 pub struct KreivoApisErrorCode(u32);
@@ -35,9 +37,13 @@ impl From<Error> for KreivoApisErrorCode {
 pub enum KreivoApisError {
 	UnknownError,
 	ExtQueryError,
+	/// The input of the call could not be decoded, or the `func_id` is not
+	/// registered.
+	InvalidInput,
 	Assets(AssetsApiError),
 	Listings(ListingsApiError),
 	Memberships(MembershipsApiError),
+	Randomness(RandomnessApiError),
 }
 
 impl From<KreivoApisError> for KreivoApisErrorCode {
@@ -46,9 +52,11 @@ impl From<KreivoApisError> for KreivoApisErrorCode {
 		Self(match error {
 			KreivoApisError::UnknownError => 1,
 			KreivoApisError::ExtQueryError => 2,
+			KreivoApisError::InvalidInput => 3,
 			KreivoApisError::Assets(e) => 0x00010000 | inner(e as u16),
 			KreivoApisError::Listings(e) => 0x00020000 | inner(e as u16),
 			KreivoApisError::Memberships(e) => 0x00030000 | inner(e as u16),
+			KreivoApisError::Randomness(e) => 0x00040000 | inner(e as u16),
 		})
 	}
 }
@@ -58,9 +66,11 @@ impl From<KreivoApisErrorCode> for KreivoApisError {
 		let inner = (value.0 & 0x0000ffff) as u16;
 		match value.0 {
 			0x00000002 => Some(KreivoApisError::ExtQueryError),
+			0x00000003 => Some(KreivoApisError::InvalidInput),
 			0x00010000..0x00020000 => TryFrom::<u16>::try_from(inner).ok().map(KreivoApisError::Assets),
 			0x00020000..0x00030000 => TryFrom::<u16>::try_from(inner).ok().map(KreivoApisError::Listings),
 			0x00030000..0x00040000 => TryFrom::<u16>::try_from(inner).ok().map(KreivoApisError::Memberships),
+			0x00040000..0x00050000 => TryFrom::<u16>::try_from(inner).ok().map(KreivoApisError::Randomness),
 			_ => None,
 		}
 		.unwrap_or(KreivoApisError::UnknownError)
@@ -84,6 +94,7 @@ mod tests {
 	fn convert_from_error_to_error_code_back_to_error_works() {
 		test_error_code_conversion!(KreivoApisError::UnknownError);
 		test_error_code_conversion!(KreivoApisError::ExtQueryError);
+		test_error_code_conversion!(KreivoApisError::InvalidInput);
 
 		test_error_code_conversion!(AssetsApiError::CannotDeposit);
 		test_error_code_conversion!(AssetsApiError::CannotTransfer);
@@ -103,5 +114,7 @@ mod tests {
 		test_error_code_conversion!(MembershipsApiError::UnknownMembership);
 		test_error_code_conversion!(MembershipsApiError::CannotAddMember);
 		test_error_code_conversion!(MembershipsApiError::FailedToSetAttribute);
+
+		test_error_code_conversion!(RandomnessApiError::NotAvailableYet);
 	}
 }
@@ -0,0 +1,15 @@
+use super::*;
+
+#[repr(u16)]
+#[derive(TypeInfo, Encode, Decode, Clone, Debug, PartialEq, TryFromPrimitive)]
+pub enum RandomnessApiError {
+	/// The source of randomness has not been seeded yet, so no random values
+	/// can be derived from it.
+	NotAvailableYet,
+}
+
+impl From<RandomnessApiError> for KreivoApisError {
+	fn from(error: RandomnessApiError) -> Self {
+		KreivoApisError::Randomness(error)
+	}
+}
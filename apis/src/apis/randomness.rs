@@ -0,0 +1,25 @@
+//! # Randomness APIs
+//!
+//! Provide applications with a source of randomness.
+
+use crate::apis::KreivoApisError;
+use core::fmt;
+use parity_scale_codec::Codec;
+
+/// An API for getting random values, derived from a source that is
+/// unpredictable to both the callers and the block authors.
+pub trait RandomnessAPI<Env> {
+	type Hash: Codec + Clone + Eq + fmt::Debug;
+	type BlockNumber: Codec + Copy + Eq + fmt::Debug;
+
+	/// Returns a random value for [`subject`], along with the block number
+	/// since which such value is known.
+	///
+	/// The value is not predictable before that block, but it is afterwards:
+	/// to avoid being gamed, applications must only use it to resolve
+	/// decisions committed to _before_ the returned block number. Calling with
+	/// the same [`subject`] within the same period yields the same value.
+	///
+	/// Fails if the source of randomness has not been seeded yet.
+	fn random(env: &Env, subject: &[u8]) -> Result<(Self::Hash, Self::BlockNumber), KreivoApisError>;
+}
@@ -1,13 +1,15 @@
 use super::*;
 use frame_contrib_traits::memberships::GenericRank;
 
-use crate::contract::config::{ListingsConfig, MembershipsConfig};
+use crate::contract::config::{ListingsConfig, MembershipsConfig, RandomnessConfig};
 use contract::config::{AssetsConfig, Config};
 use ink::env::{DefaultEnvironment, Environment};
 
 mod api_impls;
 mod chain_extension;
 pub mod config;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub use api_impls::KreivoApi;
 
 #[derive(Clone)]
@@ -51,3 +53,8 @@ impl<E: Environment> MembershipsConfig for KreivoApiEnvironment<E> {
 	type Membership = virto_common::MembershipId;
 	type Rank = GenericRank;
 }
+
+impl<E: Environment> RandomnessConfig for KreivoApiEnvironment<E> {
+	type Hash = ink::primitives::Hash;
+	type BlockNumber = u32;
+}
@@ -4,8 +4,8 @@ use super::{
 	config::{AccountIdOf, AssetBalanceOf, AssetIdOf, InventoryIdOf, ItemIdOf, ItemOf, ItemPriceOf},
 	KreivoApiEnvironment,
 };
-use crate::apis::MembershipsAPI;
-use crate::contract::config::{MembershipOf, RankOf};
+use crate::apis::{MembershipsAPI, RandomnessAPI};
+use crate::contract::config::{BlockNumberOf, HashOf, MembershipOf, RankOf};
 use core::marker::PhantomData;
 use frame_support::Parameter;
 use ink::{
@@ -21,6 +21,19 @@ impl<E: Environment<ChainExtension = ChainExtension>> KreivoAPI<EnvAccess<'_, E>
 	type Assets = KreivoAssetsApi;
 	type Listings = KreivoListingsApi;
 	type Memberships = KreivoMembershipsApi;
+	type Randomness = KreivoRandomnessApi;
+}
+
+impl<E: Environment<ChainExtension = ChainExtension>> KreivoApi<E> {
+	/// Dispatches a batch of calls in a single round-trip. Each call is a pair
+	/// of the `func_id` and the SCALE-encoded input of the call.
+	///
+	/// Calls are dispatched with all-or-nothing semantics: if any of them fails,
+	/// every change is reverted and the error of the failing call is returned.
+	/// Otherwise, returns the SCALE-encoded result of each call, in order.
+	pub fn batch(env: &EnvAccess<'_, E>, calls: Vec<(u16, Vec<u8>)>) -> Result<Vec<Vec<u8>>, KreivoApisError> {
+		env.clone().extension().batch(calls).map_err(|code| code.into())
+	}
 }
 
 // Assets
@@ -406,3 +419,21 @@ where
 			.memberships__filter_membership(*who, key.encode(), value.encode())
 	}
 }
+
+// Randomness
+pub struct KreivoRandomnessApi;
+
+impl<E> RandomnessAPI<EnvAccess<'_, E>> for KreivoRandomnessApi
+where
+	E: Environment<ChainExtension = ChainExtension>,
+{
+	type Hash = HashOf<KreivoApiEnvironment>;
+	type BlockNumber = BlockNumberOf<KreivoApiEnvironment>;
+
+	fn random(env: &EnvAccess<'_, E>, subject: &[u8]) -> Result<(Self::Hash, Self::BlockNumber), KreivoApisError> {
+		env.clone()
+			.extension()
+			.randomness__random(subject.to_vec())
+			.map_err(|code| code.into())
+	}
+}
@@ -1,7 +1,9 @@
 use super::*;
 
 use crate::apis::KreivoApisErrorCode;
-use crate::contract::config::{AssetBalanceOf, InventoryIdOf, ItemIdOf, ItemOf, ItemPriceOf, MembershipOf, RankOf};
+use crate::contract::config::{
+	AssetBalanceOf, BlockNumberOf, HashOf, InventoryIdOf, ItemIdOf, ItemOf, ItemPriceOf, MembershipOf, RankOf,
+};
 use config::{AccountIdOf, AssetIdOf, BalanceOf};
 use ink::{chain_extension, prelude::vec::Vec};
 
@@ -214,6 +216,17 @@ pub trait ChainExtension {
 		key: Vec<u8>,
 		value: Vec<u8>,
 	) -> Option<MembershipOf<Environment>>;
+
+	// Randomness
+	#[allow(non_snake_case)]
+	#[ink(function = 0x0300)]
+	fn randomness__random(
+		subject: Vec<u8>,
+	) -> Result<(HashOf<Environment>, BlockNumberOf<Environment>), KreivoApisErrorCode>;
+
+	// Batch: `function` must be a literal, matching `BATCH_FUNC_ID`
+	#[ink(function = 0xff00)]
+	fn batch(calls: Vec<(u16, Vec<u8>)>) -> Result<Vec<Vec<u8>>, KreivoApisErrorCode>;
 }
 
 impl ink::env::chain_extension::FromStatusCode for KreivoApisErrorCode {
@@ -54,3 +54,12 @@ pub trait MembershipsConfig: Config {
 
 pub type MembershipOf<T> = <T as MembershipsConfig>::Membership;
 pub type RankOf<T> = <T as MembershipsConfig>::Rank;
+
+// Randomness
+pub trait RandomnessConfig: Config {
+	type Hash: 'static + scale::Codec + Clone + PartialEq + Eq + core::fmt::Debug;
+	type BlockNumber: 'static + scale::Codec + Copy + PartialEq + Eq + core::fmt::Debug;
+}
+
+pub type HashOf<T> = <T as RandomnessConfig>::Hash;
+pub type BlockNumberOf<T> = <T as RandomnessConfig>::BlockNumber;
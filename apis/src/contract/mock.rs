@@ -0,0 +1,812 @@
+//! # Mock
+//!
+//! An in-memory implementation of the Kreivo APIs, meant to be used by contract
+//! developers in off-chain (`#[ink::test]`) tests.
+//!
+//! The mock is registered as an off-chain [`ChainExtension`] handler, so
+//! contracts using [`KreivoApi`][crate::KreivoApi] don't need any changes to be
+//! tested. Its state can be seeded before running the test, and inspected (or
+//! mutated) afterwards through the handle returned by [`register`]:
+//!
+//! ```ignore
+//! let state = kreivo_apis::mock::register(
+//! 	MockState::new(contract, caller)
+//! 		.with_merchant_id(1)
+//! 		.with_balance(asset, caller, 100)
+//! 		.with_membership(1, membership, rank),
+//! );
+//!
+//! // ... call the contract ...
+//!
+//! assert_eq!(state.borrow().balance(&asset, &contract), 10);
+//! ```
+
+use super::config::{
+	AccountIdOf, AssetBalanceOf, AssetIdOf, BlockNumberOf, HashOf, InventoryIdOf, ItemIdOf, ItemOf, ItemPriceOf,
+	MembershipOf, RankOf,
+};
+use crate::apis::{
+	AssetsApiError, KreivoApisError, KreivoApisErrorCode, ListingsApiError, MembershipsApiError, RandomnessApiError,
+	BATCH_FUNC_ID,
+};
+use crate::KreivoApiEnvironment;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use frame_contrib_traits::listings::item::Item;
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::env::test::ChainExtension;
+use ink::prelude::{collections::BTreeMap, vec::Vec};
+use ink::scale::{Decode, Encode};
+use virto_common::CommunityId;
+
+type Env = KreivoApiEnvironment;
+type AccountId = AccountIdOf<Env>;
+type AssetId = AssetIdOf<Env>;
+type Balance = AssetBalanceOf<Env>;
+type InventoryId = InventoryIdOf<Env>;
+type ItemId = ItemIdOf<Env>;
+type ItemPrice = ItemPriceOf<Env>;
+type MembershipId = MembershipOf<Env>;
+type Rank = RankOf<Env>;
+type BlockNumber = BlockNumberOf<Env>;
+
+/// The identifier of the chain extension, as declared by
+/// [`ChainExtension`][super::chain_extension::ChainExtension].
+const EXTENSION_ID: u16 = 0;
+
+/// The maximum length of the subject of a random value, as enforced by the
+/// runtime.
+const MAX_SUBJECT_LENGTH: usize = 256;
+
+#[derive(Clone, Default)]
+struct MockInventory {
+	active: bool,
+	metadata: Option<Vec<u8>>,
+	attributes: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+#[derive(Clone)]
+struct MockItem {
+	name: Vec<u8>,
+	owner: AccountId,
+	price: Option<ItemPrice>,
+	transferable: bool,
+	can_resell: bool,
+	metadata: Option<Vec<u8>>,
+	attributes: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl MockItem {
+	fn new(name: Vec<u8>, owner: AccountId, price: Option<ItemPrice>) -> Self {
+		Self {
+			name,
+			owner,
+			price,
+			transferable: true,
+			can_resell: true,
+			metadata: None,
+			attributes: BTreeMap::new(),
+		}
+	}
+
+	fn as_item(&self) -> ItemOf<Env> {
+		Item {
+			name: self.name.clone(),
+			owner: self.owner,
+			price: self.price.clone(),
+		}
+	}
+}
+
+#[derive(Clone)]
+struct MockMembership {
+	owner: Option<AccountId>,
+	rank: Rank,
+	attributes: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+/// The in-memory state of the Kreivo APIs.
+#[derive(Clone)]
+pub struct MockState {
+	/// The account of the contract being tested.
+	pub contract: AccountId,
+	/// The account calling into the contract. Used when depositing assets.
+	pub caller: AccountId,
+	/// The merchant id associated to the contract (which is also its group),
+	/// if any. Without it, the listings and memberships APIs fail the same way
+	/// they do on-chain.
+	pub merchant_id: Option<CommunityId>,
+	balances: Vec<(AssetId, AccountId, Balance)>,
+	inventories: BTreeMap<InventoryId, MockInventory>,
+	items: BTreeMap<(InventoryId, ItemId), MockItem>,
+	memberships: BTreeMap<(CommunityId, MembershipId), MockMembership>,
+	randomness: Option<([u8; 32], BlockNumber)>,
+}
+
+impl MockState {
+	pub fn new(contract: AccountId, caller: AccountId) -> Self {
+		Self {
+			contract,
+			caller,
+			merchant_id: None,
+			balances: Vec::new(),
+			inventories: BTreeMap::new(),
+			items: BTreeMap::new(),
+			memberships: BTreeMap::new(),
+			randomness: None,
+		}
+	}
+
+	// Seeding
+
+	/// Associates a merchant id (and group) to the contract.
+	pub fn with_merchant_id(mut self, merchant_id: CommunityId) -> Self {
+		self.merchant_id = Some(merchant_id);
+		self
+	}
+
+	/// Sets the balance of an asset account.
+	pub fn with_balance(mut self, asset: AssetId, who: AccountId, amount: Balance) -> Self {
+		self.set_balance(&asset, &who, amount);
+		self
+	}
+
+	/// Creates an active inventory for the merchant.
+	pub fn with_inventory(mut self, id: InventoryId) -> Self {
+		self.insert_inventory(id);
+		self
+	}
+
+	/// Publishes an item (owned by the contract) in an existing inventory.
+	pub fn with_item(mut self, inventory_id: InventoryId, id: ItemId, name: Vec<u8>, price: Option<ItemPrice>) -> Self {
+		let owner = self.contract;
+		self.items.insert((inventory_id, id), MockItem::new(name, owner, price));
+		self
+	}
+
+	/// Adds an available (unassigned) membership to a group.
+	pub fn with_membership(mut self, group: CommunityId, id: MembershipId, rank: Rank) -> Self {
+		self.memberships.insert(
+			(group, id),
+			MockMembership {
+				owner: None,
+				rank,
+				attributes: BTreeMap::new(),
+			},
+		);
+		self
+	}
+
+	/// Adds a membership to a group, already assigned to `who`.
+	pub fn with_member(mut self, group: CommunityId, id: MembershipId, who: AccountId, rank: Rank) -> Self {
+		self.memberships.insert(
+			(group, id),
+			MockMembership {
+				owner: Some(who),
+				rank,
+				attributes: BTreeMap::new(),
+			},
+		);
+		self
+	}
+
+	/// Sets the seed the random values are derived from, and the block number
+	/// since which it is known. Without it, the randomness APIs fail the same
+	/// way they do on-chain before the first seed.
+	pub fn with_randomness(mut self, seed: [u8; 32], known_since: BlockNumber) -> Self {
+		self.randomness = Some((seed, known_since));
+		self
+	}
+
+	// Inspecting
+
+	/// Returns the balance of an asset account.
+	pub fn balance(&self, asset: &AssetId, who: &AccountId) -> Balance {
+		self.balances
+			.iter()
+			.find(|(a, w, _)| a == asset && w == who)
+			.map(|(_, _, amount)| *amount)
+			.unwrap_or_default()
+	}
+
+	/// Returns whether an inventory exists and is active.
+	pub fn inventory_is_active(&self, id: &InventoryId) -> bool {
+		self.inventories.get(id).is_some_and(|inventory| inventory.active)
+	}
+
+	/// Returns an item, if it exists.
+	pub fn item(&self, inventory_id: &InventoryId, id: &ItemId) -> Option<ItemOf<Env>> {
+		self.items.get(&(*inventory_id, *id)).map(MockItem::as_item)
+	}
+
+	/// Returns the owner of a membership of a group, if assigned.
+	pub fn membership_owner(&self, group: CommunityId, id: &MembershipId) -> Option<AccountId> {
+		self.memberships.get(&(group, *id)).and_then(|m| m.owner)
+	}
+}
+
+/// Registers the mock as the off-chain handler of the Kreivo chain extension,
+/// returning a handle to its state.
+pub fn register(state: MockState) -> Rc<RefCell<MockState>> {
+	let state = Rc::new(RefCell::new(state));
+	ink::env::test::register_chain_extension(MockChainExtension(state.clone()));
+	state
+}
+
+struct MockChainExtension(Rc<RefCell<MockState>>);
+
+impl ChainExtension for MockChainExtension {
+	fn ext_id(&self) -> u16 {
+		EXTENSION_ID
+	}
+
+	fn call(&mut self, func_id: u16, input: &[u8], output: &mut Vec<u8>) -> u32 {
+		let mut state = self.0.borrow_mut();
+		match state.dispatch(func_id, input) {
+			Ok(result) => {
+				output.extend(result);
+				0
+			}
+			Err(error) => KreivoApisErrorCode::from(error).into(),
+		}
+	}
+}
+
+/// Decodes the input of a call, failing the same way the runtime does when
+/// it's malformed.
+fn decode<T: Decode>(mut input: &[u8]) -> Result<T, KreivoApisError> {
+	T::decode(&mut input).map_err(|_| KreivoApisError::InvalidInput)
+}
+
+type DispatchResult = Result<Vec<u8>, KreivoApisError>;
+
+impl MockState {
+	fn dispatch(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		if func_id != BATCH_FUNC_ID {
+			return self.dispatch_single(func_id, input);
+		}
+
+		// Batches are all-or-nothing: keep a snapshot to revert to in case any call
+		// fails.
+		let calls: Vec<(u16, Vec<u8>)> = decode(input)?;
+		let snapshot = self.clone();
+		let results = calls
+			.into_iter()
+			.map(|(func_id, input)| self.dispatch_single(func_id, &input))
+			.collect::<Result<Vec<_>, _>>();
+		if results.is_err() {
+			*self = snapshot;
+		}
+		results.map(|r| r.encode())
+	}
+
+	fn dispatch_single(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		match func_id {
+			0x0000..0x0100 => self.dispatch_assets(func_id, input),
+			0x0100..0x0200 => self.dispatch_listings(func_id, input),
+			0x0200..0x0300 => self.dispatch_memberships(func_id, input),
+			0x0300..0x0400 => self.dispatch_randomness(func_id, input),
+			_ => Err(KreivoApisError::InvalidInput),
+		}
+	}
+
+	fn set_balance(&mut self, asset: &AssetId, who: &AccountId, amount: Balance) {
+		match self.balances.iter_mut().find(|(a, w, _)| a == asset && w == who) {
+			Some((_, _, balance)) => *balance = amount,
+			None => self.balances.push((*asset, *who, amount)),
+		}
+	}
+
+	fn do_transfer(&mut self, asset: AssetId, from: AccountId, to: AccountId, amount: Balance) -> Option<Balance> {
+		let from_balance = self.balance(&asset, &from).checked_sub(amount)?;
+		let to_balance = self.balance(&asset, &to).checked_add(amount)?;
+		self.set_balance(&asset, &from, from_balance);
+		self.set_balance(&asset, &to, to_balance);
+		Some(amount)
+	}
+
+	fn dispatch_assets(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		match func_id {
+			0x0000 => {
+				let (asset, who): (AssetId, AccountId) = decode(input)?;
+				Ok(self.balance(&asset, &who).encode())
+			}
+			0x0001 => {
+				let (asset, amount): (AssetId, Balance) = decode(input)?;
+				self.do_transfer(asset, self.caller, self.contract, amount)
+					.map(|v| v.encode())
+					.ok_or(AssetsApiError::CannotDeposit.into())
+			}
+			0x0002 => {
+				let (asset, amount, beneficiary): (AssetId, Balance, AccountId) = decode(input)?;
+				self.do_transfer(asset, self.contract, beneficiary, amount)
+					.map(|v| v.encode())
+					.ok_or(AssetsApiError::CannotTransfer.into())
+			}
+			_ => Err(KreivoApisError::InvalidInput),
+		}
+	}
+
+	fn active_inventory(&mut self, id: &InventoryId) -> Result<&mut MockInventory, KreivoApisError> {
+		let inventory = self.inventories.get_mut(id).ok_or(ListingsApiError::UnknownInventory)?;
+		if !inventory.active {
+			Err(ListingsApiError::ArchivedInventory)?
+		}
+		Ok(inventory)
+	}
+
+	fn item_mut(
+		&mut self,
+		inventory_id: InventoryId,
+		id: ItemId,
+		error: ListingsApiError,
+	) -> Result<&mut MockItem, KreivoApisError> {
+		self.items.get_mut(&(inventory_id, id)).ok_or(error.into())
+	}
+
+	fn dispatch_listings(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		let has_merchant_id = self.merchant_id.is_some();
+
+		// Queries don't fail when there's no merchant id.
+		match func_id {
+			0x0100 => {
+				let id: InventoryId = decode(input)?;
+				return Ok((has_merchant_id && self.inventories.contains_key(&id)).encode());
+			}
+			0x0101 => {
+				let id: InventoryId = decode(input)?;
+				return Ok((has_merchant_id && self.inventory_is_active(&id)).encode());
+			}
+			0x0102 => {
+				let (id, key): (InventoryId, Vec<u8>) = decode(input)?;
+				let value = self
+					.inventories
+					.get(&id)
+					.filter(|_| has_merchant_id)
+					.and_then(|inventory| inventory.attributes.get(&key).cloned());
+				return Ok(value.encode());
+			}
+			0x0110 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				return Ok(self.item(&inventory_id, &id).filter(|_| has_merchant_id).encode());
+			}
+			0x0111 => {
+				let (inventory_id, id, key): (InventoryId, ItemId, Vec<u8>) = decode(input)?;
+				let value = self
+					.items
+					.get(&(inventory_id, id))
+					.filter(|_| has_merchant_id)
+					.and_then(|item| item.attributes.get(&key).cloned());
+				return Ok(value.encode());
+			}
+			0x0112 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				let transferable = self
+					.items
+					.get(&(inventory_id, id))
+					.is_some_and(|item| item.transferable);
+				return Ok((has_merchant_id && transferable).encode());
+			}
+			0x0113 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				let can_resell = self.items.get(&(inventory_id, id)).is_some_and(|item| item.can_resell);
+				return Ok((has_merchant_id && can_resell).encode());
+			}
+			_ => {}
+		}
+
+		if !has_merchant_id {
+			Err(ListingsApiError::NoMerchantId)?
+		}
+
+		match func_id {
+			// Inventories
+			0x0103 => {
+				let id: InventoryId = decode(input)?;
+				if self.inventories.contains_key(&id) {
+					Err(ListingsApiError::FailedToCreateInventory)?
+				}
+				self.insert_inventory(id);
+			}
+			0x0104 => {
+				let id: InventoryId = decode(input)?;
+				self.active_inventory(&id)?.active = false;
+			}
+			0x0105 => {
+				let (id, key, value): (InventoryId, Vec<u8>, Vec<u8>) = decode(input)?;
+				let inventory = self
+					.inventories
+					.get_mut(&id)
+					.ok_or(ListingsApiError::UnknownInventory)?;
+				inventory.attributes.insert(key, value);
+			}
+			0x0106 => {
+				let (id, key): (InventoryId, Vec<u8>) = decode(input)?;
+				let inventory = self
+					.inventories
+					.get_mut(&id)
+					.ok_or(ListingsApiError::UnknownInventory)?;
+				inventory.attributes.remove(&key);
+			}
+			0x0107 => {
+				let (id, metadata): (InventoryId, Vec<u8>) = decode(input)?;
+				self.active_inventory(&id)?.metadata = Some(metadata);
+			}
+			0x0108 => {
+				let id: InventoryId = decode(input)?;
+				self.active_inventory(&id)?.metadata = None;
+			}
+			// Items
+			0x0114 => {
+				let (inventory_id, id, name, maybe_price): (InventoryId, ItemId, Vec<u8>, Option<ItemPrice>) =
+					decode(input)?;
+				if !self.inventory_is_active(&inventory_id) || self.items.contains_key(&(inventory_id, id)) {
+					Err(ListingsApiError::FailedToPublishItem)?
+				}
+				let owner = self.contract;
+				self.items
+					.insert((inventory_id, id), MockItem::new(name, owner, maybe_price));
+			}
+			0x0115 => {
+				let (inventory_id, id, price): (InventoryId, ItemId, ItemPrice) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetAttribute)?
+					.price = Some(price);
+			}
+			0x0116 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetAttribute)?
+					.price = None;
+			}
+			0x0117 | 0x0118 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetNotForResale)?
+					.can_resell = func_id == 0x0117;
+			}
+			0x0119 | 0x011a => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetTransferable)?
+					.transferable = func_id == 0x0119;
+			}
+			0x011b => {
+				let (inventory_id, id, key, value): (InventoryId, ItemId, Vec<u8>, Vec<u8>) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetAttribute)?
+					.attributes
+					.insert(key, value);
+			}
+			0x011c => {
+				let (inventory_id, id, key): (InventoryId, ItemId, Vec<u8>) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetAttribute)?
+					.attributes
+					.remove(&key);
+			}
+			0x011d | 0x011e => {
+				let (inventory_id, id, beneficiary): (InventoryId, ItemId, AccountId) = decode(input)?;
+				let item = self.item_mut(inventory_id, id, ListingsApiError::CannotTransfer)?;
+				if !item.transferable {
+					Err(ListingsApiError::CannotTransfer)?
+				}
+				item.owner = beneficiary;
+			}
+			0x011f => {
+				let (inventory_id, id, metadata): (InventoryId, ItemId, Vec<u8>) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetMetadata)?
+					.metadata = Some(metadata);
+			}
+			0x0120 => {
+				let (inventory_id, id): (InventoryId, ItemId) = decode(input)?;
+				self.item_mut(inventory_id, id, ListingsApiError::FailedToSetMetadata)?
+					.metadata = None;
+			}
+			_ => Err(KreivoApisError::InvalidInput)?,
+		}
+
+		Ok(().encode())
+	}
+
+	fn insert_inventory(&mut self, id: InventoryId) {
+		self.inventories.insert(
+			id,
+			MockInventory {
+				active: true,
+				..Default::default()
+			},
+		);
+	}
+
+	/// The memberships of the contract's group. Empty if there's no group.
+	fn group_memberships(&self) -> impl Iterator<Item = (&MembershipId, &MockMembership)> {
+		let group = self.merchant_id;
+		self.memberships
+			.iter()
+			.filter(move |((g, _), _)| Some(*g) == group)
+			.map(|((_, id), m)| (id, m))
+	}
+
+	fn group_membership_mut(&mut self, id: &MembershipId) -> Result<&mut MockMembership, KreivoApisError> {
+		let group = self.merchant_id.ok_or(MembershipsApiError::NoGroup)?;
+		Ok(self
+			.memberships
+			.get_mut(&(group, *id))
+			.ok_or(MembershipsApiError::FailedToSetAttribute)?)
+	}
+
+	fn dispatch_memberships(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		match func_id {
+			0x0200 => {
+				let who: AccountId = decode(input)?;
+				let group = self.merchant_id.ok_or(MembershipsApiError::NoGroup)?;
+				let membership = self
+					.memberships
+					.iter_mut()
+					.find(|((g, _), m)| *g == group && m.owner.is_none())
+					.map(|(_, m)| m)
+					.ok_or(MembershipsApiError::CannotAddMember)?;
+				membership.owner = Some(who);
+				Ok(().encode())
+			}
+			0x0201 => {
+				let who: AccountId = decode(input)?;
+				let id = self
+					.group_memberships()
+					.find(|(_, m)| m.owner == Some(who))
+					.map(|(id, _)| *id);
+				Ok(id.encode())
+			}
+			0x0202 => {
+				let id: MembershipId = decode(input)?;
+				let rank = self.group_memberships().find(|(i, _)| **i == id).map(|(_, m)| m.rank);
+				Ok(rank.encode())
+			}
+			0x0203 => {
+				let (id, key): (MembershipId, Vec<u8>) = decode(input)?;
+				let value = self
+					.group_memberships()
+					.find(|(i, _)| **i == id)
+					.and_then(|(_, m)| m.attributes.get(&key).cloned());
+				Ok(value.encode())
+			}
+			0x0204 => {
+				let (id, key, value): (MembershipId, Vec<u8>, Vec<u8>) = decode(input)?;
+				self.group_membership_mut(&id)?.attributes.insert(key, value);
+				Ok(().encode())
+			}
+			0x0205 => {
+				let (id, key): (MembershipId, Vec<u8>) = decode(input)?;
+				self.group_membership_mut(&id)?.attributes.remove(&key);
+				Ok(().encode())
+			}
+			0x0206 => {
+				let (who, key, value): (AccountId, Vec<u8>, Vec<u8>) = decode(input)?;
+				let id = self
+					.group_memberships()
+					.find(|(_, m)| m.owner == Some(who) && m.attributes.get(&key) == Some(&value))
+					.map(|(id, _)| *id);
+				Ok(id.encode())
+			}
+			_ => Err(KreivoApisError::InvalidInput),
+		}
+	}
+
+	fn dispatch_randomness(&mut self, func_id: u16, input: &[u8]) -> DispatchResult {
+		match func_id {
+			0x0300 => {
+				let subject: Vec<u8> = decode(input)?;
+				if subject.len() > MAX_SUBJECT_LENGTH {
+					Err(KreivoApisError::InvalidInput)?
+				}
+				let (seed, known_since) = self.randomness.ok_or(RandomnessApiError::NotAvailableYet)?;
+				let mut output = <Blake2x256 as HashOutput>::Type::default();
+				ink::env::hash_encoded::<Blake2x256, _>(&(seed, subject), &mut output);
+				Ok((HashOf::<Env>::from(output), known_since).encode())
+			}
+			_ => Err(KreivoApisError::InvalidInput),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::apis::{AssetsAPI, KreivoAPI, ListingsInventoriesAPI, ListingsItemsAPI, MembershipsAPI, RandomnessAPI};
+	use crate::KreivoApi;
+	use frame_contrib_traits::listings::item::ItemPrice;
+	use ink::{prelude::vec, EnvAccess};
+	use virto_common::FungibleAssetLocation;
+
+	type Assets = <KreivoApi as KreivoAPI<EnvAccess<'static, Env>>>::Assets;
+	type Listings = <KreivoApi as KreivoAPI<EnvAccess<'static, Env>>>::Listings;
+	type Memberships = <KreivoApi as KreivoAPI<EnvAccess<'static, Env>>>::Memberships;
+	type Randomness = <KreivoApi as KreivoAPI<EnvAccess<'static, Env>>>::Randomness;
+
+	const CONTRACT: [u8; 32] = [1; 32];
+	const CALLER: [u8; 32] = [2; 32];
+	const BOB: [u8; 32] = [3; 32];
+	const ASSET: AssetId = FungibleAssetLocation::Here(1);
+	const GROUP: CommunityId = 1;
+
+	fn account(id: [u8; 32]) -> AccountId {
+		AccountId::from(id)
+	}
+
+	fn env() -> EnvAccess<'static, Env> {
+		EnvAccess::default()
+	}
+
+	#[ink::test]
+	fn seeded_balances_can_be_deposited_and_transferred() {
+		let state =
+			register(MockState::new(account(CONTRACT), account(CALLER)).with_balance(ASSET, account(CALLER), 100));
+
+		assert_eq!(Assets::balance(&env(), ASSET, &account(CALLER)), 100);
+		assert_eq!(Assets::deposit(&env(), ASSET, 30), Ok(30));
+		assert_eq!(Assets::transfer(&env(), ASSET, 10, &account(BOB)), Ok(10));
+		assert_eq!(
+			Assets::transfer(&env(), ASSET, 100, &account(BOB)),
+			Err(AssetsApiError::CannotTransfer.into())
+		);
+
+		let state = state.borrow();
+		assert_eq!(state.balance(&ASSET, &account(CALLER)), 70);
+		assert_eq!(state.balance(&ASSET, &account(CONTRACT)), 20);
+		assert_eq!(state.balance(&ASSET, &account(BOB)), 10);
+	}
+
+	#[ink::test]
+	fn listings_require_a_merchant_id() {
+		register(MockState::new(account(CONTRACT), account(CALLER)).with_inventory(1));
+
+		assert!(!Listings::inventory_exists(&env(), &1));
+		assert_eq!(Listings::create(&env(), &2), Err(ListingsApiError::NoMerchantId.into()));
+	}
+
+	#[ink::test]
+	fn items_are_published_and_transferred() {
+		let price = ItemPrice {
+			asset: ASSET,
+			amount: 10,
+		};
+		let state = register(
+			MockState::new(account(CONTRACT), account(CALLER))
+				.with_merchant_id(GROUP)
+				.with_inventory(1)
+				.with_item(1, 1, b"seeded".to_vec(), None),
+		);
+
+		assert_eq!(
+			Listings::publish(&env(), &1, &1, b"duplicated".to_vec(), None),
+			Err(ListingsApiError::FailedToPublishItem.into())
+		);
+		assert_eq!(
+			Listings::publish(&env(), &2, &1, b"unknown inventory".to_vec(), None),
+			Err(ListingsApiError::FailedToPublishItem.into())
+		);
+		assert_eq!(
+			Listings::publish(&env(), &1, &2, b"published".to_vec(), Some(price.clone())),
+			Ok(())
+		);
+		assert_eq!(Listings::item_transfer(&env(), &1, &2, &account(BOB)), Ok(()));
+
+		assert_eq!(Listings::archive(&env(), &1), Ok(()));
+		assert!(!Listings::inventory_is_active(&env(), &1));
+		assert_eq!(
+			Listings::publish(&env(), &1, &3, b"archived".to_vec(), None),
+			Err(ListingsApiError::FailedToPublishItem.into())
+		);
+
+		let item = state.borrow().item(&1, &2).expect("item was published; qed");
+		assert_eq!(item.owner, account(BOB));
+		assert_eq!(item.price, Some(price));
+	}
+
+	#[ink::test]
+	fn memberships_are_scoped_to_the_group() {
+		let rank = Rank::default();
+		let state = register(
+			MockState::new(account(CONTRACT), account(CALLER))
+				.with_merchant_id(GROUP)
+				.with_membership(GROUP + 1, 1, rank)
+				.with_member(GROUP + 1, 2, account(BOB), rank)
+				.with_membership(GROUP, 3, rank),
+		);
+
+		// Memberships of other groups are neither visible nor assignable
+		assert_eq!(Memberships::membership_of(&env(), &account(BOB)), None);
+		assert_eq!(Memberships::rank_of(&env(), &2), None);
+		assert_eq!(
+			Memberships::set_attribute(&env(), &2, &b"key", &b"value"),
+			Err(MembershipsApiError::FailedToSetAttribute.into())
+		);
+
+		assert_eq!(Memberships::assign_membership(&env(), &account(CALLER)), Ok(()));
+		assert_eq!(Memberships::membership_of(&env(), &account(CALLER)), Some(3));
+		assert_eq!(
+			Memberships::assign_membership(&env(), &account(BOB)),
+			Err(MembershipsApiError::CannotAddMember.into())
+		);
+
+		let state = state.borrow();
+		assert_eq!(state.membership_owner(GROUP, &3), Some(account(CALLER)));
+		assert_eq!(state.membership_owner(GROUP + 1, &1), None);
+	}
+
+	#[ink::test]
+	fn memberships_require_a_group() {
+		register(MockState::new(account(CONTRACT), account(CALLER)).with_membership(GROUP, 1, Rank::default()));
+
+		assert_eq!(
+			Memberships::assign_membership(&env(), &account(CALLER)),
+			Err(MembershipsApiError::NoGroup.into())
+		);
+		assert_eq!(Memberships::rank_of(&env(), &1), None);
+	}
+
+	#[ink::test]
+	fn randomness_requires_a_seed() {
+		let state = register(MockState::new(account(CONTRACT), account(CALLER)));
+
+		assert_eq!(
+			Randomness::random(&env(), b"subject"),
+			Err(RandomnessApiError::NotAvailableYet.into())
+		);
+
+		state.borrow_mut().randomness = Some(([7; 32], 10));
+		let (random, known_since) = Randomness::random(&env(), b"subject").expect("there's a seed; qed");
+		assert_eq!(known_since, 10);
+		assert_ne!(Randomness::random(&env(), b"other subject"), Ok((random, known_since)));
+		assert_eq!(
+			Randomness::random(&env(), &[0; MAX_SUBJECT_LENGTH + 1]),
+			Err(KreivoApisError::InvalidInput)
+		);
+	}
+
+	#[ink::test]
+	fn batches_are_all_or_nothing() {
+		let state =
+			register(MockState::new(account(CONTRACT), account(CALLER)).with_balance(ASSET, account(CALLER), 100));
+
+		assert_eq!(
+			KreivoApi::<Env>::batch(
+				&env(),
+				vec![
+					(0x0001, (ASSET, 30u128).encode()),
+					(0x0000, (ASSET, account(CONTRACT)).encode())
+				]
+			),
+			Ok(vec![30u128.encode(), 30u128.encode()])
+		);
+		assert_eq!(
+			KreivoApi::<Env>::batch(
+				&env(),
+				vec![
+					(0x0001, (ASSET, 30u128).encode()),
+					(0x0002, (ASSET, 100u128, account(BOB)).encode())
+				]
+			),
+			Err(AssetsApiError::CannotTransfer.into())
+		);
+
+		assert_eq!(state.borrow().balance(&ASSET, &account(CONTRACT)), 30);
+		assert_eq!(state.borrow().balance(&ASSET, &account(CALLER)), 70);
+	}
+
+	#[ink::test]
+	fn malformed_calls_are_invalid_input() {
+		let mut state = MockState::new(account(CONTRACT), account(CALLER)).with_merchant_id(GROUP);
+
+		assert_eq!(state.dispatch(0x0000, &[1, 2, 3]), Err(KreivoApisError::InvalidInput));
+		assert_eq!(state.dispatch(0x0114, &[]), Err(KreivoApisError::InvalidInput));
+		assert_eq!(state.dispatch(0x0400, &[]), Err(KreivoApisError::InvalidInput));
+		assert_eq!(
+			state.dispatch(0x02ff, &1u32.encode()),
+			Err(KreivoApisError::InvalidInput)
+		);
+		assert_eq!(
+			state.dispatch(BATCH_FUNC_ID, &[0xff]),
+			Err(KreivoApisError::InvalidInput)
+		);
+		assert_eq!(
+			state.dispatch(BATCH_FUNC_ID, &vec![(0x0400u16, Vec::<u8>::new())].encode()),
+			Err(KreivoApisError::InvalidInput)
+		);
+	}
+}
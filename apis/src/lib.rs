@@ -11,6 +11,12 @@
 //!
 //! - **[`AssetsAPI`][apis::AssetsAPI]:** These APIs can facilitate transactions
 //!   regarding assets.
+//!
+//! ## Testing
+//!
+//! Enabling the `mock` feature exposes the `mock` module: an in-memory
+//! implementation of the APIs that can be registered to test contracts
+//! off-chain.
 
 extern crate alloc;
 extern crate core;
@@ -21,7 +27,13 @@ mod contract;
 #[cfg(feature = "runtime")]
 mod runtime;
 
+#[cfg(feature = "mock")]
+pub use contract::mock;
 #[cfg(feature = "contract")]
 pub use contract::{KreivoApi, KreivoApiEnvironment};
+#[cfg(feature = "revive")]
+pub use runtime::{
+	AssetsPrecompile, IKreivoAssets, IKreivoListings, IKreivoMemberships, ListingsPrecompile, MembershipsPrecompile,
+};
 #[cfg(feature = "runtime")]
-pub use runtime::{Config, GroupInfo, KreivoChainExtensions, MerchantIdInfo};
+pub use runtime::{Config, GroupInfo, KreivoChainExtensions, MaybeRandomness, MerchantIdInfo};
@@ -7,7 +7,8 @@ use frame_support::DefaultNoBound;
 use pallet_contracts::chain_extension::{ChainExtension, Environment, Ext, InitState, RetVal};
 
 mod config;
-pub use config::{Config, GroupInfo, MerchantIdInfo};
+use config::AccountIdOf;
+pub use config::{Config, GroupInfo, MaybeRandomness, MerchantIdInfo};
 
 mod impls;
 use impls::*;
@@ -15,6 +16,13 @@ use impls::*;
 mod types;
 use types::*;
 
+#[cfg(feature = "revive")]
+mod precompiles;
+#[cfg(feature = "revive")]
+pub use precompiles::{
+	AssetsPrecompile, IKreivoAssets, IKreivoListings, IKreivoMemberships, ListingsPrecompile, MembershipsPrecompile,
+};
+
 mod api_impls {
 	use super::*;
 	use config::*;
@@ -22,12 +30,39 @@ mod api_impls {
 	mod assets;
 	mod listings;
 	mod memberships;
+	mod randomness;
 	pub use assets::*;
 	pub use listings::*;
 	pub use memberships::*;
+	pub use randomness::*;
 }
 use api_impls::*;
 
+/// The context in which the [`KreivoAPI`]s are called within the Runtime.
+pub trait ApiContext<T: Config> {
+	/// The account of the contract calling the APIs.
+	fn address(&self) -> &AccountIdOf<T>;
+
+	/// The account that called the contract.
+	fn caller(&self) -> Result<AccountIdOf<T>, KreivoApisError>;
+}
+
+impl<E: Ext> ApiContext<E::T> for E
+where
+	E::T: Config,
+{
+	fn address(&self) -> &AccountIdOf<E::T> {
+		Ext::address(self)
+	}
+
+	fn caller(&self) -> Result<AccountIdOf<E::T>, KreivoApisError> {
+		Ext::caller(self)
+			.account_id()
+			.cloned()
+			.map_err(|_| KreivoApisError::ExtQueryError)
+	}
+}
+
 /// A helper structure that implements [`KreivoAPI`] in the context of the
 /// Runtime.
 struct RuntimeKreivoAPI<T>(PhantomData<T>);
@@ -35,11 +70,12 @@ struct RuntimeKreivoAPI<T>(PhantomData<T>);
 impl<T, E> KreivoAPI<E> for RuntimeKreivoAPI<T>
 where
 	T: Config,
-	E: Ext<T = T>,
+	E: ApiContext<T>,
 {
 	type Assets = RuntimeAssetsAPI<T>;
 	type Listings = RuntimeListingsAPI<T>;
 	type Memberships = RuntimeMembershipsAPI<T>;
+	type Randomness = RuntimeRandomnessAPI<T>;
 }
 
 /// A [`ChainExtension`] that implements the [`KreivoAPI`]s.
@@ -56,13 +92,22 @@ where
 	) -> pallet_contracts::chain_extension::Result<RetVal> {
 		let mut env = env.buf_in_buf_out();
 
-		let request: ApiInfo<_> = ApiInfo::<T>::try_from(&mut env)?;
-
-		let result = match request.clone() {
-			ApiInfo::Assets(ref api_info) => api_info.call(env.ext()),
-			ApiInfo::Listings(ref api_info) => api_info.call(env.ext()),
-			ApiInfo::Memberships(ref api_info) => api_info.call(env.ext()),
+		// A malformed request is a contract error, not a reason to trap.
+		let request: ApiInfo<_> = match ApiInfo::<T>::try_from(&mut env) {
+			Ok(request) => request,
+			Err(error) => {
+				log::debug!(
+					target: "chainx",
+					"call(func_id: {:#06x}) failed to read input: {error:?}",
+					env.func_id(),
+				);
+				let error_code: KreivoApisErrorCode = KreivoApisError::InvalidInput.into();
+				return Ok(RetVal::Converging(error_code.into()));
+			}
 		};
+		env.charge_weight(request.weight(env.func_id()))?;
+
+		let result = request.call(env.ext());
 
 		log::trace!(
 			target: "chainx",
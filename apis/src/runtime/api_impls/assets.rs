@@ -12,7 +12,7 @@ pub struct RuntimeAssetsAPI<T>(PhantomData<T>);
 impl<T, E> AssetsAPI<E> for RuntimeAssetsAPI<T>
 where
 	T: Config,
-	E: Ext<T = T>,
+	E: ApiContext<T>,
 {
 	type AccountId = T::AccountId;
 	type AssetId = <T::Assets as Inspect<T::AccountId>>::AssetId;
@@ -23,11 +23,7 @@ where
 	}
 
 	fn deposit(e: &E, asset: Self::AssetId, amount: Self::Balance) -> Result<Self::Balance, KreivoApisError> {
-		let caller = e
-			.caller()
-			.account_id()
-			.map_err(|_| KreivoApisError::ExtQueryError)?
-			.clone();
+		let caller = e.caller()?;
 		T::Assets::transfer(asset, &caller, e.address(), amount, Preservation::Preserve)
 			.map_err(|_| AssetsApiError::CannotDeposit.into())
 	}
@@ -7,14 +7,14 @@ use frame_contrib_traits::listings::{item::ItemPrice, *};
 pub struct RuntimeListingsAPI<T>(PhantomData<T>);
 
 impl<T: Config> RuntimeListingsAPI<T> {
-	fn merchant_id<E: Ext<T = T>>(ext: &E) -> Option<MerchantIdOf<T>> {
+	fn merchant_id<E: ApiContext<T>>(ext: &E) -> Option<MerchantIdOf<T>> {
 		T::MerchantIdInfo::maybe_merchant_id(ext.address())
 	}
 }
 
 impl<T: Config, E> ListingsInventoriesAPI<E> for RuntimeListingsAPI<T>
 where
-	E: Ext<T = T>,
+	E: ApiContext<T>,
 {
 	type InventoryId = <<T as Config>::Listings as InspectItem<AccountIdOf<T>>>::InventoryId;
 
@@ -120,7 +120,7 @@ type ItemIdOf<T> = <<T as Config>::Listings as InspectItem<AccountIdOf<T>>>::Ite
 
 impl<T: Config, E> ListingsItemsAPI<E> for RuntimeListingsAPI<T>
 where
-	E: Ext<T = T>,
+	E: ApiContext<T>,
 {
 	type AccountId = AccountIdOf<T>;
 	type InventoryId = InventoryIdOf<T>;
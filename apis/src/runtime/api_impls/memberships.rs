@@ -1,10 +1,10 @@
 use crate::apis::{KreivoApisError, MembershipsAPI, MembershipsApiError};
 use crate::runtime::config::{GroupInfo, MembershipOf};
+use crate::runtime::ApiContext;
 use crate::Config;
 use core::marker::PhantomData;
 use frame_contrib_traits::memberships::{Attributes, GenericRank, InspectEnumerable, Manager, Rank};
 use frame_support::Parameter;
-use pallet_contracts::chain_extension::Ext;
 use parity_scale_codec::Encode;
 
 /// A helper structure that implements [`MembershipsAPI`] in the context of the
@@ -14,7 +14,7 @@ pub struct RuntimeMembershipsAPI<T>(PhantomData<T>);
 impl<T, Env> MembershipsAPI<Env> for RuntimeMembershipsAPI<T>
 where
 	T: Config,
-	Env: Ext<T = T>,
+	Env: ApiContext<T>,
 {
 	type AccountId = T::AccountId;
 	type MembershipId = MembershipOf<T>;
@@ -0,0 +1,22 @@
+use super::*;
+
+use crate::apis::RandomnessApiError;
+use crate::runtime::config::MaybeRandomness;
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// A helper structure that implements [`RandomnessAPI`] in the context of the
+/// Runtime.
+pub struct RuntimeRandomnessAPI<T>(PhantomData<T>);
+
+impl<T, E> RandomnessAPI<E> for RuntimeRandomnessAPI<T>
+where
+	T: Config,
+	E: ApiContext<T>,
+{
+	type Hash = T::Hash;
+	type BlockNumber = BlockNumberFor<T>;
+
+	fn random(_: &E, subject: &[u8]) -> Result<(Self::Hash, Self::BlockNumber), KreivoApisError> {
+		<T as Config>::Randomness::maybe_random(subject).ok_or(RandomnessApiError::NotAvailableYet.into())
+	}
+}
@@ -1,8 +1,11 @@
 use frame_contrib_traits::listings;
 use frame_contrib_traits::listings::ListingsIdentifier;
 use frame_contrib_traits::memberships;
-use frame_support::traits::{fungible, fungibles};
+use frame_support::sp_runtime::traits::Convert;
+use frame_support::traits::{fungible, fungibles, Get};
+use frame_support::weights::Weight;
 use frame_support::Parameter;
+use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::MaxEncodedLen;
 
 pub trait Config: pallet_contracts::Config
@@ -33,12 +36,23 @@ where
 		>;
 	/// A type that implements the `GroupInfo` trait.
 	type GroupInfo: GroupInfo<AccountIdOf<Self>, Group = GroupOf<Self>>;
+	/// The source of randomness of the randomness APIs.
+	type Randomness: MaybeRandomness<Self::Hash, BlockNumberFor<Self>>;
 	/// A type that implements the memberships' APIs.
 	type Memberships: memberships::Inspect<Self::AccountId>
 		+ memberships::InspectEnumerable<Self::AccountId>
 		+ memberships::Attributes<Self::AccountId>
 		+ memberships::Manager<Self::AccountId>
 		+ memberships::Rank<Self::AccountId>;
+	/// The maximum number of calls that can be dispatched in a single batch.
+	type MaxBatchedCalls: Get<u32>;
+	/// The weight charged for a call to the APIs with the given `func_id`,
+	/// including every call dispatched within a batch. It must cover the
+	/// heaviest dispatch the call can map to.
+	type CallWeight: Convert<u16, Weight>;
+	/// The weight charged for every byte of input decoded before the call is
+	/// dispatched.
+	type InputByteWeight: Get<Weight>;
 }
 
 pub trait MerchantIdInfo<AccountId> {
@@ -53,6 +67,13 @@ pub trait GroupInfo<AccountId> {
 	fn maybe_group(who: &AccountId) -> Option<Self::Group>;
 }
 
+/// A source of randomness that is not available until it's first seeded.
+pub trait MaybeRandomness<Output, BlockNumber> {
+	/// Returns a random value for `subject`, along with the block number since
+	/// which it is known, or `None` if there's no seed yet.
+	fn maybe_random(subject: &[u8]) -> Option<(Output, BlockNumber)>;
+}
+
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type MerchantIdOf<T> = <<T as Config>::MerchantIdInfo as MerchantIdInfo<AccountIdOf<T>>>::MerchantId;
 pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::AssetId;
@@ -1,9 +1,46 @@
 use super::*;
 
+use frame_support::storage::{with_transaction_opaque, TransactionOutcome};
+
 mod assets;
 mod listings;
 mod memberships;
+mod randomness;
 
 pub trait ChainExtensionDispatch<E> {
 	fn call(&self, ext: &E) -> Result<Vec<u8>, KreivoApisError>;
 }
+
+impl<T, E> ChainExtensionDispatch<E> for ApiInfo<T>
+where
+	T: Config,
+	E: Ext<T = T>,
+{
+	fn call(&self, ext: &E) -> Result<Vec<u8>, KreivoApisError> {
+		match self {
+			ApiInfo::Assets(api_info) => api_info.call(ext),
+			ApiInfo::Listings(api_info) => api_info.call(ext),
+			ApiInfo::Memberships(api_info) => api_info.call(ext),
+			ApiInfo::Randomness(api_info) => api_info.call(ext),
+			// Batched calls are dispatched within a single storage layer: either all
+			// of them succeed, or every change is reverted.
+			ApiInfo::Batch(calls) => with_transaction_opaque(|| {
+				let mut results = Vec::with_capacity(calls.len());
+				for (index, (_, call)) in calls.iter().enumerate() {
+					match call.call(ext) {
+						Ok(result) => results.push(result),
+						Err(error) => {
+							log::debug!(
+								target: "chainx",
+								"batch: call #{index} failed with {error:?}, reverting",
+							);
+							return TransactionOutcome::Rollback(Err(error));
+						}
+					}
+				}
+				TransactionOutcome::Commit(Ok(results.encode()))
+			})
+			.map_err(|_| KreivoApisError::UnknownError)?,
+		}
+	}
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+type RandomnessAPIOf<T, E> = <RuntimeKreivoAPI<T> as KreivoAPI<E>>::Randomness;
+
+impl<T, E> ChainExtensionDispatch<E> for RandomnessApiInfo
+where
+	T: Config,
+	E: Ext<T = T>,
+{
+	fn call(&self, ext: &E) -> Result<Vec<u8>, KreivoApisError> {
+		match self {
+			RandomnessApiInfo::Random { subject } => RandomnessAPIOf::<T, E>::random(ext, subject).map(|r| r.encode()),
+		}
+	}
+}
@@ -0,0 +1,454 @@
+//! # Precompiles
+//!
+//! Exposes the [`KreivoAPI`]s to `pallet-revive` contracts as precompiles at
+//! fixed addresses. Their Solidity interfaces are found under the `sol`
+//! directory of this crate.
+//!
+//! In the context of a precompile, the contract calling the precompile takes
+//! the place of the contract address (i.e. the merchant or group). The caller
+//! of that contract is not known to the precompile, so APIs acting on behalf
+//! of the caller (like [`AssetsAPI::deposit`]) are not exposed: the origin of
+//! the transaction is not a safe replacement, as any contract it calls into
+//! (directly or not) would be able to move its funds.
+
+use super::*;
+
+use alloc::format;
+use config::{AssetBalanceOf, AssetIdOf, MembershipOf};
+use core::num::NonZero;
+use frame_contrib_traits::listings::{item::ItemPrice, InspectItem};
+use frame_support::{sp_runtime::traits::Convert, traits::ConstU32, BoundedVec};
+use pallet_revive::precompiles::{
+	alloy::{
+		self,
+		primitives::{Address, Bytes, U256},
+		sol_types::{Revert, SolValue},
+	},
+	AddressMatcher, Error, Ext as PrecompileExt, Precompile, H160,
+};
+use pallet_revive::AddressMapper;
+
+alloy::sol!("sol/IKreivoAssets.sol");
+alloy::sol!("sol/IKreivoListings.sol");
+alloy::sol!("sol/IKreivoMemberships.sol");
+
+use IKreivoAssets::IKreivoAssetsCalls;
+use IKreivoListings::IKreivoListingsCalls;
+use IKreivoMemberships::IKreivoMembershipsCalls;
+
+type InventoryIdOf<T> = <<T as Config>::Listings as InspectItem<AccountIdOf<T>>>::InventoryId;
+type ItemIdOf<T> = <<T as Config>::Listings as InspectItem<AccountIdOf<T>>>::ItemId;
+type KeyOf = BoundedVec<u8, ConstU32<256>>;
+
+/// The [`ApiContext`] of a call to a precompile.
+struct PrecompileContext<T: Config> {
+	address: AccountIdOf<T>,
+}
+
+impl<T: Config> ApiContext<T> for PrecompileContext<T> {
+	fn address(&self) -> &AccountIdOf<T> {
+		&self.address
+	}
+
+	fn caller(&self) -> Result<AccountIdOf<T>, KreivoApisError> {
+		Err(KreivoApisError::ExtQueryError)
+	}
+}
+
+impl<T: Config + pallet_revive::Config> PrecompileContext<T> {
+	/// Builds the context from the precompile environment.
+	fn new(env: &mut impl PrecompileExt<T = T>, mutates: bool) -> Result<Self, Error> {
+		if mutates && env.is_read_only() {
+			Err(pallet_revive::Error::<T>::StateChangeDenied)?
+		}
+
+		Ok(Self {
+			address: env.caller().account_id()?.clone(),
+		})
+	}
+}
+
+/// Charges the weight of the API call a precompile call maps to.
+fn charge<T: Config + pallet_revive::Config>(env: &mut impl PrecompileExt<T = T>, func_id: u16) -> Result<(), Error> {
+	env.charge(T::CallWeight::convert(func_id))?;
+	Ok(())
+}
+
+impl IKreivoAssetsCalls {
+	/// The `func_id` of the [`AssetsAPI`] call this call maps to.
+	fn func_id(&self) -> u16 {
+		match self {
+			Self::balance(_) => 0x0000,
+			Self::transfer(_) => 0x0002,
+		}
+	}
+}
+
+impl IKreivoListingsCalls {
+	/// The `func_id` of the [`ListingsInventoriesAPI`] or [`ListingsItemsAPI`]
+	/// call this call maps to.
+	fn func_id(&self) -> u16 {
+		match self {
+			Self::inventoryExists(_) => 0x0100,
+			Self::inventoryIsActive(_) => 0x0101,
+			Self::inventoryAttribute(_) => 0x0102,
+			Self::createInventory(_) => 0x0103,
+			Self::archiveInventory(_) => 0x0104,
+			Self::setInventoryAttribute(_) => 0x0105,
+			Self::clearInventoryAttribute(_) => 0x0106,
+			Self::setInventoryMetadata(_) => 0x0107,
+			Self::clearInventoryMetadata(_) => 0x0108,
+			Self::item(_) => 0x0110,
+			Self::itemAttribute(_) => 0x0111,
+			Self::itemTransferable(_) => 0x0112,
+			Self::itemCanResell(_) => 0x0113,
+			Self::publishItem(_) => 0x0114,
+			Self::setItemPrice(_) => 0x0115,
+			Self::clearItemPrice(_) => 0x0116,
+			Self::enableItemResell(_) => 0x0117,
+			Self::disableItemResell(_) => 0x0118,
+			Self::enableItemTransfer(_) => 0x0119,
+			Self::disableItemTransfer(_) => 0x011a,
+			Self::setItemAttribute(_) => 0x011b,
+			Self::clearItemAttribute(_) => 0x011c,
+			Self::transferItem(_) => 0x011d,
+			Self::creatorTransferItem(_) => 0x011e,
+			Self::setItemMetadata(_) => 0x011f,
+			Self::clearItemMetadata(_) => 0x0120,
+		}
+	}
+}
+
+impl IKreivoMembershipsCalls {
+	/// The `func_id` of the [`MembershipsAPI`] call this call maps to.
+	fn func_id(&self) -> u16 {
+		match self {
+			Self::assignMembership(_) => 0x0200,
+			Self::membershipOf(_) => 0x0201,
+			Self::rankOf(_) => 0x0202,
+			Self::attribute(_) => 0x0203,
+			Self::setAttribute(_) => 0x0204,
+			Self::clearAttribute(_) => 0x0205,
+			Self::filterMembership(_) => 0x0206,
+		}
+	}
+}
+
+fn revert(error: KreivoApisError) -> Error {
+	let code: u32 = KreivoApisErrorCode::from(error).into();
+	Error::Revert(Revert {
+		reason: format!("KreivoApisError({code:#010x})"),
+	})
+}
+
+fn invalid_input() -> Error {
+	Error::Revert(Revert {
+		reason: "Invalid input".into(),
+	})
+}
+
+fn account_id<T: pallet_revive::Config>(address: &Address) -> AccountIdOf<T> {
+	T::AddressMapper::to_account_id(&H160::from(address.into_array()))
+}
+
+fn decode<V: Decode>(bytes: &Bytes) -> Result<V, Error> {
+	V::decode(&mut &bytes[..]).map_err(|_| invalid_input())
+}
+
+fn bounded(bytes: &Bytes) -> Result<KeyOf, Error> {
+	bytes.to_vec().try_into().map_err(|_| invalid_input())
+}
+
+fn balance<T: Config>(amount: U256) -> Result<AssetBalanceOf<T>, Error>
+where
+	AssetBalanceOf<T>: TryFrom<U256>,
+{
+	amount.try_into().map_err(|_| invalid_input())
+}
+
+/// Exposes the [`AssetsAPI`] at `0x0000000000000000000000000000000000005000`.
+pub struct AssetsPrecompile<T>(PhantomData<T>);
+
+impl<T> Precompile for AssetsPrecompile<T>
+where
+	T: Config + pallet_revive::Config,
+	AssetBalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+	type T = T;
+	type Interface = IKreivoAssetsCalls;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(0x5000).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(_: &[u8; 20], input: &Self::Interface, env: &mut impl PrecompileExt<T = T>) -> Result<Vec<u8>, Error> {
+		type Api<T> = RuntimeAssetsAPI<T>;
+		type Ctx<T> = PrecompileContext<T>;
+		charge(env, input.func_id())?;
+
+		match input {
+			IKreivoAssetsCalls::balance(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let balance =
+					Api::<T>::balance(&ctx, decode::<AssetIdOf<T>>(&call.asset)?, &account_id::<T>(&call.who));
+				Ok(Into::<U256>::into(balance).abi_encode())
+			}
+			IKreivoAssetsCalls::transfer(call) => {
+				let ctx = Ctx::new(env, true)?;
+				Api::<T>::transfer(
+					&ctx,
+					decode(&call.asset)?,
+					balance::<T>(call.amount)?,
+					&account_id::<T>(&call.beneficiary),
+				)
+				.map(|amount| Into::<U256>::into(amount).abi_encode())
+				.map_err(revert)
+			}
+		}
+	}
+}
+
+/// Exposes the [`ListingsInventoriesAPI`] and [`ListingsItemsAPI`] at
+/// `0x0000000000000000000000000000000000005001`.
+pub struct ListingsPrecompile<T>(PhantomData<T>);
+
+impl<T> Precompile for ListingsPrecompile<T>
+where
+	T: Config + pallet_revive::Config,
+	AssetBalanceOf<T>: TryFrom<U256>,
+	InventoryIdOf<T>: From<u32>,
+	ItemIdOf<T>: From<u64>,
+{
+	type T = T;
+	type Interface = IKreivoListingsCalls;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(0x5001).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(_: &[u8; 20], input: &Self::Interface, env: &mut impl PrecompileExt<T = T>) -> Result<Vec<u8>, Error> {
+		type Api<T> = RuntimeListingsAPI<T>;
+		type Ctx<T> = PrecompileContext<T>;
+		charge(env, input.func_id())?;
+		use IKreivoListingsCalls as Call;
+
+		let done = |result: Result<(), KreivoApisError>| result.map(|_| Vec::new()).map_err(revert);
+
+		match input {
+			// Inventories
+			Call::inventoryExists(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(Api::<T>::inventory_exists(&ctx, &call.id.into()).abi_encode())
+			}
+			Call::inventoryIsActive(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(Api::<T>::inventory_is_active(&ctx, &call.id.into()).abi_encode())
+			}
+			Call::inventoryAttribute(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let key = bounded(&call.key)?;
+				let value: Option<Vec<u8>> = Api::<T>::inventory_attribute(&ctx, &call.id.into(), &key);
+				Ok(Bytes::from(value.unwrap_or_default()).abi_encode())
+			}
+			Call::createInventory(call) => done(Api::<T>::create(&Ctx::new(env, true)?, &call.id.into())),
+			Call::archiveInventory(call) => done(Api::<T>::archive(&Ctx::new(env, true)?, &call.id.into())),
+			Call::setInventoryMetadata(call) => done(Api::<T>::set_inventory_metadata(
+				&Ctx::new(env, true)?,
+				&call.id.into(),
+				&call.metadata,
+			)),
+			Call::clearInventoryMetadata(call) => done(Api::<T>::clear_inventory_metadata(
+				&Ctx::new(env, true)?,
+				&call.id.into(),
+			)),
+			Call::setInventoryAttribute(call) => done(Api::<T>::inventory_set_attribute(
+				&Ctx::new(env, true)?,
+				&call.id.into(),
+				&bounded(&call.key)?,
+				&bounded(&call.value)?,
+			)),
+			Call::clearInventoryAttribute(call) => done(Api::<T>::inventory_clear_attribute::<_, Vec<u8>>(
+				&Ctx::new(env, true)?,
+				&call.id.into(),
+				&bounded(&call.key)?,
+			)),
+			// Items
+			Call::item(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let item = Api::<T>::item(&ctx, &call.inventoryId.into(), &call.id.into());
+				Ok(Bytes::from(item.map(|item| item.encode()).unwrap_or_default()).abi_encode())
+			}
+			Call::itemAttribute(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let value: Option<Vec<u8>> =
+					Api::<T>::item_attribute(&ctx, &call.inventoryId.into(), &call.id.into(), &bounded(&call.key)?);
+				Ok(Bytes::from(value.unwrap_or_default()).abi_encode())
+			}
+			Call::itemTransferable(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(Api::<T>::item_transferable(&ctx, &call.inventoryId.into(), &call.id.into()).abi_encode())
+			}
+			Call::itemCanResell(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(Api::<T>::item_can_resell(&ctx, &call.inventoryId.into(), &call.id.into()).abi_encode())
+			}
+			Call::publishItem(call) => {
+				let maybe_price = if call.asset.is_empty() {
+					None
+				} else {
+					Some(ItemPrice {
+						asset: decode(&call.asset)?,
+						amount: balance::<T>(call.amount)?,
+					})
+				};
+				done(Api::<T>::publish(
+					&Ctx::new(env, true)?,
+					&call.inventoryId.into(),
+					&call.id.into(),
+					bounded(&call.name)?.into_inner(),
+					maybe_price,
+				))
+			}
+			Call::setItemPrice(call) => {
+				let price = ItemPrice {
+					asset: decode(&call.asset)?,
+					amount: balance::<T>(call.amount)?,
+				};
+				done(Api::<T>::set_price(
+					&Ctx::new(env, true)?,
+					&call.inventoryId.into(),
+					&call.id.into(),
+					price,
+				))
+			}
+			Call::clearItemPrice(call) => done(Api::<T>::clear_price(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::setItemMetadata(call) => done(Api::<T>::set_metadata(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+				&call.metadata,
+			)),
+			Call::clearItemMetadata(call) => done(Api::<T>::clear_metadata(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::enableItemResell(call) => done(Api::<T>::item_enable_resell(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::disableItemResell(call) => done(Api::<T>::item_disable_resell(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::enableItemTransfer(call) => done(Api::<T>::item_enable_transfer(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::disableItemTransfer(call) => done(Api::<T>::item_disable_transfer(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+			)),
+			Call::setItemAttribute(call) => done(Api::<T>::item_set_attribute(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+				&bounded(&call.key)?,
+				&bounded(&call.value)?,
+			)),
+			Call::clearItemAttribute(call) => done(Api::<T>::item_clear_attribute(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+				&bounded(&call.key)?,
+			)),
+			Call::transferItem(call) => done(Api::<T>::item_transfer(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+				&account_id::<T>(&call.beneficiary),
+			)),
+			Call::creatorTransferItem(call) => done(Api::<T>::item_creator_transfer(
+				&Ctx::new(env, true)?,
+				&call.inventoryId.into(),
+				&call.id.into(),
+				&account_id::<T>(&call.beneficiary),
+			)),
+		}
+	}
+}
+
+/// Exposes the [`MembershipsAPI`] at
+/// `0x0000000000000000000000000000000000005002`.
+pub struct MembershipsPrecompile<T>(PhantomData<T>);
+
+impl<T> Precompile for MembershipsPrecompile<T>
+where
+	T: Config + pallet_revive::Config,
+	MembershipOf<T>: From<u32> + Into<u32>,
+{
+	type T = T;
+	type Interface = IKreivoMembershipsCalls;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(0x5002).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(_: &[u8; 20], input: &Self::Interface, env: &mut impl PrecompileExt<T = T>) -> Result<Vec<u8>, Error> {
+		type Api<T> = RuntimeMembershipsAPI<T>;
+		type Ctx<T> = PrecompileContext<T>;
+		charge(env, input.func_id())?;
+		use IKreivoMembershipsCalls as Call;
+
+		let found = |maybe_id: Option<MembershipOf<T>>| match maybe_id {
+			Some(id) => (true, Into::<u32>::into(id)).abi_encode_params(),
+			None => (false, 0u32).abi_encode_params(),
+		};
+
+		match input {
+			Call::assignMembership(call) => {
+				let ctx = Ctx::new(env, true)?;
+				Api::<T>::assign_membership(&ctx, &account_id::<T>(&call.who))
+					.map(|_| Vec::new())
+					.map_err(revert)
+			}
+			Call::membershipOf(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(found(Api::<T>::membership_of(&ctx, &account_id::<T>(&call.who))))
+			}
+			Call::rankOf(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let rank = Api::<T>::rank_of(&ctx, &call.id.into());
+				Ok(Bytes::from(rank.map(|rank| rank.encode()).unwrap_or_default()).abi_encode())
+			}
+			Call::attribute(call) => {
+				let ctx = Ctx::new(env, false)?;
+				let value: Option<KeyOf> = Api::<T>::attribute(&ctx, &call.id.into(), &bounded(&call.key)?);
+				Ok(Bytes::from(value.map(|v| v.into_inner()).unwrap_or_default()).abi_encode())
+			}
+			Call::setAttribute(call) => {
+				let ctx = Ctx::new(env, true)?;
+				Api::<T>::set_attribute(&ctx, &call.id.into(), &bounded(&call.key)?, &bounded(&call.value)?)
+					.map(|_| Vec::new())
+					.map_err(revert)
+			}
+			Call::clearAttribute(call) => {
+				let ctx = Ctx::new(env, true)?;
+				Api::<T>::clear_attribute(&ctx, &call.id.into(), &bounded(&call.key)?)
+					.map(|_| Vec::new())
+					.map_err(revert)
+			}
+			Call::filterMembership(call) => {
+				let ctx = Ctx::new(env, false)?;
+				Ok(found(Api::<T>::filter_membership(
+					&ctx,
+					&account_id::<T>(&call.who),
+					&bounded(&call.key)?,
+					&bounded(&call.value)?,
+				)))
+			}
+		}
+	}
+}
@@ -1,7 +1,8 @@
 use super::*;
 use config::AccountIdOf;
-use frame_support::pallet_prelude::DispatchError;
-use frame_support::{CloneNoBound, DebugNoBound};
+use frame_support::pallet_prelude::{DispatchError, Get, MaxEncodedLen, Weight};
+use frame_support::sp_runtime::traits::Convert;
+use frame_support::{BoundedVec, CloneNoBound, DebugNoBound};
 use pallet_contracts::chain_extension::BufInBufOutState;
 
 mod assets;
@@ -12,6 +13,47 @@ pub use listings::*;
 mod memberships;
 pub use memberships::*;
 
+mod randomness;
+pub use randomness::*;
+
+/// A source for the `func_id` and the encoded input of a call to the
+/// [`KreivoAPI`]s.
+pub trait ApiInput {
+	fn func_id(&self) -> u16;
+
+	fn read_as<V: Decode + MaxEncodedLen>(&mut self) -> Result<V, DispatchError>;
+}
+
+impl<E: Ext> ApiInput for Environment<'_, '_, E, BufInBufOutState> {
+	fn func_id(&self) -> u16 {
+		Environment::func_id(self)
+	}
+
+	fn read_as<V: Decode + MaxEncodedLen>(&mut self) -> Result<V, DispatchError> {
+		Environment::read_as(self)
+	}
+}
+
+/// A single call within a batch: the `func_id` of the call, and its encoded
+/// input.
+#[derive(Encode, Decode, Clone, Debug)]
+pub struct BatchedCall {
+	func_id: u16,
+	input: Vec<u8>,
+}
+
+impl ApiInput for BatchedCall {
+	fn func_id(&self) -> u16 {
+		self.func_id
+	}
+
+	fn read_as<V: Decode + MaxEncodedLen>(&mut self) -> Result<V, DispatchError> {
+		V::decode(&mut &self.input[..]).map_err(|_| DispatchError::Other("DecodingFailed"))
+	}
+}
+
+pub type BatchedCallsOf<T> = BoundedVec<BatchedCall, <T as Config>::MaxBatchedCalls>;
+
 #[derive(CloneNoBound, DebugNoBound)]
 pub enum ApiInfo<T>
 where
@@ -20,6 +62,25 @@ where
 	Assets(AssetsApiInfo<T>),
 	Listings(ListingsApiInfo<T>),
 	Memberships(MembershipsApiInfo<T>),
+	Randomness(RandomnessApiInfo),
+	/// The calls of a batch, along with their `func_id`.
+	Batch(Vec<(u16, ApiInfo<T>)>),
+}
+
+impl<T: Config> ApiInfo<T> {
+	/// Reads a single (non-batch) call from the given input.
+	pub fn read_from(input: &mut impl ApiInput) -> Result<Self, DispatchError> {
+		match input.func_id() {
+			0x0000..0x0100 => AssetsApiInfo::read_from(input).map(|api_info| Self::Assets(api_info)),
+			0x0100..0x0200 => ListingsApiInfo::read_from(input).map(|api_info| Self::Listings(api_info)),
+			0x0200..0x0300 => MembershipsApiInfo::read_from(input).map(|api_info| Self::Memberships(api_info)),
+			0x0300..0x0400 => RandomnessApiInfo::read_from(input).map(|api_info| Self::Randomness(api_info)),
+			id => {
+				log::error!("Called an unregistered `func_id`: {id:}");
+				Err(DispatchError::Other("Unimplemented func_id"))
+			}
+		}
+	}
 }
 
 impl<T, E> TryFrom<&mut Environment<'_, '_, E, BufInBufOutState>> for ApiInfo<T>
@@ -30,14 +91,60 @@ where
 	type Error = DispatchError;
 
 	fn try_from(env: &mut Environment<'_, '_, E, BufInBufOutState>) -> Result<Self, Self::Error> {
-		match env.func_id() {
-			0x0000..0x0100 => env.try_into().map(|api_info| Self::Assets(api_info)),
-			0x0100..0x0200 => env.try_into().map(|api_info| Self::Listings(api_info)),
-			0x0200..0x0300 => env.try_into().map(|api_info| Self::Memberships(api_info)),
-			id => {
-				log::error!("Called an unregistered `func_id`: {id:}");
-				Err(DispatchError::Other("Unimplemented func_id"))
-			}
+		// Decoding is paid for before reading the input.
+		let len = env.in_len();
+		env.charge_weight(T::InputByteWeight::get().saturating_mul(len.into()))?;
+
+		if env.func_id() != BATCH_FUNC_ID {
+			return Self::read_from(env);
 		}
+
+		// The length of the batch is bounded by `MaxBatchedCalls`, and decoding the
+		// whole input was charged above, so it's safe to read it unbounded.
+		let calls: BatchedCallsOf<T> = env.read_as_unbounded(len)?;
+		calls
+			.into_iter()
+			.map(|mut call| Ok((call.func_id(), Self::read_from(&mut call)?)))
+			.collect::<Result<Vec<_>, _>>()
+			.map(Self::Batch)
+	}
+}
+
+impl<T: Config> ApiInfo<T> {
+	/// The additional weight to be charged before dispatching this request,
+	/// made with the given `func_id`. Batches are charged per element.
+	pub fn weight(&self, func_id: u16) -> Weight {
+		match self {
+			ApiInfo::Batch(calls) => calls.iter().fold(Weight::zero(), |weight, (func_id, call)| {
+				weight.saturating_add(call.weight(*func_id))
+			}),
+			_ => T::CallWeight::convert(func_id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn batched_calls_are_read_in_order() {
+		let input = vec![(0x0001u16, (7u32, 10u128).encode()), (0x0100u16, 3u32.encode())].encode();
+		let calls = Vec::<BatchedCall>::decode(&mut &input[..]).expect("valid batch; qed");
+
+		let [mut first, mut second] = <[BatchedCall; 2]>::try_from(calls).expect("two calls; qed");
+		assert_eq!(first.func_id(), 0x0001);
+		assert_eq!(first.read_as::<(u32, u128)>(), Ok((7, 10)));
+		assert_eq!(second.func_id(), 0x0100);
+		assert_eq!(second.read_as::<u32>(), Ok(3));
+	}
+
+	#[test]
+	fn malformed_batched_calls_fail_to_read() {
+		let mut call = BatchedCall {
+			func_id: 0x0001,
+			input: vec![1, 2],
+		};
+		assert!(call.read_as::<(u32, u128)>().is_err());
 	}
 }
@@ -2,7 +2,6 @@ use super::*;
 
 use frame_support::sp_runtime::DispatchError;
 use frame_support::traits::fungibles;
-use pallet_contracts::chain_extension::{BufInBufOutState, Environment, Ext};
 
 pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::AssetId;
 pub type AssetBalanceOf<T> = <<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::Balance;
@@ -24,25 +23,19 @@ pub enum AssetsApiInfo<T: Config> {
 	},
 }
 
-impl<T, E> TryFrom<&mut Environment<'_, '_, E, BufInBufOutState>> for AssetsApiInfo<T>
-where
-	T: Config,
-	E: Ext<T = T>,
-{
-	type Error = DispatchError;
-
-	fn try_from(env: &mut Environment<'_, '_, E, BufInBufOutState>) -> Result<Self, Self::Error> {
-		match env.func_id() {
+impl<T: Config> AssetsApiInfo<T> {
+	pub fn read_from(input: &mut impl ApiInput) -> Result<Self, DispatchError> {
+		match input.func_id() {
 			0x0000 => {
-				let (asset, who) = env.read_as()?;
+				let (asset, who) = input.read_as()?;
 				Ok(AssetsApiInfo::Balance { asset, who })
 			}
 			0x0001 => {
-				let (asset, amount) = env.read_as()?;
+				let (asset, amount) = input.read_as()?;
 				Ok(AssetsApiInfo::Deposit { asset, amount })
 			}
 			0x0002 => {
-				let (asset, amount, beneficiary) = env.read_as()?;
+				let (asset, amount, beneficiary) = input.read_as()?;
 				Ok(AssetsApiInfo::Transfer {
 					asset,
 					amount,
@@ -122,71 +122,65 @@ pub enum ListingsApiInfo<T: Config> {
 	},
 }
 
-impl<T, E> TryFrom<&mut Environment<'_, '_, E, BufInBufOutState>> for ListingsApiInfo<T>
-where
-	T: Config,
-	E: Ext<T = T>,
-{
-	type Error = DispatchError;
-
-	fn try_from(env: &mut Environment<'_, '_, E, BufInBufOutState>) -> Result<Self, Self::Error> {
-		match env.func_id() {
+impl<T: Config> ListingsApiInfo<T> {
+	pub fn read_from(input: &mut impl ApiInput) -> Result<Self, DispatchError> {
+		match input.func_id() {
 			// Inventories
 			0x0100 => {
-				let id = env.read_as()?;
+				let id = input.read_as()?;
 				Ok(ListingsApiInfo::InventoryExists { id })
 			}
 			0x0101 => {
-				let id = env.read_as()?;
+				let id = input.read_as()?;
 				Ok(ListingsApiInfo::InventoryIsActive { id })
 			}
 			0x0102 => {
-				let (id, key) = env.read_as()?;
+				let (id, key) = input.read_as()?;
 				Ok(ListingsApiInfo::InventoryAttribute { id, key })
 			}
 			0x0103 => {
-				let id = env.read_as()?;
+				let id = input.read_as()?;
 				Ok(ListingsApiInfo::Create { id })
 			}
 			0x0104 => {
-				let id = env.read_as()?;
+				let id = input.read_as()?;
 				Ok(ListingsApiInfo::Archive { id })
 			}
 			0x0105 => {
-				let (id, key, value) = env.read_as()?;
+				let (id, key, value) = input.read_as()?;
 				Ok(ListingsApiInfo::InventorySetAttribute { id, key, value })
 			}
 			0x0106 => {
-				let (id, key) = env.read_as()?;
+				let (id, key) = input.read_as()?;
 				Ok(ListingsApiInfo::InventoryClearAttribute { id, key })
 			}
 			0x0107 => {
-				let (inventory_id, metadata) = env.read_as()?;
+				let (inventory_id, metadata) = input.read_as()?;
 				Ok(ListingsApiInfo::SetInventoryMetadata { inventory_id, metadata })
 			}
 			0x0108 => {
-				let inventory_id = env.read_as()?;
+				let inventory_id = input.read_as()?;
 				Ok(ListingsApiInfo::ClearInventoryMetadata { inventory_id })
 			}
 			// Items
 			0x0110 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::Item { inventory_id, id })
 			}
 			0x0111 => {
-				let (inventory_id, id, key) = env.read_as()?;
+				let (inventory_id, id, key) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemAttribute { inventory_id, id, key })
 			}
 			0x0112 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemTransferable { inventory_id, id })
 			}
 			0x0113 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemCanResell { inventory_id, id })
 			}
 			0x0114 => {
-				let (inventory_id, id, name, maybe_price) = env.read_as()?;
+				let (inventory_id, id, name, maybe_price) = input.read_as()?;
 				Ok(ListingsApiInfo::Publish {
 					inventory_id,
 					id,
@@ -195,7 +189,7 @@ where
 				})
 			}
 			0x0115 => {
-				let (inventory_id, id, price) = env.read_as()?;
+				let (inventory_id, id, price) = input.read_as()?;
 				Ok(ListingsApiInfo::SetPrice {
 					inventory_id,
 					id,
@@ -203,27 +197,27 @@ where
 				})
 			}
 			0x0116 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ClearPrice { inventory_id, id })
 			}
 			0x0117 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemEnableResell { inventory_id, id })
 			}
 			0x0118 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemDisableResell { inventory_id, id })
 			}
 			0x0119 => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemEnableTransfer { inventory_id, id })
 			}
 			0x011a => {
-				let (inventory_id, id) = env.read_as()?;
+				let (inventory_id, id) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemDisableTransfer { inventory_id, id })
 			}
 			0x011b => {
-				let (inventory_id, id, key, value) = env.read_as()?;
+				let (inventory_id, id, key, value) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemSetAttribute {
 					inventory_id,
 					id,
@@ -232,11 +226,11 @@ where
 				})
 			}
 			0x011c => {
-				let (inventory_id, id, key) = env.read_as()?;
+				let (inventory_id, id, key) = input.read_as()?;
 				Ok(ListingsApiInfo::ItemClearAttribute { inventory_id, id, key })
 			}
 			0x011d => {
-				let (inventory_id, id, beneficiary) = env.read_as()?;
+				let (inventory_id, id, beneficiary) = input.read_as()?;
 				Ok(ListingsApiInfo::Transfer {
 					inventory_id,
 					id,
@@ -244,7 +238,7 @@ where
 				})
 			}
 			0x011e => {
-				let (inventory_id, id, beneficiary) = env.read_as()?;
+				let (inventory_id, id, beneficiary) = input.read_as()?;
 				Ok(ListingsApiInfo::CreatorTransfer {
 					inventory_id,
 					id,
@@ -252,7 +246,7 @@ where
 				})
 			}
 			0x011f => {
-				let (inventory_id, item_id, metadata) = env.read_as()?;
+				let (inventory_id, item_id, metadata) = input.read_as()?;
 				Ok(ListingsApiInfo::SetMetadata {
 					inventory_id,
 					item_id,
@@ -260,7 +254,7 @@ where
 				})
 			}
 			0x0120 => {
-				let (inventory_id, item_id) = env.read_as()?;
+				let (inventory_id, item_id) = input.read_as()?;
 				Ok(ListingsApiInfo::ClearMetadata { inventory_id, item_id })
 			}
 			id => {
@@ -4,7 +4,6 @@ use crate::runtime::config::MembershipOf;
 use frame_support::pallet_prelude::ConstU32;
 use frame_support::sp_runtime::DispatchError;
 use frame_support::BoundedVec;
-use pallet_contracts::chain_extension::{BufInBufOutState, Environment, Ext};
 
 #[derive(Encode, Decode, Clone, DebugNoBound)]
 pub enum MembershipsApiInfo<T: Config> {
@@ -37,41 +36,35 @@ pub enum MembershipsApiInfo<T: Config> {
 	},
 }
 
-impl<T, E> TryFrom<&mut Environment<'_, '_, E, BufInBufOutState>> for MembershipsApiInfo<T>
-where
-	T: Config,
-	E: Ext<T = T>,
-{
-	type Error = DispatchError;
-
-	fn try_from(env: &mut Environment<'_, '_, E, BufInBufOutState>) -> Result<Self, Self::Error> {
-		match env.func_id() {
+impl<T: Config> MembershipsApiInfo<T> {
+	pub fn read_from(input: &mut impl ApiInput) -> Result<Self, DispatchError> {
+		match input.func_id() {
 			0x0200 => {
-				let who = env.read_as()?;
+				let who = input.read_as()?;
 				Ok(MembershipsApiInfo::AssignMembership { who })
 			}
 			0x0201 => {
-				let who = env.read_as()?;
+				let who = input.read_as()?;
 				Ok(MembershipsApiInfo::MembershipOf { who })
 			}
 			0x0202 => {
-				let id = env.read_as()?;
+				let id = input.read_as()?;
 				Ok(MembershipsApiInfo::RankOf { id })
 			}
 			0x0203 => {
-				let (id, key) = env.read_as()?;
+				let (id, key) = input.read_as()?;
 				Ok(MembershipsApiInfo::Attribute { id, key })
 			}
 			0x0204 => {
-				let (id, key, value) = env.read_as()?;
+				let (id, key, value) = input.read_as()?;
 				Ok(MembershipsApiInfo::SetAttribute { id, key, value })
 			}
 			0x0205 => {
-				let (id, key) = env.read_as()?;
+				let (id, key) = input.read_as()?;
 				Ok(MembershipsApiInfo::ClearAttribute { id, key })
 			}
 			0x0206 => {
-				let (who, key, value) = env.read_as()?;
+				let (who, key, value) = input.read_as()?;
 				Ok(MembershipsApiInfo::FilterMembership { who, key, value })
 			}
 			id => {
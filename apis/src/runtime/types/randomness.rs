@@ -0,0 +1,25 @@
+use super::*;
+
+use frame_support::pallet_prelude::ConstU32;
+use frame_support::sp_runtime::DispatchError;
+use frame_support::BoundedVec;
+
+#[derive(Encode, Decode, Clone, Debug)]
+pub enum RandomnessApiInfo {
+	Random { subject: BoundedVec<u8, ConstU32<256>> },
+}
+
+impl RandomnessApiInfo {
+	pub fn read_from(input: &mut impl ApiInput) -> Result<Self, DispatchError> {
+		match input.func_id() {
+			0x0300 => {
+				let subject = input.read_as()?;
+				Ok(RandomnessApiInfo::Random { subject })
+			}
+			id => {
+				log::error!("Called an unregistered `func_id`: {id:}");
+				Err(DispatchError::Other("Unimplemented func_id"))
+			}
+		}
+	}
+}
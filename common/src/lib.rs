@@ -1,14 +1,20 @@
 #![cfg_attr(not(any(test, feature = "std")), no_std)]
-#![cfg_attr(feature = "nightly", feature(ascii_char))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
 mod multilocation_asset_id;
 mod payment_id;
+#[cfg(feature = "payment-request")]
+mod payment_request;
 
 pub use multilocation_asset_id::{FungibleAssetLocation, NetworkId, Para};
-pub use payment_id::PaymentId;
+pub use payment_id::{ParseError as PaymentIdParseError, PaymentId};
+#[cfg(feature = "payment-request")]
+pub use payment_request::{
+	from_ss58, to_ss58, ParseError as PaymentRequestParseError, PaymentRequest, Remark, MAX_REMARK_LENGTH, SS58_PREFIX,
+	URI_PREFIX,
+};
 
 #[cfg(feature = "runtime")]
 pub use multilocation_asset_id::runtime::AsFungibleAssetLocation;
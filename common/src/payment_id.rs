@@ -11,12 +11,28 @@ pub struct PaymentId {
 	block: u32,
 }
 
+/// The base58 alphabet ids are encoded with.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The size of the buffer ids are encoded into as base58, which fits the
+/// estimate `bs58` makes for 8 bytes.
+const MAX_ENCODED_LEN: usize = 12;
+
+/// Where the pretty form of an id is split.
+const SEPARATOR_AT: usize = 5;
+
 #[cfg_attr(feature = "js", wasm_bindgen)]
 impl PaymentId {
 	#[cfg_attr(feature = "js", wasm_bindgen(constructor))]
-	#[cfg(feature = "nightly")]
 	pub fn new(id: &str) -> PaymentId {
-		id.parse().unwrap_or(Default::default())
+		id.parse().unwrap_or_default()
+	}
+
+	/// Parses an id, failing if it's malformed or its checksum doesn't match.
+	#[cfg(feature = "js")]
+	#[wasm_bindgen(js_name = parse)]
+	pub fn try_parse(id: &str) -> Result<PaymentId, JsError> {
+		id.parse().map_err(|e: ParseError| JsError::new(&alloc::format!("{e}")))
 	}
 
 	#[cfg_attr(feature = "js", wasm_bindgen(js_name = fromNumber))]
@@ -34,7 +50,7 @@ impl PaymentId {
 		self.index as u32
 	}
 
-	#[cfg(all(feature = "nightly", feature = "alloc"))]
+	#[cfg(feature = "alloc")]
 	pub fn encode(&self, pretty: bool) -> alloc::string::String {
 		if pretty {
 			alloc::format!("{self:#}")
@@ -43,6 +59,41 @@ impl PaymentId {
 		}
 	}
 
+	/// Encodes the id followed by its checksum character, so mistyped ids can
+	/// be detected when parsed.
+	#[cfg(feature = "alloc")]
+	#[cfg_attr(feature = "js", wasm_bindgen(js_name = encodeWithChecksum))]
+	pub fn encode_with_checksum(&self, pretty: bool) -> alloc::string::String {
+		alloc::format!("{}-{}", self.encode(pretty), self.checksum())
+	}
+
+	#[cfg(feature = "js")]
+	#[wasm_bindgen(js_name = toString)]
+	pub fn to_js_string(&self) -> alloc::string::String {
+		self.encode(false)
+	}
+
+	/// The checksum character of the id, computed with the Luhn mod N
+	/// algorithm over its base58 form.
+	pub fn checksum(&self) -> char {
+		let mut buf = [0u8; MAX_ENCODED_LEN];
+		let n = self.encode_base58(&mut buf);
+		let n_chars = ALPHABET.len() as u32;
+
+		let sum: u32 = buf[..n]
+			.iter()
+			.rev()
+			.enumerate()
+			.map(|(i, c)| {
+				let digit = ALPHABET.iter().position(|a| a == c).unwrap_or_default() as u32;
+				let addend = if i % 2 == 0 { 2 * digit } else { digit };
+				addend / n_chars + addend % n_chars
+			})
+			.sum();
+
+		ALPHABET[((n_chars - sum % n_chars) % n_chars) as usize] as char
+	}
+
 	#[cfg_attr(feature = "js", wasm_bindgen(js_name = toNumber))]
 	pub fn to_number(&self) -> u64 {
 		(*self).into()
@@ -54,6 +105,34 @@ impl PaymentId {
 	}
 }
 
+impl PaymentId {
+	/// Encodes the id as base58 into `buf`, returning the length of the
+	/// encoded id.
+	fn encode_base58(&self, buf: &mut [u8; MAX_ENCODED_LEN]) -> usize {
+		bs58::encode(self)
+			.onto(&mut buf[..])
+			.expect("8 bytes fit in 12 base58 characters; qed")
+	}
+}
+
+/// An error parsing a [`PaymentId`] from its human-readable form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+	/// The id is too long, or not valid base58.
+	InvalidEncoding,
+	/// The checksum doesn't match the id, which was likely mistyped.
+	InvalidChecksum,
+}
+
+impl core::fmt::Display for ParseError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			ParseError::InvalidEncoding => write!(f, "invalid payment id"),
+			ParseError::InvalidChecksum => write!(f, "payment id checksum mismatch"),
+		}
+	}
+}
+
 impl From<PaymentId> for u64 {
 	fn from(id: PaymentId) -> Self {
 		u64::from_le_bytes(id.as_ref().try_into().expect("fits in u64"))
@@ -91,38 +170,97 @@ impl AsRef<[u8]> for PaymentId {
 	}
 }
 
-#[cfg(feature = "nightly")]
+/// Parses the base58 form of an id, with or without the separator of its
+/// pretty form, optionally followed by `-` and its checksum character.
 impl core::str::FromStr for PaymentId {
-	type Err = ();
+	type Err = ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let mut tmp = [0u8; 12];
-		let mut input = s;
-		if let Some((p1, p2)) = s.split_once('-') {
-			let len_without_sep = p1.len() + p2.len();
-			debug_assert!(len_without_sep <= 12);
-			tmp[..p1.len()].copy_from_slice(p1.as_bytes());
-			tmp[p1.len()..len_without_sep].copy_from_slice(p2.as_bytes());
-			input = tmp[..len_without_sep].as_ascii().ok_or(())?.as_str();
+		let (input, checksum) = match s.rsplit_once('-') {
+			Some((id, checksum)) if checksum.chars().count() == 1 => (id, checksum.chars().next()),
+			_ => (s, None),
 		};
+
+		let mut tmp = [0u8; MAX_ENCODED_LEN];
+		let mut len = 0;
+		for c in input.bytes().filter(|c| *c != b'-') {
+			*tmp.get_mut(len).ok_or(ParseError::InvalidEncoding)? = c;
+			len += 1;
+		}
 		let mut out = [0u8; 8];
-		let _ = bs58::decode(input).onto(&mut out[..]).map_err(|_| ())?;
-		Ok(u64::from_le_bytes(out).into())
+		bs58::decode(&tmp[..len])
+			.onto(&mut out[..])
+			.map_err(|_| ParseError::InvalidEncoding)?;
+
+		let id: PaymentId = u64::from_le_bytes(out).into();
+		if checksum.is_some_and(|checksum| checksum != id.checksum()) {
+			return Err(ParseError::InvalidChecksum);
+		}
+		Ok(id)
 	}
 }
 
-#[cfg(feature = "nightly")]
 impl core::fmt::Display for PaymentId {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		use core::fmt::Error;
-		let mut out = [0u8; 12];
-		let n = bs58::encode(self).onto(&mut out[..]).map_err(|_| Error)?;
-		let out = out[..n].as_ascii().ok_or(Error)?.as_str();
-		write!(f, "{}", &out[..5])?;
+		let mut out = [0u8; MAX_ENCODED_LEN];
+		let n = self.encode_base58(&mut out);
+		let out = core::str::from_utf8(&out[..n]).map_err(|_| Error)?;
+		let (head, tail) = out.split_at(SEPARATOR_AT.min(n));
+		write!(f, "{head}")?;
 		if f.alternate() {
 			write!(f, "-")?;
 		}
-		write!(f, "{}", &out[5..])
+		write!(f, "{tail}")
+	}
+}
+
+// Ids are serialized in their base58 form for human-readable formats, and as a
+// u64 otherwise.
+#[cfg(feature = "serde")]
+mod serde_impl {
+	use super::{PaymentId, MAX_ENCODED_LEN};
+	use core::fmt;
+	use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+	impl Serialize for PaymentId {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			if serializer.is_human_readable() {
+				let mut buf = [0u8; MAX_ENCODED_LEN];
+				let n = self.encode_base58(&mut buf);
+				serializer.serialize_str(core::str::from_utf8(&buf[..n]).map_err(ser::Error::custom)?)
+			} else {
+				serializer.serialize_u64(self.to_number())
+			}
+		}
+	}
+
+	impl<'de> Deserialize<'de> for PaymentId {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			struct PaymentIdVisitor;
+
+			impl de::Visitor<'_> for PaymentIdVisitor {
+				type Value = PaymentId;
+
+				fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					f.write_str("a base58 payment id, or its number")
+				}
+
+				fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+					v.parse().map_err(E::custom)
+				}
+
+				fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+					Ok(v.into())
+				}
+			}
+
+			if deserializer.is_human_readable() {
+				deserializer.deserialize_str(PaymentIdVisitor)
+			} else {
+				deserializer.deserialize_u64(PaymentIdVisitor)
+			}
+		}
 	}
 }
 
@@ -169,7 +307,7 @@ mod runtime {
 	}
 }
 
-#[cfg(all(test, feature = "nightly"))]
+#[cfg(test)]
 mod tests {
 	extern crate alloc;
 	use super::*;
@@ -191,5 +329,21 @@ mod tests {
 		let id: PaymentId = (1_234_567, 5, &[2, 5][..]).into();
 		assert_eq!(format!("{id:#}"), TEST_ID);
 		assert_eq!(id, TEST_ID.parse().unwrap());
+		assert_eq!(id, "LbNvSNtVQs".parse().unwrap());
+	}
+
+	#[test]
+	fn payment_id_checksum() {
+		let id: PaymentId = (1_234_567, 5, &[2, 5][..]).into();
+		let with_checksum = format!("{id:#}-{}", id.checksum());
+		assert_eq!(id, with_checksum.parse().unwrap());
+
+		// A mistyped character, or two swapped ones, are detected
+		let mistyped = with_checksum.replacen('N', "M", 1);
+		assert_eq!(mistyped.parse::<PaymentId>(), Err(ParseError::InvalidChecksum));
+		let swapped = format!("bLNvS-NtVQs-{}", id.checksum());
+		assert_eq!(swapped.parse::<PaymentId>(), Err(ParseError::InvalidChecksum));
+
+		assert_eq!("LbNvS-NtVQ0".parse::<PaymentId>(), Err(ParseError::InvalidEncoding));
 	}
 }
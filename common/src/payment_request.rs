@@ -0,0 +1,564 @@
+//! Requests for a payment, e.g. an invoice a point of sale shows as a QR code,
+//! encoded as URIs like
+//! `kreivo:pay?to=<ss58>&asset=here:1&amount=100&remark=table%205&expires=1700000000000&order=42`.
+use crate::{FungibleAssetLocation, NetworkId, Para};
+use alloc::{format, string::String, vec, vec::Vec};
+use bounded_collections::{BoundedVec, ConstU32};
+use core::{fmt, str::FromStr};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "js")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "scale")]
+use {
+	parity_scale_codec::{Decode, DecodeWithMemTracking, Encode},
+	scale_info::TypeInfo,
+};
+
+/// What every payment request URI starts with.
+pub const URI_PREFIX: &str = "kreivo:pay?";
+
+/// The SS58 address format of Kreivo, used to encode beneficiaries.
+pub const SS58_PREFIX: u16 = 2;
+
+/// The maximum length of a remark, matching the `MaxRemarkLength` of the
+/// payments pallet in the runtime.
+pub const MAX_REMARK_LENGTH: usize = 50;
+
+/// A remark attached to a payment request.
+pub type Remark = BoundedVec<u8, ConstU32<{ MAX_REMARK_LENGTH as u32 }>>;
+
+/// A request to pay some amount of an asset to a beneficiary.
+#[cfg_attr(feature = "js", wasm_bindgen)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "scale", derive(Encode, Decode, DecodeWithMemTracking, TypeInfo))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PaymentRequest {
+	beneficiary: [u8; 32],
+	asset: FungibleAssetLocation,
+	amount: u128,
+	remark: Option<Remark>,
+	expires_at: Option<u64>,
+	order_id: Option<u64>,
+}
+
+impl PaymentRequest {
+	pub fn new(beneficiary: [u8; 32], asset: FungibleAssetLocation, amount: u128) -> Self {
+		Self {
+			beneficiary,
+			asset,
+			amount,
+			remark: None,
+			expires_at: None,
+			order_id: None,
+		}
+	}
+
+	/// Attaches a remark to the request, failing if it exceeds
+	/// [`MAX_REMARK_LENGTH`].
+	pub fn with_remark(mut self, remark: &[u8]) -> Result<Self, ParseError> {
+		self.remark = Some(Remark::try_from(remark.to_vec()).map_err(|_| ParseError::RemarkTooLong)?);
+		Ok(self)
+	}
+
+	/// Sets the moment, as a unix timestamp in milliseconds, after which the
+	/// request should no longer be paid.
+	pub fn with_expiry(mut self, expires_at: u64) -> Self {
+		self.expires_at = Some(expires_at);
+		self
+	}
+
+	/// Sets the id of the order being paid.
+	pub fn with_order_id(mut self, order_id: u64) -> Self {
+		self.order_id = Some(order_id);
+		self
+	}
+
+	pub fn beneficiary(&self) -> &[u8; 32] {
+		&self.beneficiary
+	}
+
+	pub fn asset(&self) -> FungibleAssetLocation {
+		self.asset
+	}
+
+	pub fn amount(&self) -> u128 {
+		self.amount
+	}
+
+	pub fn remark(&self) -> Option<&[u8]> {
+		self.remark.as_ref().map(|remark| remark.as_slice())
+	}
+
+	pub fn expires_at(&self) -> Option<u64> {
+		self.expires_at
+	}
+
+	pub fn order_id(&self) -> Option<u64> {
+		self.order_id
+	}
+
+	/// Whether the request expired by `now`, a unix timestamp in milliseconds.
+	pub fn is_expired(&self, now: u64) -> bool {
+		self.expires_at.is_some_and(|expires_at| now > expires_at)
+	}
+}
+
+#[cfg(feature = "js")]
+#[wasm_bindgen]
+impl PaymentRequest {
+	/// Creates a request to pay `amount`, given as a decimal string, of
+	/// `asset` to the Kreivo SS58 address of `beneficiary`.
+	#[wasm_bindgen(constructor)]
+	pub fn js_new(beneficiary: &str, asset: &str, amount: &str) -> Result<PaymentRequest, JsError> {
+		let js_error = |e: ParseError| JsError::new(&format!("{e}"));
+		Ok(PaymentRequest::new(
+			parse_beneficiary(beneficiary).map_err(js_error)?,
+			parse_asset(asset).map_err(js_error)?,
+			amount.parse().map_err(|_| js_error(ParseError::InvalidAmount))?,
+		))
+	}
+
+	#[wasm_bindgen(js_name = withRemark)]
+	pub fn js_with_remark(self, remark: &str) -> Result<PaymentRequest, JsError> {
+		self.with_remark(remark.as_bytes())
+			.map_err(|e| JsError::new(&format!("{e}")))
+	}
+
+	#[wasm_bindgen(js_name = withExpiry)]
+	pub fn js_with_expiry(self, expires_at: u64) -> PaymentRequest {
+		self.with_expiry(expires_at)
+	}
+
+	#[wasm_bindgen(js_name = withOrderId)]
+	pub fn js_with_order_id(self, order_id: u64) -> PaymentRequest {
+		self.with_order_id(order_id)
+	}
+
+	/// Parses a payment request URI.
+	#[wasm_bindgen(js_name = parse)]
+	pub fn try_parse(uri: &str) -> Result<PaymentRequest, JsError> {
+		uri.parse().map_err(|e: ParseError| JsError::new(&format!("{e}")))
+	}
+
+	#[wasm_bindgen(js_name = toString)]
+	pub fn to_js_string(&self) -> String {
+		format!("{self}")
+	}
+
+	/// The SS58 address of the beneficiary.
+	#[wasm_bindgen(getter = beneficiary)]
+	pub fn js_beneficiary(&self) -> String {
+		to_ss58(&self.beneficiary, SS58_PREFIX)
+	}
+
+	#[wasm_bindgen(getter = asset)]
+	pub fn js_asset(&self) -> String {
+		encode_asset(&self.asset)
+	}
+
+	/// The amount as a decimal string, as it may not fit a JS number.
+	#[wasm_bindgen(getter = amount)]
+	pub fn js_amount(&self) -> String {
+		format!("{}", self.amount)
+	}
+
+	#[wasm_bindgen(getter = remark)]
+	pub fn js_remark(&self) -> Option<String> {
+		self.remark().map(|r| String::from_utf8_lossy(r).into_owned())
+	}
+
+	#[wasm_bindgen(getter = expiresAt)]
+	pub fn js_expires_at(&self) -> Option<u64> {
+		self.expires_at
+	}
+
+	#[wasm_bindgen(getter = orderId)]
+	pub fn js_order_id(&self) -> Option<u64> {
+		self.order_id
+	}
+}
+
+impl fmt::Display for PaymentRequest {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{URI_PREFIX}to={}&asset={}&amount={}",
+			to_ss58(&self.beneficiary, SS58_PREFIX),
+			encode_asset(&self.asset),
+			self.amount
+		)?;
+		if let Some(remark) = &self.remark {
+			write!(f, "&remark={}", percent_encode(remark))?;
+		}
+		if let Some(expires_at) = self.expires_at {
+			write!(f, "&expires={expires_at}")?;
+		}
+		if let Some(order_id) = self.order_id {
+			write!(f, "&order={order_id}")?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for PaymentRequest {
+	type Err = ParseError;
+
+	/// Parses a payment request URI. Unknown parameters are ignored so newer
+	/// requests can still be paid by older wallets, while known ones can only
+	/// be given once.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let query = s.strip_prefix(URI_PREFIX).ok_or(ParseError::InvalidScheme)?;
+
+		let (mut beneficiary, mut asset, mut amount) = (None, None, None);
+		let (mut remark, mut expires_at, mut order_id) = (None, None, None);
+		for param in query.split('&').filter(|p| !p.is_empty()) {
+			let (key, value) = param.split_once('=').unwrap_or((param, ""));
+			match key {
+				"to" => set_once(&mut beneficiary, parse_beneficiary(value)?)?,
+				"asset" => set_once(&mut asset, parse_asset(value)?)?,
+				"amount" => set_once(&mut amount, value.parse().map_err(|_| ParseError::InvalidAmount)?)?,
+				"remark" => set_once(&mut remark, percent_decode(value)?)?,
+				"expires" => set_once(&mut expires_at, value.parse().map_err(|_| ParseError::InvalidExpiry)?)?,
+				"order" => set_once(&mut order_id, value.parse().map_err(|_| ParseError::InvalidOrderId)?)?,
+				_ => {}
+			}
+		}
+
+		let request = PaymentRequest {
+			beneficiary: beneficiary.ok_or(ParseError::MissingBeneficiary)?,
+			asset: asset.ok_or(ParseError::MissingAsset)?,
+			amount: amount.ok_or(ParseError::InvalidAmount)?,
+			remark: None,
+			expires_at,
+			order_id,
+		};
+		match remark {
+			Some(remark) => request.with_remark(&remark),
+			None => Ok(request),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+	InvalidScheme,
+	MissingBeneficiary,
+	InvalidBeneficiary,
+	ForeignBeneficiary,
+	MissingAsset,
+	InvalidAsset,
+	InvalidAmount,
+	InvalidRemark,
+	RemarkTooLong,
+	InvalidExpiry,
+	InvalidOrderId,
+	DuplicateParam,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			ParseError::InvalidScheme => "not a payment request URI",
+			ParseError::MissingBeneficiary => "missing beneficiary",
+			ParseError::InvalidBeneficiary => "invalid beneficiary address",
+			ParseError::ForeignBeneficiary => "beneficiary address is not a Kreivo address",
+			ParseError::MissingAsset => "missing asset",
+			ParseError::InvalidAsset => "invalid asset",
+			ParseError::InvalidAmount => "missing or invalid amount",
+			ParseError::InvalidRemark => "invalid remark encoding",
+			ParseError::RemarkTooLong => "remark is too long",
+			ParseError::InvalidExpiry => "invalid expiry",
+			ParseError::InvalidOrderId => "invalid order id",
+			ParseError::DuplicateParam => "parameter given more than once",
+		})
+	}
+}
+
+/// Encodes an account as an SS58 address with the given format.
+pub fn to_ss58(account: &[u8; 32], prefix: u16) -> String {
+	let ident = prefix & 0b0011_1111_1111_1111;
+	let mut data = match ident {
+		0..=63 => vec![ident as u8],
+		_ => vec![
+			((ident & 0b0000_0000_1111_1100) as u8 >> 2) | 0b0100_0000,
+			(ident >> 8) as u8 | ((ident & 0b0000_0000_0000_0011) as u8) << 6,
+		],
+	};
+	data.extend_from_slice(account);
+	let checksum = ss58_hash(&data);
+	data.extend_from_slice(&checksum.as_bytes()[..2]);
+	bs58::encode(data).into_string()
+}
+
+/// Decodes an SS58 address of any format into the format and the account.
+pub fn from_ss58(address: &str) -> Result<(u16, [u8; 32]), ParseError> {
+	let data = bs58::decode(address)
+		.into_vec()
+		.map_err(|_| ParseError::InvalidBeneficiary)?;
+	let (prefix_len, prefix) = match (data.first(), data.get(1)) {
+		(Some(&first @ 0..=63), _) => (1, first as u16),
+		(Some(&first @ 64..=127), Some(&second)) => {
+			let lower = (first << 2) | (second >> 6);
+			let upper = second & 0b0011_1111;
+			(2, lower as u16 | (upper as u16) << 8)
+		}
+		_ => return Err(ParseError::InvalidBeneficiary),
+	};
+	if data.len() != prefix_len + 32 + 2 {
+		return Err(ParseError::InvalidBeneficiary);
+	}
+
+	let (payload, checksum) = data.split_at(prefix_len + 32);
+	if ss58_hash(payload).as_bytes()[..2] != *checksum {
+		return Err(ParseError::InvalidBeneficiary);
+	}
+	let mut account = [0u8; 32];
+	account.copy_from_slice(&payload[prefix_len..]);
+	Ok((prefix, account))
+}
+
+/// Decodes the SS58 address of a beneficiary, which must use the Kreivo
+/// format.
+fn parse_beneficiary(address: &str) -> Result<[u8; 32], ParseError> {
+	match from_ss58(address)? {
+		(SS58_PREFIX, account) => Ok(account),
+		_ => Err(ParseError::ForeignBeneficiary),
+	}
+}
+
+/// Sets a parameter, failing if it was already set.
+fn set_once<T>(param: &mut Option<T>, value: T) -> Result<(), ParseError> {
+	match param.replace(value) {
+		Some(_) => Err(ParseError::DuplicateParam),
+		None => Ok(()),
+	}
+}
+
+fn ss58_hash(data: &[u8]) -> blake2b_simd::Hash {
+	blake2b_simd::Params::new()
+		.hash_length(64)
+		.to_state()
+		.update(b"SS58PRE")
+		.update(data)
+		.finalize()
+}
+
+/// Encodes an asset as `here:<index>`, `sibling:<para>:<pallet>:<index>` or
+/// `<network>[:<para>:<pallet>:<index>]`, where the network is `polkadot`,
+/// `kusama` or `ethereum.<chain id>`.
+fn encode_asset(asset: &FungibleAssetLocation) -> String {
+	let para = |p: &Para| format!("{}:{}:{}", p.id, p.pallet, p.index);
+	match asset {
+		FungibleAssetLocation::Here(index) => format!("here:{index}"),
+		FungibleAssetLocation::Sibling(p) => format!("sibling:{}", para(p)),
+		FungibleAssetLocation::External { network, child } => {
+			let network = match network {
+				NetworkId::Polkadot => "polkadot".into(),
+				NetworkId::Kusama => "kusama".into(),
+				NetworkId::Ethereum { chain_id } => format!("ethereum.{chain_id}"),
+			};
+			match child {
+				Some(p) => format!("{network}:{}", para(p)),
+				None => network,
+			}
+		}
+	}
+}
+
+fn parse_asset(s: &str) -> Result<FungibleAssetLocation, ParseError> {
+	let mut parts = s.split(':');
+	let kind = parts.next().ok_or(ParseError::InvalidAsset)?;
+	let rest: Vec<&str> = parts.collect();
+
+	let para = |rest: &[&str]| match rest {
+		[id, pallet, index] => Some(Para {
+			id: id.parse().ok()?,
+			pallet: pallet.parse().ok()?,
+			index: index.parse().ok()?,
+		}),
+		_ => None,
+	};
+
+	let asset = match (kind, &rest[..]) {
+		("here", [index]) => index.parse().ok().map(FungibleAssetLocation::Here),
+		("sibling", rest) => para(rest).map(FungibleAssetLocation::Sibling),
+		(network, rest) => {
+			let network = match network {
+				"polkadot" => Some(NetworkId::Polkadot),
+				"kusama" => Some(NetworkId::Kusama),
+				n => n
+					.strip_prefix("ethereum.")
+					.and_then(|chain_id| chain_id.parse().ok())
+					.map(|chain_id| NetworkId::Ethereum { chain_id }),
+			};
+			match rest {
+				[] => network.map(|network| FungibleAssetLocation::External { network, child: None }),
+				rest => network
+					.zip(para(rest))
+					.map(|(network, child)| FungibleAssetLocation::External {
+						network,
+						child: Some(child),
+					}),
+			}
+		}
+	};
+	asset.ok_or(ParseError::InvalidAsset)
+}
+
+fn percent_encode(bytes: &[u8]) -> String {
+	let mut encoded = String::with_capacity(bytes.len());
+	for &b in bytes {
+		if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+			encoded.push(b as char);
+		} else {
+			encoded.push_str(&format!("%{b:02X}"));
+		}
+	}
+	encoded
+}
+
+fn percent_decode(s: &str) -> Result<Vec<u8>, ParseError> {
+	let mut bytes = s.bytes();
+	let mut decoded = Vec::with_capacity(s.len());
+	while let Some(b) = bytes.next() {
+		decoded.push(match b {
+			b'%' => {
+				let hex = [
+					bytes.next().ok_or(ParseError::InvalidRemark)?,
+					bytes.next().ok_or(ParseError::InvalidRemark)?,
+				];
+				let hex = core::str::from_utf8(&hex).map_err(|_| ParseError::InvalidRemark)?;
+				u8::from_str_radix(hex, 16).map_err(|_| ParseError::InvalidRemark)?
+			}
+			b'+' => b' ',
+			b => b,
+		});
+	}
+	Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALICE: [u8; 32] = [
+		0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6, 0x82, 0x2c,
+		0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+	];
+
+	#[test]
+	fn ss58_round_trip() {
+		let kreivo = to_ss58(&ALICE, SS58_PREFIX);
+		assert_eq!(kreivo, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F");
+		assert_eq!(from_ss58(&kreivo), Ok((SS58_PREFIX, ALICE)));
+		assert_eq!(
+			from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+			Ok((42, ALICE))
+		);
+		assert_eq!(from_ss58(&to_ss58(&ALICE, 1000)), Ok((1000, ALICE)));
+		assert_eq!(
+			from_ss58("HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74G"),
+			Err(ParseError::InvalidBeneficiary)
+		);
+	}
+
+	#[test]
+	fn uri_round_trip() {
+		let assets = [
+			FungibleAssetLocation::Here(1),
+			FungibleAssetLocation::Sibling(Para {
+				id: 1000,
+				pallet: 50,
+				index: 1984,
+			}),
+			FungibleAssetLocation::External {
+				network: NetworkId::Polkadot,
+				child: None,
+			},
+			FungibleAssetLocation::External {
+				network: NetworkId::Ethereum { chain_id: 1 },
+				child: Some(Para {
+					id: 2000,
+					pallet: 10,
+					index: 0,
+				}),
+			},
+		];
+
+		for asset in assets {
+			let request = PaymentRequest::new(ALICE, asset, u128::MAX);
+			assert_eq!(request.to_string().parse(), Ok(request.clone()));
+
+			let request = request
+				.with_remark(b"table 5 & co = 100%")
+				.unwrap()
+				.with_expiry(1_700_000_000_000)
+				.with_order_id(42);
+			assert_eq!(request.to_string().parse(), Ok(request));
+		}
+	}
+
+	#[test]
+	fn uri_format() {
+		let request = PaymentRequest::new(ALICE, FungibleAssetLocation::Here(1), 100)
+			.with_remark(b"table 5")
+			.unwrap()
+			.with_order_id(42);
+		assert_eq!(
+			request.to_string(),
+			"kreivo:pay?to=HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F&asset=here:1&amount=100&remark=table%205&order=42"
+		);
+		assert!(!request.is_expired(u64::MAX));
+		assert!(request.clone().with_expiry(10).is_expired(11));
+	}
+
+	#[test]
+	fn invalid_uris() {
+		let to = "to=HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F";
+		let parse = |uri: String| uri.parse::<PaymentRequest>();
+
+		assert_eq!(
+			parse(format!("kreivo:send?{to}&asset=here:1&amount=1")),
+			Err(ParseError::InvalidScheme)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}asset=here:1&amount=1")),
+			Err(ParseError::MissingBeneficiary)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}{to}&asset=there:1&amount=1")),
+			Err(ParseError::InvalidAsset)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}{to}&asset=here:1&amount=-1")),
+			Err(ParseError::InvalidAmount)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}{to}&asset=here:1&amount=1&remark=%G0")),
+			Err(ParseError::InvalidRemark)
+		);
+		assert_eq!(
+			parse(format!(
+				"{URI_PREFIX}{to}&asset=here:1&amount=1&remark={}",
+				"a".repeat(MAX_REMARK_LENGTH + 1)
+			)),
+			Err(ParseError::RemarkTooLong)
+		);
+		assert_eq!(
+			parse(format!(
+				"{URI_PREFIX}to=5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY&asset=here:1&amount=1"
+			)),
+			Err(ParseError::ForeignBeneficiary)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}{to}&asset=here:1&amount=1&amount=100")),
+			Err(ParseError::DuplicateParam)
+		);
+		assert_eq!(
+			parse(format!("{URI_PREFIX}{to}&{to}&asset=here:1&amount=1")),
+			Err(ParseError::DuplicateParam)
+		);
+		assert!(parse(format!("{URI_PREFIX}{to}&asset=here:1&amount=1&tip=5&tip=6")).is_ok());
+	}
+}
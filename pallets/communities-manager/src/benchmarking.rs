@@ -1,11 +1,20 @@
 //! Benchmarking setup for pallet-communities
 use super::*;
 
+use alloc::vec;
+
 use frame_benchmarking::v2::*;
 
-use frame_support::{dispatch::DispatchResult, traits::fungible::Mutate};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{fungible::Mutate, fungibles::Create as AssetCreate},
+};
 use frame_system::RawOrigin;
-use sp_runtime::SaturatedConversion;
+use pallet_communities::BenchmarkHelper;
+use sp_runtime::{
+	app_crypto::{sr25519, RuntimePublic},
+	KeyTypeId, Perbill, SaturatedConversion,
+};
 
 // Since `periodicity` is arbitrary, we assume `DAYS` is a nominal day for 6s
 // block.
@@ -32,8 +41,12 @@ where
 where
 	T::RuntimeEvent: From<pallet_communities::Event<T>>,
 	NativeBalanceOf<T>: From<u64>,
-	CommunityIdOf<T>: One,
+	CommunityIdOf<T>: One + From<u16>,
 	T::MembershipId: From<u32>,
+	T::Assets: AssetCreate<AccountIdOf<T>>,
+	AssetBalanceOf<T>: From<u64>,
+	T::OffchainSignature: From<sr25519::Signature>,
+	T::OffchainPublic: From<sr25519::Public>,
 )]
 mod benchmarks {
 	use super::*;
@@ -54,6 +67,7 @@ mod benchmarks {
 			T::Lookup::unlookup(first_member.clone()),
 			None,
 			None,
+			None,
 		);
 
 		// verification code
@@ -74,6 +88,7 @@ mod benchmarks {
 			TankConfig {
 				capacity: Some(block_weight::<T>()),
 				periodicity: Some((7 * DAYS).into()),
+				policy: TankPolicy::RankMultiplier(Perbill::from_percent(10)),
 			},
 			Some(u32::MAX.into()),
 		);
@@ -111,11 +126,708 @@ mod benchmarks {
 			TankConfig {
 				capacity: Some(block_weight::<T>()),
 				periodicity: Some((7 * DAYS).into()),
+				policy: TankPolicy::RankMultiplier(Perbill::from_percent(10)),
 			},
 		);
 
 		Ok(())
 	}
 
+	#[benchmark]
+	fn deregister(m: Linear<0, 1024>, i: Linear<0, 1024>, r: Linear<0, 1024>) -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+
+		// The registration deposit gets returned
+		let beneficiary: AccountIdOf<T> = account("beneficiary", 0, 0);
+		setup_account::<T>(&beneficiary)?;
+		RegistrationDeposit::<T>::insert(community_id, (1_000u64.into(), first_member.clone(), beneficiary));
+
+		for i in 0..m {
+			T::CreateMemberships::mint_into(&community_id, &i.into(), &first_member, &Default::default(), true)?;
+		}
+
+		// Half of the invitations are signers, and the other half claims
+		for n in 0..i {
+			if n % 2 == 0 {
+				InvitationSigners::<T>::insert(community_id, account::<AccountIdOf<T>>("signer", n, 0), ());
+			} else {
+				ClaimedInvitations::<T>::insert(community_id, n, frame_system::Pallet::<T>::block_number());
+			}
+		}
+
+		for role in 0..T::MaxRoles::get() {
+			CommunityRoles::<T>::insert(community_id, role as RoleId, RoleName::default());
+		}
+		for n in 0..r {
+			let membership_id: T::MembershipId = n.into();
+			MembershipRoles::<T>::insert((community_id, 0, membership_id), ());
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, community_id, m, i, r);
+
+		// verification code
+		assert_has_event::<T>(Event::<T>::CommunityDeregistered { id: community_id }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_metadata() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+
+		let metadata = CommunityMetadata {
+			name: BoundedVec::truncate_from([b'a'; 25].to_vec()),
+			description: BoundedVec::truncate_from([b'a'; 256].to_vec()),
+			logo: BoundedVec::truncate_from([b'a'; 128].to_vec()),
+			website: BoundedVec::truncate_from([b'a'; 128].to_vec()),
+			matrix_room: BoundedVec::truncate_from([b'a'; 128].to_vec()),
+		};
+
+		#[extrinsic_call]
+		_(origin, metadata.clone());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MetadataSet {
+				id: community_id,
+				metadata,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_track() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+
+		#[extrinsic_call]
+		_(origin, community_id, CommunityTrack::Template(TrackTemplate::Treasury));
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::TrackUpdated {
+				id: community_id,
+				track: community_id,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_track() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let track_id: CommunityIdOf<T> = 2u16.into();
+
+		#[extrinsic_call]
+		_(
+			origin,
+			track_id,
+			Subset::Member(0u32.into()),
+			CommunityTrack::Template(TrackTemplate::Treasury),
+		);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::TrackAdded {
+				id: community_id,
+				track: track_id,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_track() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let track_id: CommunityIdOf<T> = 2u16.into();
+		Pallet::<T>::add_track(
+			origin.clone(),
+			track_id,
+			Subset::Member(0u32.into()),
+			CommunityTrack::Template(TrackTemplate::Treasury),
+		)?;
+
+		#[extrinsic_call]
+		_(origin, track_id);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::TrackRemoved {
+				id: community_id,
+				track: track_id,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_renewal_price() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+
+		#[extrinsic_call]
+		_(origin, Some(RenewalPrice::Native(1_000u64.into())));
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::RenewalPriceSet {
+				id: community_id,
+				price: Some(RenewalPrice::Native(1_000u64.into())),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn renew_membership() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		RenewalPrices::<T>::insert(community_id, RenewalPrice::Native(1_000u64.into()));
+
+		let membership_id: T::MembershipId = 1u32.into();
+		T::CreateMemberships::mint_into(&community_id, &membership_id, &first_member, &Default::default(), true)?;
+		let expiration: BlockNumberFor<T> = 1u32.into();
+		T::CreateMemberships::set_typed_attribute(&community_id, &membership_id, &MEMBERSHIP_EXPIRATION, &expiration)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(first_member), community_id, membership_id.clone());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipRenewed {
+				id: community_id,
+				membership: membership_id,
+				expiration: frame_system::Pallet::<T>::block_number().max(expiration) + T::RenewalPeriod::get(),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reclaim_expired() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+		let caller: AccountIdOf<T> = whitelisted_caller();
+		let member: AccountIdOf<T> = account("member", 0, 0);
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+		setup_account::<T>(&community_account)?;
+
+		let membership_id: T::MembershipId = 1u32.into();
+		Pallet::<T>::prepare_memberships_collection()?;
+		T::CreateMemberships::mint_into(
+			&T::MembershipsManagerCollectionId::get(),
+			&membership_id,
+			&community_account,
+			&Default::default(),
+			true,
+		)?;
+		pallet_communities::Pallet::<T>::add_member(
+			CommunityOrigin::<T>::new(community_id).into(),
+			T::Lookup::unlookup(member),
+		)?;
+
+		let expiration: BlockNumberFor<T> = 1u32.into();
+		T::CreateMemberships::set_typed_attribute(&community_id, &membership_id, &MEMBERSHIP_EXPIRATION, &expiration)?;
+		frame_system::Pallet::<T>::set_block_number(expiration + T::ReclaimGracePeriod::get() + 1u32.into());
+
+		// The membership holds every role the community can define
+		for role in 0..T::MaxRoles::get() {
+			CommunityRoles::<T>::insert(community_id, role as RoleId, RoleName::default());
+			MembershipRoles::<T>::insert((community_id, role as RoleId, membership_id.clone()), ());
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), community_id, membership_id.clone());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipReclaimed {
+				id: community_id,
+				membership: membership_id,
+				reward: T::ReclaimReward::get(),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_asset_price() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_account::<T>(&T::MembershipsManagerOwner::get())?;
+		Pallet::<T>::create_memberships(
+			RawOrigin::Root.into(),
+			1,
+			1u32.into(),
+			0u64.into(),
+			TankConfig::default(),
+			None,
+		)?;
+		let price = (T::BenchmarkHelper::community_asset_id(), 1_000u64.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 1u32.into(), Some(price.clone()));
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::AssetPriceSet {
+				membership: 1u32.into(),
+				price: Some(price),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn buy_membership() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+
+		setup_account::<T>(&T::MembershipsManagerOwner::get())?;
+		Pallet::<T>::create_memberships(
+			RawOrigin::Root.into(),
+			1,
+			1u32.into(),
+			0u64.into(),
+			TankConfig::default(),
+			None,
+		)?;
+
+		let buyer: AccountIdOf<T> = whitelisted_caller();
+		setup_account::<T>(&buyer)?;
+		let asset = T::BenchmarkHelper::community_asset_id();
+		T::Assets::create(asset.clone(), first_member, true, 1u64.into())?;
+		T::Assets::mint_into(asset.clone(), &buyer, 1_000_000u64.into())?;
+		AssetPrices::<T>::insert(
+			T::MembershipId::from(1u32),
+			(asset, AssetBalanceOf::<T>::from(1_000u64)),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), 1u32.into(), community_id);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipBought {
+				membership: 1u32.into(),
+				community: community_id,
+				buyer,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_rank_requirements() -> Result<(), BenchmarkError> {
+		// setup code
+		let requirements: RankRequirementsOf<T> =
+			BoundedVec::truncate_from(vec![CommunityMetrics::default(); MAX_RANK_REQUIREMENTS as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, requirements.clone());
+
+		// verification code
+		assert_has_event::<T>(Event::<T>::RankRequirementsSet { requirements }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_promotion() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		RankRequirements::<T>::put(BoundedVec::truncate_from(vec![CommunityMetrics::default()]));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(first_member), community_id);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::CommunityPromoted {
+				id: community_id,
+				rank: 1,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn enforce_demotion() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, Some(1))?;
+		RankRequirements::<T>::put(BoundedVec::truncate_from(vec![CommunityMetrics {
+			members: u32::MAX,
+			..Default::default()
+		}]));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(first_member), community_id);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::CommunityDemoted {
+				id: community_id,
+				rank: 0,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_community_token() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		setup_account::<T>(&pallet_communities::Pallet::<T>::community_account(&community_id))?;
+
+		let token = CommunityToken {
+			name: BoundedVec::truncate_from(b"Test Community Token".into()),
+			symbol: BoundedVec::truncate_from(b"TCT".into()),
+			decimals: 10,
+			min_balance: 1u64.into(),
+			initial_supply: 1_000_000u64.into(),
+		};
+
+		#[block]
+		{
+			Pallet::<T>::create_community_token(community_id, token)?;
+		}
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::CommunityTokenCreated {
+				id: community_id,
+				asset: T::CommunityAssetId::convert(community_id),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_admin() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let new_admin: AccountIdOf<T> = account("admin", 0, 0);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(first_member),
+			community_id,
+			T::Lookup::unlookup(new_admin.clone()),
+			false,
+		);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::AdminTransferred {
+				id: community_id,
+				admin: new_admin,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_admin() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let new_admin: AccountIdOf<T> = account("admin", 0, 0);
+		PendingAdmin::<T>::insert(community_id, new_admin.clone());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(new_admin.clone()), community_id);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::AdminTransferred {
+				id: community_id,
+				admin: new_admin,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_invitation_signer() -> Result<(), BenchmarkError> {
+		// setup code
+		let community_id: CommunityIdOf<T> = One::one();
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let signer: AccountIdOf<T> = account("signer", 0, 0);
+
+		#[extrinsic_call]
+		_(origin, T::Lookup::unlookup(signer.clone()), true);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::InvitationSignerSet {
+				id: community_id,
+				signer,
+				enabled: true,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_invitation() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+
+		setup_account::<T>(&T::MembershipsManagerOwner::get())?;
+		Pallet::<T>::create_memberships(
+			RawOrigin::Root.into(),
+			1,
+			1u32.into(),
+			0u64.into(),
+			TankConfig::default(),
+			None,
+		)?;
+		T::CreateMemberships::transfer(
+			&T::MembershipsManagerCollectionId::get(),
+			&1u32.into(),
+			&pallet_communities::Pallet::<T>::community_account(&community_id),
+		)?;
+
+		let key_type = KeyTypeId(*b"invt");
+		let public = sr25519::Public::generate_pair(key_type, None);
+		let signer = T::OffchainPublic::from(public).into_account();
+		InvitationSigners::<T>::insert(community_id, &signer, ());
+
+		let invitation_key = sr25519::Public::generate_pair(key_type, None);
+		let invitation = Invitation {
+			community_id,
+			nonce: 1,
+			expires_at: frame_system::Pallet::<T>::block_number() + 1u32.into(),
+			key: T::OffchainPublic::from(invitation_key).into_account(),
+		};
+		let signature = public
+			.sign(key_type, &Pallet::<T>::invitation_payload(&invitation))
+			.ok_or(BenchmarkError::Stop("could not sign the invitation"))?;
+
+		let who: AccountIdOf<T> = whitelisted_caller();
+		let claim_signature = invitation_key
+			.sign(key_type, &Pallet::<T>::claim_payload(&invitation, &who))
+			.ok_or(BenchmarkError::Stop("could not sign the claim"))?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(who.clone()),
+			invitation,
+			signer,
+			signature.into(),
+			claim_signature.into(),
+		);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::InvitationClaimed {
+				id: community_id,
+				nonce: 1,
+				who,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn prune_invitation() -> Result<(), BenchmarkError> {
+		// setup code
+		let community_id: CommunityIdOf<T> = One::one();
+		ClaimedInvitations::<T>::insert(community_id, 1, frame_system::Pallet::<T>::block_number());
+		frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 1u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(whitelisted_caller()), community_id, 1);
+
+		// verification code
+		assert!(!ClaimedInvitations::<T>::contains_key(community_id, 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn define_role() -> Result<(), BenchmarkError> {
+		// setup code
+		let community_id: CommunityIdOf<T> = One::one();
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let name: RoleName = BoundedVec::truncate_from(b"cashier".into());
+
+		// Defines the last role the community can have
+		let role = T::MaxRoles::get().saturating_sub(1) as RoleId;
+		for defined in 0..role {
+			CommunityRoles::<T>::insert(community_id, defined, RoleName::default());
+		}
+
+		#[extrinsic_call]
+		_(origin, role, name.clone());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::RoleDefined {
+				id: community_id,
+				role,
+				name,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_role(h: Linear<0, 1024>) -> Result<(), BenchmarkError> {
+		// setup code
+		let community_id: CommunityIdOf<T> = One::one();
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		CommunityRoles::<T>::insert(community_id, 1, RoleName::truncate_from(b"cashier".into()));
+		for i in 0..h {
+			let membership_id: T::MembershipId = i.into();
+			MembershipRoles::<T>::insert((community_id, 1, membership_id), ());
+		}
+
+		#[extrinsic_call]
+		_(origin, 1, h);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::RoleRemoved {
+				id: community_id,
+				role: 1,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn assign_role() -> Result<(), BenchmarkError> {
+		// setup code
+		let first_member: AccountIdOf<T> = account("founder", 0, 0);
+		setup_account::<T>(&first_member)?;
+		let community_id: CommunityIdOf<T> = One::one();
+		Pallet::<T>::try_register(None, community_id, "Test Community", &first_member, None, None, None)?;
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+
+		let membership_id: T::MembershipId = 1u32.into();
+		T::CreateMemberships::mint_into(&community_id, &membership_id, &first_member, &Default::default(), true)?;
+		CommunityRoles::<T>::insert(community_id, 1, RoleName::truncate_from(b"cashier".into()));
+
+		#[extrinsic_call]
+		_(origin, membership_id.clone(), 1);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::RoleAssigned {
+				id: community_id,
+				membership: membership_id,
+				role: 1,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn revoke_role() -> Result<(), BenchmarkError> {
+		// setup code
+		let community_id: CommunityIdOf<T> = One::one();
+		let origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let membership_id: T::MembershipId = 1u32.into();
+		MembershipRoles::<T>::insert((community_id, 1, membership_id.clone()), ());
+
+		#[extrinsic_call]
+		_(origin, membership_id.clone(), 1);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::RoleRevoked {
+				id: community_id,
+				membership: membership_id,
+				role: 1,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, sp_io::TestExternalities::new(Default::default()), mock::Test);
 }
@@ -0,0 +1,161 @@
+use super::*;
+
+use core::marker::PhantomData;
+use frame_contrib_traits::gas_tank::GasBurner;
+use sp_runtime::Perbill;
+
+/// The attribute of a membership that holds its rank within the community.
+pub const MEMBERSHIP_RANK: &[u8; 22] = b"membership_member_rank";
+
+/// How the capacity of a gas tank is computed when the tank is configured.
+#[derive(Default, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum TankPolicy {
+	/// The tank gets exactly the configured capacity.
+	#[default]
+	Standard,
+	/// The gas left unused since the tank was last configured, or last
+	/// refilled if it has a periodicity, is added to the new capacity.
+	Rollover,
+	/// The capacity is increased by the given ratio for every rank of the
+	/// membership.
+	RankMultiplier(Perbill),
+	/// The given amount of gas is added to the capacity, only the first time
+	/// the tank is configured.
+	Bonus(Weight),
+}
+
+/// The gas consumption of a membership.
+#[derive(Default, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct GasUsage {
+	/// The capacity of the tank as last configured, once its policy was
+	/// applied. `None` means unlimited.
+	pub capacity: Option<Weight>,
+	/// The gas burned since the tank was last configured or, for tanks with
+	/// a periodicity, since it was last refilled.
+	pub used: Weight,
+	/// The gas burned since the tank was first configured.
+	pub total_used: Weight,
+}
+
+/// Wraps a gas tank to record how much gas is burned by every membership.
+///
+/// The burned gas is attributed to the first membership held by the account
+/// with a tank configured via this pallet, which matches the order in which
+/// memberships are picked to pay for gas. Only the first
+/// [`MaxMembershipLookups`](Config::MaxMembershipLookups) memberships of the
+/// account are checked, so the lookup is covered by the benchmarks of the
+/// gas transaction payment.
+pub struct RecordGasUsage<T, Tank>(PhantomData<(T, Tank)>);
+
+impl<T, Tank> GasBurner for RecordGasUsage<T, Tank>
+where
+	T: Config,
+	Tank: GasBurner<AccountId = AccountIdOf<T>, Gas = Weight>,
+{
+	type AccountId = AccountIdOf<T>;
+	type Gas = Weight;
+
+	fn check_available_gas(who: &Self::AccountId, estimated: &Self::Gas) -> Option<Self::Gas> {
+		Tank::check_available_gas(who, estimated)
+	}
+
+	fn burn_gas(who: &Self::AccountId, expected_remaining: &Self::Gas, used: &Self::Gas) -> Self::Gas {
+		let remaining = Tank::burn_gas(who, expected_remaining, used);
+		Pallet::<T>::note_gas_burned(who, *used);
+		remaining
+	}
+}
+
+impl<T, Tank> MakeTank for RecordGasUsage<T, Tank>
+where
+	Tank: MakeTank,
+{
+	type TankId = Tank::TankId;
+	type Gas = Tank::Gas;
+	type BlockNumber = Tank::BlockNumber;
+
+	fn make_tank(
+		tank_id: &Self::TankId,
+		capacity: Option<Self::Gas>,
+		periodicity: Option<Self::BlockNumber>,
+	) -> DispatchResult {
+		Tank::make_tank(tank_id, capacity, periodicity)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The capacity of the tank of a membership once the given policy is
+	/// applied.
+	pub(crate) fn tank_capacity(
+		tank_id: &(CommunityIdOf<T>, T::MembershipId),
+		capacity: Option<Weight>,
+		policy: &TankPolicy,
+	) -> Option<Weight> {
+		let capacity = capacity?;
+		let (community_id, membership_id) = tank_id;
+		let usage = MembershipGasUsage::<T>::get(membership_id).map(|mut usage| {
+			Self::apply_refills(membership_id, &mut usage);
+			usage
+		});
+
+		Some(match policy {
+			TankPolicy::Standard => capacity,
+			TankPolicy::Rollover => {
+				let unused = usage
+					.and_then(|usage| usage.capacity.map(|previous| previous.saturating_sub(usage.used)))
+					.unwrap_or_default();
+				capacity.saturating_add(unused)
+			}
+			TankPolicy::RankMultiplier(ratio) => {
+				let rank: u8 =
+					T::CreateMemberships::typed_system_attribute(community_id, Some(membership_id), &MEMBERSHIP_RANK)
+						.unwrap_or_default();
+				capacity.saturating_add((*ratio * capacity).saturating_mul(rank.into()))
+			}
+			TankPolicy::Bonus(bonus) if usage.is_none() => capacity.saturating_add(*bonus),
+			TankPolicy::Bonus(_) => capacity,
+		})
+	}
+
+	/// Records the gas burned by `who` against the first of its memberships
+	/// that has a tank, and the community it belongs to.
+	pub fn note_gas_burned(who: &AccountIdOf<T>, used: Weight) {
+		let manager_collection_id = T::MembershipsManagerCollectionId::get();
+		let Some((community_id, membership_id)) = T::CreateMemberships::owned(who)
+			.take(T::MaxMembershipLookups::get() as usize)
+			.find(|(community_id, membership_id)| {
+				*community_id != manager_collection_id && MembershipGasUsage::<T>::contains_key(membership_id)
+			})
+		else {
+			return;
+		};
+
+		MembershipGasUsage::<T>::mutate(&membership_id, |maybe_usage| {
+			if let Some(usage) = maybe_usage {
+				Self::apply_refills(&membership_id, usage);
+				usage.used.saturating_accrue(used);
+				usage.total_used.saturating_accrue(used);
+			}
+		});
+		CommunityGasUsage::<T>::mutate(community_id, |total| total.saturating_accrue(used));
+	}
+
+	/// Starts the current period of a tank with a periodicity, if it has been
+	/// refilled since the period `usage` was recorded in, so the gas burned
+	/// before the refill no longer counts as used. Periods are counted from
+	/// the block the tank was configured at.
+	fn apply_refills(membership_id: &T::MembershipId, usage: &mut GasUsage) {
+		let Some((period_start, periodicity)) = MembershipGasPeriods::<T>::get(membership_id) else {
+			return;
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		if periodicity.is_zero() || now < period_start.saturating_add(periodicity) {
+			return;
+		}
+
+		let elapsed = now.saturating_sub(period_start);
+		let current_start = period_start.saturating_add(elapsed.saturating_sub(elapsed % periodicity));
+		MembershipGasPeriods::<T>::insert(membership_id, (current_start, periodicity));
+		usage.used = Weight::zero();
+	}
+}
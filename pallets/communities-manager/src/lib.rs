@@ -2,33 +2,51 @@
 
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 use frame_contrib_traits::{gas_tank::MakeTank, tracks::MutateTracks};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
+		fungible::Mutate as FunMutate,
+		fungibles::{
+			metadata::Mutate as FunsMetadataMutate, Create as FunsCreate, Inspect as FunsInspect, Mutate as FunsMutate,
+		},
 		nonfungibles_v2::Inspect,
 		nonfungibles_v2::Mutate as ItemMutate,
-		nonfungibles_v2::{Create as CollectionCreate, Trading},
+		nonfungibles_v2::{Create as CollectionCreate, Destroy, InspectEnumerable, Trading, Transfer},
+		tokens::Preservation,
 		Incrementable, OriginTrait, RankedMembers,
 	},
 };
 use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 use pallet_communities::{
-	AccountIdOf, AssetIdOf, CommunityIdOf, DecisionMethodFor, NativeBalanceOf, Origin as CommunityOrigin,
-	PalletsOriginOf, RuntimeOriginFor,
+	origin::Subset, AccountIdOf, AssetIdOf, CommunityIdOf, DecisionMethodFor, NativeBalanceOf,
+	Origin as CommunityOrigin, PalletsOriginOf, RuntimeOriginFor,
 };
 use pallet_nfts::{CollectionConfig, MintSettings, MintType};
 use pallet_referenda::{TrackInfo, TracksInfo};
 use parity_scale_codec::Decode;
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	str_array,
-	traits::{Get, StaticLookup},
+	traits::{CheckedAdd, Convert, Get, IdentifyAccount, Saturating, StaticLookup, UniqueSaturatedInto, Verify, Zero},
 };
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod runtime_api;
+
+mod gas;
+pub mod migrations;
+mod ranks;
+mod roles;
+mod tracks;
+pub use gas::*;
+pub use ranks::*;
+pub use roles::*;
+pub use tracks::*;
+
 #[cfg(test)]
 pub(crate) mod mock;
 #[cfg(test)]
@@ -40,35 +58,177 @@ pub mod weights;
 pub use weights::*;
 
 type TrackInfoOf<T> = TrackInfo<NativeBalanceOf<T>, BlockNumberFor<T>>;
+pub type CommunityTrackOf<T> = CommunityTrack<TrackInfoOf<T>>;
+pub type CommunityMetricsOf<T> = CommunityMetrics<NativeBalanceOf<T>, BlockNumberFor<T>>;
+pub type RankRequirementsOf<T> = BoundedVec<CommunityMetricsOf<T>, ConstU32<MAX_RANK_REQUIREMENTS>>;
+
+/// A registration deposit: the amount, who paid it, and who received it.
+pub type DepositOf<T> = (NativeBalanceOf<T>, AccountIdOf<T>, AccountIdOf<T>);
+
+pub type CommunityName = BoundedVec<u8, ConstU32<25>>;
+pub type CommunityDescription = BoundedVec<u8, ConstU32<256>>;
+pub type CommunityUri = BoundedVec<u8, ConstU32<128>>;
+
+/// The public identity of a community, so it can be displayed by wallets and
+/// other applications. Fields other than `name` can be left empty.
+#[derive(Default, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct CommunityMetadata {
+	pub name: CommunityName,
+	pub description: CommunityDescription,
+	pub logo: CommunityUri,
+	pub website: CommunityUri,
+	/// The id of the community's Matrix room (e.g. `!room:matrix.org`).
+	pub matrix_room: CommunityUri,
+}
 
-#[derive(Default, Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+#[derive(Default, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub struct TankConfig<Weight, BlockNumber> {
 	capacity: Option<Weight>,
 	periodicity: Option<BlockNumber>,
+	policy: TankPolicy,
+}
+
+/// A batch of memberships to be minted.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct MintRequest<MembershipId, Balance, BlockNumber> {
+	pub starting_at: MembershipId,
+	pub next: MembershipId,
+	pub amount: u32,
+	pub minted: u32,
+	pub price: Balance,
+	pub tank_config: TankConfig<Weight, BlockNumber>,
+	pub maybe_expiration: Option<BlockNumber>,
+}
+
+/// The price a community charges for renewing one of its memberships.
+#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum RenewalPrice<AssetId, NativeBalance, AssetBalance> {
+	Native(NativeBalance),
+	Asset(AssetId, AssetBalance),
+}
+
+pub type AssetBalanceOf<T> = <<T as pallet_communities::Config>::Assets as FunsInspect<AccountIdOf<T>>>::Balance;
+
+pub type TokenName = BoundedVec<u8, ConstU32<32>>;
+pub type TokenSymbol = BoundedVec<u8, ConstU32<10>>;
+
+/// The own token of a community, optionally created when registering it.
+#[derive(
+	Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo, Serialize, Deserialize,
+)]
+pub struct CommunityToken<Balance> {
+	pub name: TokenName,
+	pub symbol: TokenSymbol,
+	pub decimals: u8,
+	pub min_balance: Balance,
+	/// The amount of tokens minted to the community account.
+	pub initial_supply: Balance,
+}
+
+pub type CommunityTokenOf<T> = CommunityToken<AssetBalanceOf<T>>;
+
+/// An invitation to join a community, signed off-chain by the community
+/// account or one of its invitation signers. Each invitation can be claimed
+/// only once, until it expires, by whoever holds its one-time `key`.
+#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct Invitation<CommunityId, BlockNumber, AccountId> {
+	pub community_id: CommunityId,
+	pub nonce: u32,
+	pub expires_at: BlockNumber,
+	/// The key handed out along with the invitation, which signs the account
+	/// claiming it.
+	pub key: AccountId,
+}
+
+/// The prefix of the payload signed to issue an invitation.
+pub const INVITATION_PREFIX: &[u8; 10] = b"invitation";
+/// The prefix of the payload signed with the key of an invitation to claim
+/// it.
+pub const INVITATION_CLAIM_PREFIX: &[u8; 16] = b"invitation_claim";
+
+pub type InvitationOf<T> = Invitation<CommunityIdOf<T>, BlockNumberFor<T>, AccountIdOf<T>>;
+pub type InventoryIdOf<T> =
+	<<T as Config>::Inventories as CommunityInventories<CommunityIdOf<T>, AccountIdOf<T>>>::InventoryId;
+pub type RenewalPriceOf<T> = RenewalPrice<AssetIdOf<T>, NativeBalanceOf<T>, AssetBalanceOf<T>>;
+
+/// Settles the payment of a membership bought with assets.
+pub trait MembershipPayments<AccountId, AssetId, Balance> {
+	/// Charges `amount` of `asset` from `who`, and pays it to `beneficiary`.
+	fn settle(who: &AccountId, beneficiary: &AccountId, asset: AssetId, amount: Balance) -> DispatchResult;
+
+	/// The weight of [`settle`](MembershipPayments::settle), on top of the
+	/// weight of a plain transfer.
+	fn settle_weight() -> Weight {
+		Weight::zero()
+	}
 }
 
+/// Creates the inventories where communities list the items they sell.
+pub trait CommunityInventories<CommunityId, AccountId> {
+	type InventoryId: Parameter + MaxEncodedLen + MaybeSerializeDeserialize;
+
+	/// Creates the inventory `id` of `community_id`, owned by `owner`.
+	fn create_inventory(community_id: CommunityId, id: Self::InventoryId, owner: &AccountId) -> DispatchResult;
+}
+
+impl<CommunityId, AccountId> CommunityInventories<CommunityId, AccountId> for () {
+	type InventoryId = u32;
+
+	fn create_inventory(_: CommunityId, _: Self::InventoryId, _: &AccountId) -> DispatchResult {
+		Err(DispatchError::Unavailable)
+	}
+}
+
+/// Settles membership payments with a plain transfer of `Assets`.
+pub struct TransferAssets<Assets>(PhantomData<Assets>);
+
+impl<AccountId, Assets: FunsMutate<AccountId>> MembershipPayments<AccountId, Assets::AssetId, Assets::Balance>
+	for TransferAssets<Assets>
+{
+	fn settle(
+		who: &AccountId,
+		beneficiary: &AccountId,
+		asset: Assets::AssetId,
+		amount: Assets::Balance,
+	) -> DispatchResult {
+		Assets::transfer(asset, who, beneficiary, amount, Preservation::Expendable).map(|_| ())
+	}
+}
+
+/// The attribute of a membership that holds the block number at which it
+/// expires.
+pub const MEMBERSHIP_EXPIRATION: &[u8; 21] = b"membership_expiration";
+
+pub type MintRequestOf<T> =
+	MintRequest<<T as pallet_communities::Config>::MembershipId, NativeBalanceOf<T>, BlockNumberFor<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::DefaultNoBound;
 	use parity_scale_codec::HasCompact;
 
-	type CommunityName = BoundedVec<u8, ConstU32<25>>;
-
 	/// Configure the pallet by specifying the parameters and types on which it
 	/// depends.
 	#[pallet::config]
 	pub trait Config:
 		frame_system::Config<RuntimeEvent: From<Event<Self>>>
-		+ pallet_communities::Config<MembershipId: Incrementable + HasCompact + MaybeSerializeDeserialize>
+		+ pallet_communities::Config<
+			MembershipId: Incrementable + HasCompact + MaybeSerializeDeserialize + CheckedAdd + PartialOrd + From<u32>,
+			Assets: FunsMutate<AccountIdOf<Self>>
+			            + FunsCreate<AccountIdOf<Self>>
+			            + FunsMetadataMutate<AccountIdOf<Self>>,
+		>
 	where
 		AssetIdOf<Self>: MaybeSerializeDeserialize,
 	{
 		type CreateCollection: CollectionCreate<
-			AccountIdOf<Self>,
-			CollectionConfig<NativeBalanceOf<Self>, BlockNumberFor<Self>, CommunityIdOf<Self>>,
-			CollectionId = CommunityIdOf<Self>,
-		>;
+				AccountIdOf<Self>,
+				CollectionConfig<NativeBalanceOf<Self>, BlockNumberFor<Self>, CommunityIdOf<Self>>,
+				CollectionId = CommunityIdOf<Self>,
+			> + Destroy<AccountIdOf<Self>>
+			+ InspectEnumerable<AccountIdOf<Self>>
+			+ Transfer<AccountIdOf<Self>>;
 
 		type MakeTank: MakeTank<
 			Gas = Weight,
@@ -84,15 +244,45 @@ pub mod pallet {
 				RuntimeOrigin = PalletsOriginOf<Self>,
 			>;
 
+		/// The amount of blocks in an hour, used to compute the periods of the
+		/// [`TrackTemplate`]s.
+		type BlocksPerHour: Get<u32>;
+
+		/// The maximum amount of tracks a community can add besides its main
+		/// track.
+		type MaxCommunityTracks: Get<u32>;
+
+		/// The maximum amount of roles a community can define.
+		#[pallet::constant]
+		type MaxRoles: Get<u32>;
+
+		/// The maximum amount of memberships of an account checked when
+		/// looking for the roles it holds in a community, or for the gas tank
+		/// it burns gas from.
+		type MaxMembershipLookups: Get<u32>;
+
 		type RankedCollective: RankedMembers<AccountId = AccountIdOf<Self>>;
 
+		/// The id of the token of a community, within the range of asset ids
+		/// reserved for community tokens.
+		type CommunityAssetId: Convert<CommunityIdOf<Self>, AssetIdOf<Self>>;
+
+		/// Origin allowed to set the requirements communities must meet to
+		/// hold each rank in the [`RankedCollective`](Config::RankedCollective).
+		type RankRequirementsOrigin: EnsureOrigin<OriginFor<Self>>;
+
+		/// Provides the metrics used to rank communities.
+		type CommunityMetrics: MetricsProvider<CommunityIdOf<Self>, NativeBalanceOf<Self>>;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
-		type RegisterOrigin: EnsureOrigin<
-			OriginFor<Self>,
-			Success = Option<(NativeBalanceOf<Self>, AccountIdOf<Self>, AccountIdOf<Self>)>,
-		>;
+		type RegisterOrigin: EnsureOrigin<OriginFor<Self>, Success = Option<DepositOf<Self>>>;
+
+		/// Origin allowed to deregister a community. Succeeds with `None` when
+		/// any community can be deregistered (i.e. root), or with
+		/// `Some(community_id)` when only that community can be.
+		type DeregisterOrigin: EnsureOrigin<OriginFor<Self>, Success = Option<CommunityIdOf<Self>>>;
 
 		type CreateMembershipsOrigin: EnsureOrigin<OriginFor<Self>>;
 
@@ -102,6 +292,36 @@ pub mod pallet {
 
 		type ItemConfig: Default;
 
+		/// How long a renewal extends the expiration of a membership.
+		type RenewalPeriod: Get<BlockNumberFor<Self>>;
+
+		/// How long an expired membership remains assigned before anyone can
+		/// reclaim it.
+		type ReclaimGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The reward paid by the community to whoever reclaims one of its
+		/// expired memberships.
+		type ReclaimReward: Get<NativeBalanceOf<Self>>;
+
+		/// Off-chain signature used to sign membership invitations.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key of the signers of membership invitations.
+		type OffchainPublic: IdentifyAccount<AccountId = AccountIdOf<Self>>;
+
+		/// The maximum amount of memberships minted within a single call or
+		/// block. Larger batches are queued, and minted across several blocks.
+		type MaxMintsPerBlock: Get<u32>;
+
+		/// The maximum amount of batches of memberships waiting to be minted.
+		type MaxQueuedMints: Get<u32>;
+
+		/// Settles the payment of memberships bought with assets.
+		type MembershipPayments: MembershipPayments<AccountIdOf<Self>, AssetIdOf<Self>, AssetBalanceOf<Self>>;
+
+		/// Creates the inventories of the initial communities.
+		type Inventories: CommunityInventories<CommunityIdOf<Self>, AccountIdOf<Self>>;
+
 		type CreateMemberships: CollectionCreate<
 				AccountIdOf<Self>,
 				CollectionConfig<NativeBalanceOf<Self>, BlockNumberFor<Self>, CommunityIdOf<Self>>,
@@ -115,12 +335,117 @@ pub mod pallet {
 				NativeBalanceOf<Self>,
 				CollectionId = CommunityIdOf<Self>,
 				ItemId = Self::MembershipId,
-			>;
+			> + Transfer<AccountIdOf<Self>, CollectionId = CommunityIdOf<Self>, ItemId = Self::MembershipId>
+			+ InspectEnumerable<AccountIdOf<Self>, CollectionId = CommunityIdOf<Self>, ItemId = Self::MembershipId>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// The deposit charged when registering a community, which is returned to
+	/// the payer once the community gets deregistered.
+	#[pallet::storage]
+	pub type RegistrationDeposit<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, DepositOf<T>>;
+
+	/// The batches of memberships waiting to be minted by the `on_idle` hook.
+	/// The ranges of these batches are reserved until they're fully minted.
+	#[pallet::storage]
+	pub type MintQueue<T: Config> = StorageValue<_, BoundedVec<MintRequestOf<T>, T::MaxQueuedMints>, ValueQuery>;
+
+	/// The price each community charges for renewing its memberships.
+	#[pallet::storage]
+	pub type RenewalPrices<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, RenewalPriceOf<T>>;
+
+	/// The price of the memberships on sale for assets.
+	#[pallet::storage]
+	pub type AssetPrices<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MembershipId, (AssetIdOf<T>, AssetBalanceOf<T>)>;
+
+	/// The account acting as the admin of a community.
+	#[pallet::storage]
+	pub type CommunityAdmin<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, AccountIdOf<T>>;
+
+	/// The account a community admin is being transferred to, waiting for it
+	/// to accept.
+	#[pallet::storage]
+	pub type PendingAdmin<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, AccountIdOf<T>>;
+
+	/// The accounts allowed to sign membership invitations on behalf of a
+	/// community, besides the community account.
+	#[pallet::storage]
+	pub type InvitationSigners<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CommunityIdOf<T>, Blake2_128Concat, AccountIdOf<T>, ()>;
+
+	/// The nonces of the invitations of a community that have been claimed,
+	/// along with the block they expire at, after which they can be pruned.
+	#[pallet::storage]
+	pub type ClaimedInvitations<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CommunityIdOf<T>, Twox64Concat, u32, BlockNumberFor<T>>;
+
+	/// The roles defined by a community.
+	#[pallet::storage]
+	pub type CommunityRoles<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CommunityIdOf<T>, Twox64Concat, RoleId, RoleName>;
+
+	/// The memberships of a community that hold each of its roles.
+	#[pallet::storage]
+	pub type MembershipRoles<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, CommunityIdOf<T>>,
+			NMapKey<Twox64Concat, RoleId>,
+			NMapKey<Blake2_128Concat, T::MembershipId>,
+		),
+		(),
+	>;
+
+	/// The tracks a community has added besides its main track, whose id is
+	/// the id of the community.
+	#[pallet::storage]
+	pub type AdditionalTracks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CommunityIdOf<T>,
+		BoundedVec<CommunityIdOf<T>, T::MaxCommunityTracks>,
+		ValueQuery,
+	>;
+
+	/// The community each of the additional tracks belongs to.
+	#[pallet::storage]
+	pub type TrackCommunity<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, CommunityIdOf<T>>;
+
+	/// The block at which a community was registered.
+	#[pallet::storage]
+	pub type RegisteredAt<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, BlockNumberFor<T>>;
+
+	/// The minimum metrics a community must have to hold each rank in the
+	/// collective, starting from rank `1`.
+	#[pallet::storage]
+	pub type RankRequirements<T: Config> = StorageValue<_, RankRequirementsOf<T>, ValueQuery>;
+
+	/// The gas consumption of the memberships with a tank configured via this
+	/// pallet.
+	#[pallet::storage]
+	pub type MembershipGasUsage<T: Config> = StorageMap<_, Blake2_128Concat, T::MembershipId, GasUsage>;
+
+	/// The refill period of the tanks configured with a periodicity: the
+	/// block their current period started at, and how long periods last.
+	#[pallet::storage]
+	pub type MembershipGasPeriods<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::MembershipId, (BlockNumberFor<T>, BlockNumberFor<T>)>;
+
+	/// The gas burned by the members of a community.
+	#[pallet::storage]
+	pub type CommunityGasUsage<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, Weight, ValueQuery>;
+
+	/// The metadata of a community.
+	#[pallet::storage]
+	pub type Metadata<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, CommunityMetadata>;
+
 	/// A genesis community info.
 	pub type GenesisCommunityOf<T> = (
 		// community_id
@@ -149,40 +474,116 @@ pub mod pallet {
 		Option<BlockNumberFor<T>>,
 	);
 
+	/// A genesis member info.
+	pub type GenesisMemberOf<T> = (
+		// community_id
+		CommunityIdOf<T>,
+		// who
+		AccountIdOf<T>,
+		// membership_id
+		<T as pallet_communities::Config>::MembershipId,
+		// rank
+		u8,
+	);
+
+	/// A genesis gas tank info, for an already assigned membership.
+	pub type GenesisGasTankOf<T> = (
+		// community_id
+		CommunityIdOf<T>,
+		// membership_id
+		<T as pallet_communities::Config>::MembershipId,
+		// tank_config
+		(Option<Weight>, Option<BlockNumberFor<T>>),
+	);
+
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
 		/// A list of initial communities, with the most basic settings.
 		pub communities: Vec<GenesisCommunityOf<T>>,
 		pub memberships: Vec<GenesisMembershipOf<T>>,
+		/// The track of the initial communities, either a template or a custom
+		/// one. Communities not listed here use the default track.
+		pub tracks: Vec<(CommunityIdOf<T>, GenesisTrack)>,
+		/// The tokens of the initial communities. The community accounts must
+		/// be endowed to pay for the token metadata deposit.
+		pub tokens: Vec<(CommunityIdOf<T>, CommunityTokenOf<T>)>,
+		/// The initial members of the communities, taking memberships from
+		/// the ones created in `memberships`.
+		pub members: Vec<GenesisMemberOf<T>>,
+		/// The gas tanks of the memberships assigned to the initial members.
+		pub gas_tanks: Vec<GenesisGasTankOf<T>>,
+		/// The initial inventories of the communities, owned by the community
+		/// accounts.
+		pub inventories: Vec<(CommunityIdOf<T>, InventoryIdOf<T>)>,
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
 			for (community_id, name, admin, maybe_decision_method, maybe_rank) in &self.communities {
+				let maybe_track = self
+					.tracks
+					.iter()
+					.find(|(id, _)| id == community_id)
+					.map(|(_, track)| track.community_track().expect("invalid genesis track"));
 				Pallet::<T>::try_register(
 					None, // genesis communities are given for free :)
 					*community_id,
 					name.as_str(),
 					admin,
 					maybe_decision_method.clone(),
-					None, // use the default track info. Let the community set the track later.
+					maybe_track,
 					*maybe_rank,
 				)
 				.unwrap();
 			}
 
+			for (community_id, token) in &self.tokens {
+				Pallet::<T>::create_community_token(*community_id, token.clone()).unwrap();
+			}
+
 			for (starting_at, amount, price, tank_config, maybe_expiration) in &self.memberships {
 				let (capacity, periodicity) = *tank_config;
-				Pallet::<T>::try_create_memberships(
+				let mut request = Pallet::<T>::new_mint_request(
 					starting_at.clone(),
-					*amount,
+					(*amount).into(),
 					*price,
-					TankConfig { capacity, periodicity },
+					TankConfig {
+						capacity,
+						periodicity,
+						policy: TankPolicy::Standard,
+					},
 					*maybe_expiration,
 				)
-				.unwrap()
+				.unwrap();
+				Pallet::<T>::mint_memberships(&mut request, (*amount).into()).unwrap();
+			}
+
+			for (community_id, who, membership_id, rank) in &self.members {
+				Pallet::<T>::add_genesis_member(*community_id, who, membership_id.clone(), *rank).unwrap();
+			}
+
+			for (community_id, membership_id, tank_config) in &self.gas_tanks {
+				let (capacity, periodicity) = *tank_config;
+				Pallet::<T>::do_set_gas_tank(
+					&(*community_id, membership_id.clone()),
+					&TankConfig {
+						capacity,
+						periodicity,
+						policy: TankPolicy::Standard,
+					},
+				)
+				.unwrap();
+			}
+
+			for (community_id, inventory_id) in &self.inventories {
+				T::Inventories::create_inventory(
+					*community_id,
+					inventory_id.clone(),
+					&pallet_communities::Pallet::<T>::community_account(community_id),
+				)
+				.unwrap();
 			}
 		}
 	}
@@ -195,8 +596,121 @@ pub mod pallet {
 		/// The community with [`CommunityId`](pallet_communities::CommunityId)
 		/// has been created.
 		CommunityRegistered { id: T::CommunityId },
-		/// The
+		/// The community with [`CommunityId`](pallet_communities::CommunityId)
+		/// has been deregistered.
+		CommunityDeregistered { id: T::CommunityId },
+		/// The admin of a community is being transferred, pending for
+		/// `new_admin` to accept it.
+		AdminTransferRequested {
+			id: T::CommunityId,
+			new_admin: AccountIdOf<T>,
+		},
+		/// The admin of a community has been transferred.
+		AdminTransferred { id: T::CommunityId, admin: AccountIdOf<T> },
+		/// An account has been allowed (or disallowed) to sign membership
+		/// invitations on behalf of a community.
+		InvitationSignerSet {
+			id: T::CommunityId,
+			signer: AccountIdOf<T>,
+			enabled: bool,
+		},
+		/// An invitation has been claimed, and `who` is now a member of the
+		/// community.
+		InvitationClaimed {
+			id: T::CommunityId,
+			nonce: u32,
+			who: AccountIdOf<T>,
+		},
+		/// A role has been defined (or renamed) by a community.
+		RoleDefined {
+			id: T::CommunityId,
+			role: RoleId,
+			name: RoleName,
+		},
+		/// A role has been removed by a community.
+		RoleRemoved { id: T::CommunityId, role: RoleId },
+		/// A role has been assigned to a membership.
+		RoleAssigned {
+			id: T::CommunityId,
+			membership: T::MembershipId,
+			role: RoleId,
+		},
+		/// A role has been revoked from a membership.
+		RoleRevoked {
+			id: T::CommunityId,
+			membership: T::MembershipId,
+			role: RoleId,
+		},
+		/// The token of a community has been created.
+		CommunityTokenCreated { id: T::CommunityId, asset: AssetIdOf<T> },
+		/// The metadata of the community with
+		/// [`CommunityId`](pallet_communities::CommunityId) has been set.
+		MetadataSet {
+			id: T::CommunityId,
+			metadata: CommunityMetadata,
+		},
+		/// The price for renewing the memberships of a community has been
+		/// set.
+		RenewalPriceSet {
+			id: T::CommunityId,
+			price: Option<RenewalPriceOf<T>>,
+		},
+		/// A membership has been renewed.
+		MembershipRenewed {
+			id: T::CommunityId,
+			membership: T::MembershipId,
+			expiration: BlockNumberFor<T>,
+		},
+		/// An expired membership has been reclaimed, and `reward` was paid to
+		/// whoever reclaimed it.
+		MembershipReclaimed {
+			id: T::CommunityId,
+			membership: T::MembershipId,
+			reward: NativeBalanceOf<T>,
+		},
+		/// The price of a membership in assets has been set.
+		AssetPriceSet {
+			membership: T::MembershipId,
+			price: Option<(AssetIdOf<T>, AssetBalanceOf<T>)>,
+		},
+		/// A membership has been bought for a community.
+		MembershipBought {
+			membership: T::MembershipId,
+			community: T::CommunityId,
+			buyer: AccountIdOf<T>,
+		},
+		/// The track `track` of the community with
+		/// [`CommunityId`](pallet_communities::CommunityId) has been updated.
+		TrackUpdated { id: T::CommunityId, track: T::CommunityId },
+		/// A community has added the track `track`, mapped to one of its
+		/// sub-origins.
+		TrackAdded { id: T::CommunityId, track: T::CommunityId },
+		/// A community has removed one of its additional tracks.
+		TrackRemoved { id: T::CommunityId, track: T::CommunityId },
+		/// The requirements to hold each rank in the collective have been set.
+		RankRequirementsSet { requirements: RankRequirementsOf<T> },
+		/// A community has been promoted in the collective.
+		CommunityPromoted { id: T::CommunityId, rank: u16 },
+		/// A community has been demoted in the collective.
+		CommunityDemoted { id: T::CommunityId, rank: u16 },
+		/// All the memberships of a batch have been minted.
 		MembershipsCreated { starting_at: T::MembershipId, amount: u32 },
+		/// A batch of memberships has been queued, to be minted across
+		/// several blocks.
+		MembershipsQueued { starting_at: T::MembershipId, amount: u32 },
+		/// Some memberships of a queued batch have been minted.
+		MembershipsMinted {
+			starting_at: T::MembershipId,
+			minted: u32,
+			remaining: u32,
+		},
+		/// Minting a queued batch of memberships failed, and the batch has
+		/// been dropped from the queue.
+		MembershipsMintingAborted {
+			starting_at: T::MembershipId,
+			minted: u32,
+			error: DispatchError,
+		},
 	}
 
 	// Errors inform users that something worked or went wrong.
@@ -206,8 +720,74 @@ pub mod pallet {
 		InvalidCommunityName,
 		/// It was not possible to register the community
 		CannotRegister,
-		/// The amount of memberships to create exceeds the limit of 1024
+		/// The range of memberships to create exceeds the available ids
 		CreatingTooManyMemberships,
+		/// The community does not exist
+		UnknownCommunity,
+		/// The given witness is lower than the amount of memberships the
+		/// community has
+		InvalidMembershipsWitness,
+		/// The given amount of holders is lower than the memberships holding
+		/// the role
+		InvalidHoldersWitness,
+		/// The range of memberships to create overlaps with existing ones
+		MembershipsOverlap,
+		/// There are too many batches of memberships waiting to be minted
+		MintQueueFull,
+		/// The community hasn't set a price to renew its memberships
+		RenewalNotAvailable,
+		/// The membership does not have an expiration
+		MembershipDoesNotExpire,
+		/// The membership is not assigned to any member
+		MembershipNotAssigned,
+		/// The membership has not expired, or is within its grace period
+		MembershipNotExpired,
+		/// The membership is not on sale for assets
+		MembershipNotForSale,
+		/// The community is not a member of the collective
+		NotInCollective,
+		/// There is no pending admin transfer for the caller
+		NoPendingAdminTransfer,
+		/// The invitation has expired
+		InvitationExpired,
+		/// The invitation has already been claimed
+		InvitationAlreadyClaimed,
+		/// The invitation is not signed by the community or one of its
+		/// invitation signers
+		InvalidInvitationSignature,
+		/// The claimant is not signed with the key of the invitation
+		InvalidClaimSignature,
+		/// The invitation has not been claimed
+		UnknownInvitation,
+		/// The invitation has not expired yet
+		InvitationNotExpired,
+		/// The community has not defined the role
+		UnknownRole,
+		/// The membership does not belong to the community
+		MembershipNotInCommunity,
+		/// The community does not meet the requirements for the next rank
+		RankRequirementsNotMet,
+		/// The community meets the requirements for its current rank
+		RankRequirementsMet,
+		/// The track does not belong to the community
+		UnknownTrack,
+		/// The community can't add more tracks
+		TooManyTracks,
+		/// The given witness is lower than the amount of invitation signers
+		/// and claimed invitations the community has
+		InvalidInvitationsWitness,
+		/// The given witness is lower than the amount of roles assigned to the
+		/// memberships of the community
+		InvalidRoleAssignmentsWitness,
+		/// The community can't define more roles
+		TooManyRoles,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::mint_queued(remaining_weight)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke
@@ -216,6 +796,12 @@ pub mod pallet {
 	// weight and must return a DispatchResult.
 	#[pallet::call(weight(<T as Config>::WeightInfo))]
 	impl<T: Config> Pallet<T> {
+		/// Registers a new community. Optionally, creates the community
+		/// token, owned by the community account, which must hold enough
+		/// funds to pay for the token metadata deposit.
+		#[pallet::weight(<T as Config>::WeightInfo::register().saturating_add(
+			maybe_token.as_ref().map_or(Weight::zero(), |_| <T as Config>::WeightInfo::create_community_token())
+		))]
 		#[pallet::call_index(0)]
 		pub fn register(
 			origin: OriginFor<T>,
@@ -223,7 +809,8 @@ pub mod pallet {
 			name: CommunityName,
 			first_admin: pallet_communities::AccountIdLookupOf<T>,
 			maybe_decision_method: Option<DecisionMethodFor<T>>,
-			maybe_track_info: Option<TrackInfoOf<T>>,
+			maybe_track: Option<CommunityTrackOf<T>>,
+			maybe_token: Option<CommunityTokenOf<T>>,
 		) -> DispatchResult {
 			Self::try_register(
 				T::RegisterOrigin::ensure_origin(origin)?,
@@ -231,25 +818,50 @@ pub mod pallet {
 				core::str::from_utf8(&name).map_err(|_| Error::<T>::InvalidCommunityName)?,
 				&T::Lookup::lookup(first_admin)?,
 				maybe_decision_method,
-				maybe_track_info,
+				maybe_track,
 				None,
-			)
+			)?;
+
+			if let Some(token) = maybe_token {
+				Self::create_community_token(community_id, token)?;
+			}
+			Ok(())
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::create_memberships((*amount).into()))]
+		/// Creates `amount` memberships, starting at `starting_at`. Up to
+		/// [`MaxMintsPerBlock`](Config::MaxMintsPerBlock) memberships are
+		/// minted right away; larger batches are queued and minted across
+		/// several blocks.
+		///
+		/// The whole range must be free: none of its memberships can exist, or
+		/// be part of a batch waiting to be minted.
+		#[pallet::weight(if *amount <= T::MaxMintsPerBlock::get() {
+			<T as Config>::WeightInfo::create_memberships(*amount)
+		} else {
+			<T as Config>::WeightInfo::create_memberships(0).saturating_add(T::DbWeight::get().reads((*amount).into()))
+		})]
 		#[pallet::call_index(1)]
 		pub fn create_memberships(
 			origin: OriginFor<T>,
-			amount: u16,
+			amount: u32,
 			starting_at: T::MembershipId,
 			#[pallet::compact] price: NativeBalanceOf<T>,
 			tank_config: TankConfig<Weight, BlockNumberFor<T>>,
 			maybe_expiration: Option<BlockNumberFor<T>>,
-		) -> DispatchResult {
-			ensure!(amount <= 1024u16, Error::<T>::CreatingTooManyMemberships);
+		) -> DispatchResultWithPostInfo {
 			T::CreateMembershipsOrigin::ensure_origin(origin.clone())?;
 
-			Self::try_create_memberships(starting_at.clone(), amount, price, tank_config, maybe_expiration)
+			let mut request =
+				Self::new_mint_request(starting_at.clone(), amount, price, tank_config, maybe_expiration)?;
+			if amount <= T::MaxMintsPerBlock::get() {
+				Self::mint_memberships(&mut request, amount)?;
+				return Ok(().into());
+			}
+
+			MintQueue::<T>::try_append(request).map_err(|_| Error::<T>::MintQueueFull)?;
+			Self::deposit_event(Event::<T>::MembershipsQueued { starting_at, amount });
+
+			Ok(().into())
 		}
 
 		#[pallet::call_index(2)]
@@ -262,6 +874,575 @@ pub mod pallet {
 			T::CreateMembershipsOrigin::ensure_origin(origin)?;
 			Self::do_set_gas_tank(&(community_id, membership_id), &config)
 		}
+
+		/// Deregisters a community: removes its governance track, removes it
+		/// from the collective, and destroys its memberships collection (or
+		/// locks the transfer of the remaining memberships, if any), and returns
+		/// the registration deposit to whoever paid it.
+		///
+		/// The community is still known to `pallet_communities`, so its id can't
+		/// be registered again.
+		///
+		/// `memberships` is a witness of the amount of memberships in the
+		/// community's collection, `invitations` of the amount of invitation
+		/// signers and claimed invitations it keeps, and `role_assignments` of
+		/// the amount of roles assigned to its memberships.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::deregister(*memberships, *invitations, *role_assignments).saturating_add(
+				<T as Config>::WeightInfo::remove_track().saturating_mul(T::MaxCommunityTracks::get().into())
+			)
+		)]
+		#[pallet::call_index(3)]
+		pub fn deregister(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			memberships: u32,
+			invitations: u32,
+			role_assignments: u32,
+		) -> DispatchResult {
+			if let Some(id) = T::DeregisterOrigin::ensure_origin(origin)? {
+				ensure!(id == community_id, DispatchError::BadOrigin);
+			}
+			Self::try_deregister(community_id, memberships, invitations, role_assignments)
+		}
+
+		/// Sets the metadata of the community. Must be called by the
+		/// community itself.
+		#[pallet::call_index(4)]
+		pub fn set_metadata(origin: OriginFor<T>, metadata: CommunityMetadata) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CreateCollection::collection_owner(&community_id).is_some(),
+				Error::<T>::UnknownCommunity
+			);
+			core::str::from_utf8(&metadata.name).map_err(|_| Error::<T>::InvalidCommunityName)?;
+			Self::do_set_metadata(community_id, metadata);
+			Ok(())
+		}
+
+		/// Updates one of the governance tracks of the community (either its
+		/// main track, whose id is the id of the community, or one of the
+		/// tracks added via [`add_track`](Pallet::add_track)), using one of the
+		/// [`TrackTemplate`]s or a custom track. Must be called by the
+		/// community itself.
+		#[pallet::call_index(5)]
+		pub fn update_track(
+			origin: OriginFor<T>,
+			track_id: CommunityIdOf<T>,
+			track: CommunityTrackOf<T>,
+		) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::community_of_track(track_id) == community_id,
+				Error::<T>::UnknownTrack
+			);
+
+			T::Tracks::update(&track_id, Self::track_info(&Self::community_name(community_id)?, track))?;
+
+			Self::deposit_event(Event::<T>::TrackUpdated {
+				id: community_id,
+				track: track_id,
+			});
+			Ok(())
+		}
+
+		/// Sets the price for renewing the memberships of the community, or
+		/// disables renewals when `None`. Must be called by the community
+		/// itself.
+		#[pallet::call_index(6)]
+		pub fn set_renewal_price(origin: OriginFor<T>, price: Option<RenewalPriceOf<T>>) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			RenewalPrices::<T>::set(community_id, price.clone());
+			Self::deposit_event(Event::<T>::RenewalPriceSet {
+				id: community_id,
+				price,
+			});
+			Ok(())
+		}
+
+		/// Extends the expiration of a membership by
+		/// [`RenewalPeriod`](Config::RenewalPeriod), charging the signer the
+		/// renewal price set by the community.
+		#[pallet::call_index(7)]
+		pub fn renew_membership(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			membership_id: T::MembershipId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let price = RenewalPrices::<T>::get(community_id).ok_or(Error::<T>::RenewalNotAvailable)?;
+			let expiration = Self::membership_expiration(&community_id, &membership_id)?;
+
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			match price {
+				RenewalPrice::Native(amount) => {
+					T::Balances::transfer(&who, &community_account, amount, Preservation::Preserve)?;
+				}
+				RenewalPrice::Asset(asset, amount) => {
+					T::Assets::transfer(asset, &who, &community_account, amount, Preservation::Preserve)?;
+				}
+			}
+
+			let expiration = expiration
+				.max(frame_system::Pallet::<T>::block_number())
+				.saturating_add(T::RenewalPeriod::get());
+			T::CreateMemberships::set_typed_attribute(
+				&community_id,
+				&membership_id,
+				&MEMBERSHIP_EXPIRATION,
+				&expiration,
+			)?;
+
+			Self::deposit_event(Event::<T>::MembershipRenewed {
+				id: community_id,
+				membership: membership_id,
+				expiration,
+			});
+			Ok(())
+		}
+
+		/// Removes an expired membership from its member, once
+		/// [`ReclaimGracePeriod`](Config::ReclaimGracePeriod) has passed, and
+		/// returns it to the
+		/// [`MembershipsManagerOwner`](Config::MembershipsManagerOwner), so it
+		/// can be sold again. The caller gets
+		/// [`ReclaimReward`](Config::ReclaimReward) from the community, if it
+		/// can afford it.
+		#[pallet::call_index(8)]
+		pub fn reclaim_expired(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			membership_id: T::MembershipId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let expiration = Self::membership_expiration(&community_id, &membership_id)?;
+			ensure!(
+				expiration.saturating_add(T::ReclaimGracePeriod::get()) < frame_system::Pallet::<T>::block_number(),
+				Error::<T>::MembershipNotExpired
+			);
+			let member =
+				T::CreateMemberships::owner(&community_id, &membership_id).ok_or(Error::<T>::MembershipNotAssigned)?;
+
+			pallet_communities::Pallet::<T>::remove_member(
+				CommunityOrigin::<T>::new(community_id).into(),
+				T::Lookup::unlookup(member),
+				membership_id.clone(),
+			)?;
+			// Removing the member leaves the membership with the community.
+			T::CreateMemberships::transfer(
+				&T::MembershipsManagerCollectionId::get(),
+				&membership_id,
+				&T::MembershipsManagerOwner::get(),
+			)?;
+			for role in CommunityRoles::<T>::iter_key_prefix(community_id) {
+				MembershipRoles::<T>::remove((community_id, role, membership_id.clone()));
+			}
+
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			let reward = T::ReclaimReward::get();
+			let reward =
+				T::Balances::transfer(&community_account, &who, reward, Preservation::Preserve).unwrap_or_default();
+
+			Self::deposit_event(Event::<T>::MembershipReclaimed {
+				id: community_id,
+				membership: membership_id,
+				reward,
+			});
+			Ok(())
+		}
+
+		/// Puts a membership on sale for the given asset and amount, or
+		/// removes it from sale when `None`.
+		#[pallet::call_index(9)]
+		pub fn set_asset_price(
+			origin: OriginFor<T>,
+			membership_id: T::MembershipId,
+			price: Option<(AssetIdOf<T>, AssetBalanceOf<T>)>,
+		) -> DispatchResult {
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_available(&membership_id), Error::<T>::MembershipNotForSale);
+
+			AssetPrices::<T>::set(&membership_id, price.clone());
+			Self::deposit_event(Event::<T>::AssetPriceSet {
+				membership: membership_id,
+				price,
+			});
+			Ok(())
+		}
+
+		/// Buys a membership on sale for assets, paying through
+		/// [`MembershipPayments`](Config::MembershipPayments), and gives it to
+		/// the chosen community.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::buy_membership().saturating_add(T::MembershipPayments::settle_weight())
+		)]
+		#[pallet::call_index(10)]
+		pub fn buy_membership(
+			origin: OriginFor<T>,
+			membership_id: T::MembershipId,
+			community_id: CommunityIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (asset, amount) = AssetPrices::<T>::take(&membership_id).ok_or(Error::<T>::MembershipNotForSale)?;
+			ensure!(Self::is_available(&membership_id), Error::<T>::MembershipNotForSale);
+			ensure!(
+				T::CreateCollection::collection_owner(&community_id).is_some(),
+				Error::<T>::UnknownCommunity
+			);
+
+			T::MembershipPayments::settle(&who, &T::MembershipsManagerOwner::get(), asset, amount)?;
+			T::CreateMemberships::transfer(
+				&T::MembershipsManagerCollectionId::get(),
+				&membership_id,
+				&pallet_communities::Pallet::<T>::community_account(&community_id),
+			)?;
+
+			Self::deposit_event(Event::<T>::MembershipBought {
+				membership: membership_id,
+				community: community_id,
+				buyer: who,
+			});
+			Ok(())
+		}
+
+		/// Sets the minimum metrics a community must have to hold each rank
+		/// in the collective, starting from rank `1`. Ranks without
+		/// requirements can only be changed by the collective origins.
+		#[pallet::call_index(11)]
+		pub fn set_rank_requirements(origin: OriginFor<T>, requirements: RankRequirementsOf<T>) -> DispatchResult {
+			T::RankRequirementsOrigin::ensure_origin(origin)?;
+			RankRequirements::<T>::put(&requirements);
+			Self::deposit_event(Event::<T>::RankRequirementsSet { requirements });
+			Ok(())
+		}
+
+		/// Promotes a community to the next rank in the collective, as long
+		/// as it meets the requirements for that rank. Can be called by
+		/// anyone.
+		#[pallet::call_index(12)]
+		pub fn claim_promotion(origin: OriginFor<T>, community_id: CommunityIdOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			let rank = Self::collective_rank(&community_account)?;
+
+			let requirement = RankRequirements::<T>::get()
+				.into_iter()
+				.nth(rank as usize)
+				.ok_or(Error::<T>::RankRequirementsNotMet)?;
+			ensure!(
+				Self::community_metrics(&community_id).meets(&requirement),
+				Error::<T>::RankRequirementsNotMet
+			);
+
+			T::RankedCollective::promote(&community_account)?;
+			Self::deposit_event(Event::<T>::CommunityPromoted {
+				id: community_id,
+				rank: rank.saturating_add(1),
+			});
+			Ok(())
+		}
+
+		/// Demotes a community to the previous rank in the collective, when
+		/// it no longer meets the requirements for its current rank. Can be
+		/// called by anyone.
+		#[pallet::call_index(13)]
+		pub fn enforce_demotion(origin: OriginFor<T>, community_id: CommunityIdOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			let rank = Self::collective_rank(&community_account)?;
+
+			// Rank `0` has no requirements, so communities are never removed
+			// from the collective this way.
+			let requirement = rank
+				.checked_sub(1)
+				.and_then(|i| RankRequirements::<T>::get().into_iter().nth(i as usize))
+				.ok_or(Error::<T>::RankRequirementsMet)?;
+			ensure!(
+				!Self::community_metrics(&community_id).meets(&requirement),
+				Error::<T>::RankRequirementsMet
+			);
+
+			T::RankedCollective::demote(&community_account)?;
+			Self::deposit_event(Event::<T>::CommunityDemoted {
+				id: community_id,
+				rank: rank.saturating_sub(1),
+			});
+			Ok(())
+		}
+
+		/// Transfers the admin of a community to `new_admin`. Can be called
+		/// either by the community itself, or by its current admin. When
+		/// `require_acceptance` is set, the transfer is completed once
+		/// `new_admin` calls [`accept_admin`](Pallet::accept_admin).
+		#[pallet::call_index(14)]
+		pub fn transfer_admin(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			new_admin: pallet_communities::AccountIdLookupOf<T>,
+			require_acceptance: bool,
+		) -> DispatchResult {
+			Self::ensure_admin(origin, &community_id)?;
+			let new_admin = T::Lookup::lookup(new_admin)?;
+
+			if require_acceptance {
+				PendingAdmin::<T>::insert(community_id, new_admin.clone());
+				Self::deposit_event(Event::<T>::AdminTransferRequested {
+					id: community_id,
+					new_admin,
+				});
+				Ok(())
+			} else {
+				Self::do_transfer_admin(community_id, new_admin)
+			}
+		}
+
+		/// Accepts a pending transfer of the admin of a community.
+		#[pallet::call_index(15)]
+		pub fn accept_admin(origin: OriginFor<T>, community_id: CommunityIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				PendingAdmin::<T>::get(community_id).is_some_and(|new_admin| new_admin == who),
+				Error::<T>::NoPendingAdminTransfer
+			);
+			Self::do_transfer_admin(community_id, who)
+		}
+
+		/// Allows (or disallows) `signer` to sign membership invitations on
+		/// behalf of the community. Must be called by the community itself.
+		#[pallet::call_index(16)]
+		pub fn set_invitation_signer(
+			origin: OriginFor<T>,
+			signer: pallet_communities::AccountIdLookupOf<T>,
+			enabled: bool,
+		) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			let signer = T::Lookup::lookup(signer)?;
+
+			if enabled {
+				InvitationSigners::<T>::insert(community_id, &signer, ());
+			} else {
+				InvitationSigners::<T>::remove(community_id, &signer);
+			}
+			Self::deposit_event(Event::<T>::InvitationSignerSet {
+				id: community_id,
+				signer,
+				enabled,
+			});
+			Ok(())
+		}
+
+		/// Claims an invitation signed by `signer`, assigning the caller one
+		/// of the memberships held by the community. The caller must be
+		/// signed with the key of the invitation (see
+		/// [`claim_payload`](Pallet::claim_payload)), so the invitation can't
+		/// be taken by someone else when submitted.
+		#[pallet::call_index(17)]
+		pub fn claim_invitation(
+			origin: OriginFor<T>,
+			invitation: InvitationOf<T>,
+			signer: AccountIdOf<T>,
+			signature: T::OffchainSignature,
+			claim_signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let Invitation {
+				community_id,
+				nonce,
+				expires_at,
+				ref key,
+			} = invitation;
+
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= expires_at,
+				Error::<T>::InvitationExpired
+			);
+			ensure!(
+				!ClaimedInvitations::<T>::contains_key(community_id, nonce),
+				Error::<T>::InvitationAlreadyClaimed
+			);
+			ensure!(
+				signer == pallet_communities::Pallet::<T>::community_account(&community_id)
+					|| InvitationSigners::<T>::contains_key(community_id, &signer),
+				Error::<T>::InvalidInvitationSignature
+			);
+			ensure!(
+				signature.verify(&*Self::invitation_payload(&invitation), &signer),
+				Error::<T>::InvalidInvitationSignature
+			);
+			ensure!(
+				claim_signature.verify(&*Self::claim_payload(&invitation, &who), key),
+				Error::<T>::InvalidClaimSignature
+			);
+
+			ClaimedInvitations::<T>::insert(community_id, nonce, expires_at);
+			pallet_communities::Pallet::<T>::add_member(
+				CommunityOrigin::<T>::new(community_id).into(),
+				T::Lookup::unlookup(who.clone()),
+			)?;
+
+			Self::deposit_event(Event::<T>::InvitationClaimed {
+				id: community_id,
+				nonce,
+				who,
+			});
+			Ok(())
+		}
+
+		/// Defines (or renames) a role within the community. Must be called by
+		/// the community itself, which can define up to
+		/// [`MaxRoles`](Config::MaxRoles) roles.
+		#[pallet::call_index(18)]
+		pub fn define_role(origin: OriginFor<T>, role: RoleId, name: RoleName) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				CommunityRoles::<T>::contains_key(community_id, role)
+					|| (CommunityRoles::<T>::iter_key_prefix(community_id).count() as u32) < T::MaxRoles::get(),
+				Error::<T>::TooManyRoles
+			);
+			CommunityRoles::<T>::insert(community_id, role, name.clone());
+			Self::deposit_event(Event::<T>::RoleDefined {
+				id: community_id,
+				role,
+				name,
+			});
+			Ok(())
+		}
+
+		/// Removes a role from the community, revoking it from the `holders`
+		/// memberships holding it. Must be called by the community itself.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_role(*holders))]
+		#[pallet::call_index(19)]
+		pub fn remove_role(origin: OriginFor<T>, role: RoleId, holders: u32) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				CommunityRoles::<T>::contains_key(community_id, role),
+				Error::<T>::UnknownRole
+			);
+			ensure!(
+				MembershipRoles::<T>::clear_prefix((community_id, role), holders, None)
+					.maybe_cursor
+					.is_none(),
+				Error::<T>::InvalidHoldersWitness
+			);
+			CommunityRoles::<T>::remove(community_id, role);
+			Self::deposit_event(Event::<T>::RoleRemoved { id: community_id, role });
+			Ok(())
+		}
+
+		/// Assigns a role to one of the memberships of the community. Roles
+		/// stay with the membership, so they should be revoked before the
+		/// membership is handed to another member. Must be called by the
+		/// community itself.
+		#[pallet::call_index(20)]
+		pub fn assign_role(origin: OriginFor<T>, membership_id: T::MembershipId, role: RoleId) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				CommunityRoles::<T>::contains_key(community_id, role),
+				Error::<T>::UnknownRole
+			);
+			ensure!(
+				T::CreateMemberships::owner(&community_id, &membership_id).is_some(),
+				Error::<T>::MembershipNotInCommunity
+			);
+
+			MembershipRoles::<T>::insert((community_id, role, membership_id.clone()), ());
+			Self::deposit_event(Event::<T>::RoleAssigned {
+				id: community_id,
+				membership: membership_id,
+				role,
+			});
+			Ok(())
+		}
+
+		/// Revokes a role from one of the memberships of the community. Must be
+		/// called by the community itself.
+		#[pallet::call_index(21)]
+		pub fn revoke_role(origin: OriginFor<T>, membership_id: T::MembershipId, role: RoleId) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				MembershipRoles::<T>::contains_key((community_id, role, membership_id.clone())),
+				Error::<T>::UnknownRole
+			);
+
+			MembershipRoles::<T>::remove((community_id, role, membership_id.clone()));
+			Self::deposit_event(Event::<T>::RoleRevoked {
+				id: community_id,
+				membership: membership_id,
+				role,
+			});
+			Ok(())
+		}
+
+		/// Adds a governance track to the community, besides its main track.
+		/// Referenda on this track dispatch with the `subset` sub-origin of the
+		/// community. Must be called by the community itself.
+		///
+		/// Track ids share the id space of communities: `track_id` can't be the
+		/// id of a community, and can't be registered as one while the track
+		/// exists.
+		#[pallet::call_index(22)]
+		pub fn add_track(
+			origin: OriginFor<T>,
+			track_id: CommunityIdOf<T>,
+			subset: Subset<T>,
+			track: CommunityTrackOf<T>,
+		) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+
+			AdditionalTracks::<T>::try_mutate(community_id, |tracks| tracks.try_push(track_id))
+				.map_err(|_| Error::<T>::TooManyTracks)?;
+			TrackCommunity::<T>::insert(track_id, community_id);
+
+			let mut track_origin = CommunityOrigin::<T>::new(community_id);
+			track_origin.with_subset(subset);
+			let track_origin: RuntimeOriginFor<T> = track_origin.into();
+			T::Tracks::insert(
+				track_id,
+				Self::track_info(&Self::community_name(community_id)?, track),
+				track_origin.into_caller(),
+			)?;
+
+			Self::deposit_event(Event::<T>::TrackAdded {
+				id: community_id,
+				track: track_id,
+			});
+			Ok(())
+		}
+
+		/// Removes one of the tracks added via [`add_track`](Pallet::add_track).
+		/// Must be called by the community itself.
+		#[pallet::call_index(23)]
+		pub fn remove_track(origin: OriginFor<T>, track_id: CommunityIdOf<T>) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				TrackCommunity::<T>::get(track_id) == Some(community_id),
+				Error::<T>::UnknownTrack
+			);
+
+			T::Tracks::remove(&track_id)?;
+			AdditionalTracks::<T>::mutate(community_id, |tracks| tracks.retain(|id| id != &track_id));
+			TrackCommunity::<T>::remove(track_id);
+
+			Self::deposit_event(Event::<T>::TrackRemoved {
+				id: community_id,
+				track: track_id,
+			});
+			Ok(())
+		}
+
+		/// Removes a claimed invitation once it has expired, as it can no
+		/// longer be claimed. Can be called by anyone.
+		#[pallet::call_index(24)]
+		pub fn prune_invitation(origin: OriginFor<T>, community_id: CommunityIdOf<T>, nonce: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+			let expires_at = ClaimedInvitations::<T>::get(community_id, nonce).ok_or(Error::<T>::UnknownInvitation)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > expires_at,
+				Error::<T>::InvitationNotExpired
+			);
+			ClaimedInvitations::<T>::remove(community_id, nonce);
+			Ok(())
+		}
 	}
 }
 
@@ -272,13 +1453,16 @@ impl<T: Config> Pallet<T> {
 		community_name: &str,
 		admin: &AccountIdOf<T>,
 		maybe_decision_method: Option<DecisionMethodFor<T>>,
-		maybe_track_info: Option<TrackInfoOf<T>>,
+		maybe_track: Option<CommunityTrackOf<T>>,
 		maybe_rank: Option<u16>,
 	) -> DispatchResult {
 		let admin_origin = frame_system::Origin::<T>::Signed(admin.clone());
 
 		// Register first to check if community exists
-		pallet_communities::Pallet::<T>::register(&admin_origin.clone().into(), &community_id, maybe_deposit)?;
+		pallet_communities::Pallet::<T>::register(&admin_origin.clone().into(), &community_id, maybe_deposit.clone())?;
+		if let Some(deposit) = maybe_deposit {
+			RegistrationDeposit::<T>::insert(community_id, deposit);
+		}
 
 		if let Some(decision_method) = maybe_decision_method {
 			pallet_communities::Pallet::<T>::set_decision_method(admin_origin.into(), community_id, decision_method)?;
@@ -302,9 +1486,20 @@ impl<T: Config> Pallet<T> {
 		let community_origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
 		T::Tracks::insert(
 			community_id,
-			maybe_track_info.unwrap_or_else(|| Self::default_tack(community_name)),
+			Self::track_info(community_name, maybe_track.unwrap_or_default()),
 			community_origin.into_caller(),
 		)?;
+		Self::do_set_metadata(
+			community_id,
+			CommunityMetadata {
+				name: BoundedVec::truncate_from(community_name.as_bytes().to_vec()),
+				..Default::default()
+			},
+		);
+
+		CommunityAdmin::<T>::insert(community_id, admin.clone());
+		RegisteredAt::<T>::insert(community_id, frame_system::Pallet::<T>::block_number());
+
 		// Induct community at Kreivo Governance with rank 0
 		T::RankedCollective::induct(&community_account)?;
 		if let Some(rank) = maybe_rank {
@@ -317,6 +1512,198 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	pub(crate) fn try_deregister(
+		community_id: CommunityIdOf<T>,
+		memberships: u32,
+		invitations: u32,
+		role_assignments: u32,
+	) -> DispatchResult {
+		ensure!(
+			T::CreateCollection::collection_owner(&community_id).is_some(),
+			Error::<T>::UnknownCommunity
+		);
+
+		// Remove the governance tracks of the community
+		T::Tracks::remove(&community_id)?;
+		for track_id in AdditionalTracks::<T>::take(community_id) {
+			T::Tracks::remove(&track_id)?;
+			TrackCommunity::<T>::remove(track_id);
+		}
+
+		// Remove community from Kreivo Governance. Demoting at rank 0 removes the
+		// member from the collective.
+		let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+		while T::RankedCollective::rank_of(&community_account).is_some() {
+			T::RankedCollective::demote(&community_account)?;
+		}
+
+		// Destroy the memberships collection when empty, otherwise lock the
+		// remaining memberships in place.
+		let items = T::CreateCollection::items(&community_id)
+			.take(memberships.saturating_add(1) as usize)
+			.collect::<Vec<_>>();
+		ensure!(
+			items.len() <= memberships as usize,
+			Error::<T>::InvalidMembershipsWitness
+		);
+		if items.is_empty() {
+			let witness =
+				T::CreateCollection::get_destroy_witness(&community_id).ok_or(Error::<T>::UnknownCommunity)?;
+			T::CreateCollection::destroy(community_id, witness, None)?;
+		} else {
+			for item in items.iter() {
+				if T::CreateCollection::can_transfer(&community_id, item) {
+					T::CreateCollection::disable_transfer(&community_id, item)?;
+				}
+			}
+		}
+
+		Metadata::<T>::remove(community_id);
+		RegisteredAt::<T>::remove(community_id);
+		CommunityAdmin::<T>::remove(community_id);
+		PendingAdmin::<T>::remove(community_id);
+		CommunityGasUsage::<T>::remove(community_id);
+
+		let signers = InvitationSigners::<T>::clear_prefix(community_id, invitations, None);
+		let claimed =
+			ClaimedInvitations::<T>::clear_prefix(community_id, invitations.saturating_sub(signers.unique), None);
+		ensure!(
+			signers.maybe_cursor.is_none() && claimed.maybe_cursor.is_none(),
+			Error::<T>::InvalidInvitationsWitness
+		);
+
+		let _ = CommunityRoles::<T>::clear_prefix(community_id, T::MaxRoles::get(), None);
+		ensure!(
+			MembershipRoles::<T>::clear_prefix((community_id,), role_assignments, None)
+				.maybe_cursor
+				.is_none(),
+			Error::<T>::InvalidRoleAssignmentsWitness
+		);
+
+		// Return the registration deposit
+		if let Some((amount, payer, beneficiary)) = RegistrationDeposit::<T>::take(community_id) {
+			T::Balances::transfer(&beneficiary, &payer, amount, Preservation::Preserve)?;
+		}
+
+		Self::deposit_event(Event::<T>::CommunityDeregistered { id: community_id });
+		Ok(())
+	}
+
+	pub(crate) fn create_community_token(community_id: CommunityIdOf<T>, token: CommunityTokenOf<T>) -> DispatchResult {
+		let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+		let asset = T::CommunityAssetId::convert(community_id);
+
+		T::Assets::create(asset.clone(), community_account.clone(), false, token.min_balance)?;
+		T::Assets::set(
+			asset.clone(),
+			&community_account,
+			token.name.into(),
+			token.symbol.into(),
+			token.decimals,
+		)?;
+		if !token.initial_supply.is_zero() {
+			T::Assets::mint_into(asset.clone(), &community_account, token.initial_supply)?;
+		}
+
+		Self::deposit_event(Event::<T>::CommunityTokenCreated {
+			id: community_id,
+			asset,
+		});
+		Ok(())
+	}
+
+	/// Ensures the origin is either the community itself, or its admin.
+	fn ensure_admin(origin: OriginFor<T>, community_id: &CommunityIdOf<T>) -> DispatchResult {
+		if let Ok(id) = T::AdminOrigin::ensure_origin(origin.clone()) {
+			ensure!(&id == community_id, DispatchError::BadOrigin);
+			return Ok(());
+		}
+
+		let who = ensure_signed(origin)?;
+		ensure!(
+			CommunityAdmin::<T>::get(community_id).is_some_and(|admin| admin == who),
+			DispatchError::BadOrigin
+		);
+		Ok(())
+	}
+
+	pub(crate) fn do_transfer_admin(community_id: CommunityIdOf<T>, new_admin: AccountIdOf<T>) -> DispatchResult {
+		let community_origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		let admin_origin: RuntimeOriginFor<T> = frame_system::RawOrigin::Signed(new_admin.clone()).into();
+		pallet_communities::Pallet::<T>::set_admin_origin(community_origin, Box::new(admin_origin.into_caller()))?;
+
+		CommunityAdmin::<T>::insert(community_id, new_admin.clone());
+		PendingAdmin::<T>::remove(community_id);
+		Self::deposit_event(Event::<T>::AdminTransferred {
+			id: community_id,
+			admin: new_admin,
+		});
+		Ok(())
+	}
+
+	pub(crate) fn do_set_metadata(community_id: CommunityIdOf<T>, metadata: CommunityMetadata) {
+		Metadata::<T>::insert(community_id, metadata.clone());
+		Self::deposit_event(Event::<T>::MetadataSet {
+			id: community_id,
+			metadata,
+		});
+	}
+
+	fn collective_rank(community_account: &AccountIdOf<T>) -> Result<u16, DispatchError> {
+		T::RankedCollective::rank_of(community_account)
+			.map(UniqueSaturatedInto::<u16>::unique_saturated_into)
+			.ok_or(Error::<T>::NotInCollective.into())
+	}
+
+	/// The payload the community (or one of its invitation signers) signs
+	/// to issue an invitation, bound to this chain by its genesis hash.
+	pub fn invitation_payload(invitation: &InvitationOf<T>) -> Vec<u8> {
+		(INVITATION_PREFIX, Self::genesis_hash(), invitation).encode()
+	}
+
+	/// The payload signed with the key of an invitation, so `who` can claim
+	/// it.
+	pub fn claim_payload(invitation: &InvitationOf<T>, who: &AccountIdOf<T>) -> Vec<u8> {
+		(
+			INVITATION_CLAIM_PREFIX,
+			Self::genesis_hash(),
+			&invitation.community_id,
+			invitation.nonce,
+			who,
+		)
+			.encode()
+	}
+
+	fn genesis_hash() -> T::Hash {
+		frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero())
+	}
+
+	pub(crate) fn community_metrics(community_id: &CommunityIdOf<T>) -> CommunityMetricsOf<T> {
+		let age = RegisteredAt::<T>::get(community_id)
+			.map(|registered_at| frame_system::Pallet::<T>::block_number().saturating_sub(registered_at))
+			.unwrap_or_default();
+
+		CommunityMetrics {
+			members: T::CommunityMetrics::members(community_id),
+			payments_volume: T::CommunityMetrics::payments_volume(community_id),
+			age,
+		}
+	}
+
+	/// Whether the membership is still held by the manager, so it can be sold.
+	fn is_available(membership_id: &T::MembershipId) -> bool {
+		T::CreateMemberships::owner(&T::MembershipsManagerCollectionId::get(), membership_id)
+			.is_some_and(|owner| owner == T::MembershipsManagerOwner::get())
+	}
+
+	fn membership_expiration(
+		community_id: &CommunityIdOf<T>,
+		membership_id: &T::MembershipId,
+	) -> Result<BlockNumberFor<T>, DispatchError> {
+		T::CreateMemberships::typed_system_attribute(community_id, Some(membership_id), &MEMBERSHIP_EXPIRATION)
+			.ok_or(Error::<T>::MembershipDoesNotExpire.into())
+	}
+
 	pub(crate) fn prepare_memberships_collection() -> Result<CommunityIdOf<T>, DispatchError> {
 		let collection_id = &T::MembershipsManagerCollectionId::get();
 
@@ -343,52 +1730,175 @@ impl<T: Config> Pallet<T> {
 		Ok(*collection_id)
 	}
 
-	pub(crate) fn try_create_memberships(
+	/// Returns how many memberships of the queued batch starting at
+	/// `starting_at` have been minted so far, if the batch is still queued.
+	pub fn minted_memberships(starting_at: T::MembershipId) -> Option<u32> {
+		MintQueue::<T>::get()
+			.into_iter()
+			.find(|request| request.starting_at == starting_at)
+			.map(|request| request.minted)
+	}
+
+	/// Checks none of the memberships in the range to create exist, and that
+	/// the range doesn't overlap with the batches waiting to be minted, which
+	/// keep their ranges reserved.
+	pub(crate) fn new_mint_request(
 		starting_at: T::MembershipId,
-		amount: u16,
+		amount: u32,
 		price: NativeBalanceOf<T>,
 		tank_config: TankConfig<Weight, BlockNumberFor<T>>,
 		maybe_expiration: Option<BlockNumberFor<T>>,
-	) -> DispatchResult {
+	) -> Result<MintRequestOf<T>, DispatchError> {
 		let collection_id = &Self::prepare_memberships_collection()?;
 
+		ensure!(
+			amount <= T::MaxMintsPerBlock::get().saturating_mul(T::MaxQueuedMints::get()),
+			Error::<T>::CreatingTooManyMemberships
+		);
+		let end = starting_at
+			.checked_add(&amount.into())
+			.ok_or(Error::<T>::CreatingTooManyMemberships)?;
+
+		// Queued ranges were checked when queued, so they never exceed the
+		// available ids.
+		ensure!(
+			MintQueue::<T>::get().iter().all(|request| {
+				let queued_end = request.starting_at.checked_add(&request.amount.into());
+				end <= request.starting_at || queued_end.is_none_or(|queued_end| starting_at >= queued_end)
+			}),
+			Error::<T>::MembershipsOverlap
+		);
+
 		let mut id = starting_at.clone();
-		let mut minted = 0u32;
 		for _ in 0..amount {
-			T::CreateMemberships::mint_into(
-				collection_id,
-				&id,
-				&T::MembershipsManagerOwner::get(),
-				&Default::default(),
-				true,
-			)?;
+			ensure!(
+				T::CreateMemberships::owner(collection_id, &id).is_none(),
+				Error::<T>::MembershipsOverlap
+			);
+			id = id
+				.checked_add(&1u32.into())
+				.ok_or(Error::<T>::CreatingTooManyMemberships)?;
+		}
 
-			Self::do_set_gas_tank(&(*collection_id, id.clone()), &tank_config)?;
+		Ok(MintRequest {
+			starting_at: starting_at.clone(),
+			next: starting_at,
+			amount,
+			minted: 0,
+			price,
+			tank_config,
+			maybe_expiration,
+		})
+	}
 
-			if let Some(expiration) = maybe_expiration {
-				T::CreateMemberships::set_typed_attribute(collection_id, &id, &b"membership_expiration", &expiration)?;
-			}
+	/// Mints up to `max` memberships of the given batch, and reports the
+	/// progress.
+	pub(crate) fn mint_memberships(request: &mut MintRequestOf<T>, max: u32) -> DispatchResult {
+		let collection_id = &T::MembershipsManagerCollectionId::get();
+		let owner = &T::MembershipsManagerOwner::get();
 
-			T::CreateMemberships::set_price(
-				&T::MembershipsManagerCollectionId::get(),
-				&id,
-				&T::MembershipsManagerOwner::get(),
-				Some(price),
-				None,
-			)?;
-			if let Some(next_id) = id.increment() {
-				id = next_id;
-				minted += 1;
-			} else {
-				break;
+		let to_mint = max.min(request.amount.saturating_sub(request.minted));
+		for _ in 0..to_mint {
+			let id = &request.next;
+			T::CreateMemberships::mint_into(collection_id, id, owner, &Default::default(), true)?;
+
+			Self::do_set_gas_tank(&(*collection_id, id.clone()), &request.tank_config)?;
+
+			if let Some(expiration) = request.maybe_expiration {
+				T::CreateMemberships::set_typed_attribute(collection_id, id, &MEMBERSHIP_EXPIRATION, &expiration)?;
 			}
+
+			T::CreateMemberships::set_price(collection_id, id, owner, Some(request.price), None)?;
+
+			request.next = id
+				.checked_add(&1u32.into())
+				.ok_or(Error::<T>::CreatingTooManyMemberships)?;
+			request.minted.saturating_inc();
 		}
 
-		Self::deposit_event(Event::<T>::MembershipsCreated {
-			starting_at,
-			amount: minted,
-		});
+		if request.minted == request.amount {
+			Self::deposit_event(Event::<T>::MembershipsCreated {
+				starting_at: request.starting_at.clone(),
+				amount: request.minted,
+			});
+		} else {
+			Self::deposit_event(Event::<T>::MembershipsMinted {
+				starting_at: request.starting_at.clone(),
+				minted: request.minted,
+				remaining: request.amount - request.minted,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Mints as many queued memberships as fit within `limit`.
+	pub(crate) fn mint_queued(limit: Weight) -> Weight {
+		let queue_weight = T::DbWeight::get().reads_writes(1, 1);
+		let base_weight = <T as Config>::WeightInfo::create_memberships(0).saturating_add(queue_weight);
+		if !base_weight.all_lte(limit) {
+			return Weight::zero();
+		}
+
+		let mut queue = MintQueue::<T>::get();
+		let Some(request) = queue.first_mut() else {
+			return T::DbWeight::get().reads(1);
+		};
+
+		let per_item = <T as Config>::WeightInfo::create_memberships(1)
+			.saturating_sub(<T as Config>::WeightInfo::create_memberships(0));
+		let available = limit.saturating_sub(base_weight);
+		let max = (available.ref_time() / per_item.ref_time().max(1))
+			.min(available.proof_size() / per_item.proof_size().max(1))
+			.min(T::MaxMintsPerBlock::get() as u64) as u32;
+		if max == 0 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		// Mint on a copy of the request, so the progress is only kept if the whole
+		// chunk gets minted.
+		let mut progress = request.clone();
+		if let Err(error) = frame_support::storage::with_storage_layer(|| Self::mint_memberships(&mut progress, max)) {
+			Self::deposit_event(Event::<T>::MembershipsMintingAborted {
+				starting_at: request.starting_at.clone(),
+				minted: request.minted,
+				error,
+			});
+			queue.remove(0);
+		} else if progress.minted == progress.amount {
+			queue.remove(0);
+		} else {
+			*request = progress;
+		}
+		MintQueue::<T>::put(queue);
+
+		<T as Config>::WeightInfo::create_memberships(max).saturating_add(queue_weight)
+	}
+
+	/// Hands a membership held by the manager to the community, and assigns
+	/// it to `who` with the given `rank`.
+	pub(crate) fn add_genesis_member(
+		community_id: CommunityIdOf<T>,
+		who: &AccountIdOf<T>,
+		membership_id: T::MembershipId,
+		rank: u8,
+	) -> DispatchResult {
+		let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+		T::CreateMemberships::transfer(
+			&T::MembershipsManagerCollectionId::get(),
+			&membership_id,
+			&community_account,
+		)?;
 
+		let community_origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
+		pallet_communities::Pallet::<T>::add_member(community_origin.clone(), T::Lookup::unlookup(who.clone()))?;
+		for _ in 0..rank {
+			pallet_communities::Pallet::<T>::promote(
+				community_origin.clone(),
+				T::Lookup::unlookup(who.clone()),
+				membership_id.clone(),
+			)?;
+		}
 		Ok(())
 	}
 
@@ -397,32 +1907,44 @@ impl<T: Config> Pallet<T> {
 		tank_id: &(CommunityIdOf<T>, T::MembershipId),
 		config: &TankConfig<Weight, BlockNumberFor<T>>,
 	) -> DispatchResult {
-		let TankConfig { capacity, periodicity } = config;
-		T::MakeTank::make_tank(tank_id, *capacity, *periodicity)?;
+		let TankConfig {
+			capacity,
+			periodicity,
+			policy,
+		} = config;
+		let capacity = Self::tank_capacity(tank_id, *capacity, policy);
+		T::MakeTank::make_tank(tank_id, capacity, *periodicity)?;
+
+		let (_, membership_id) = tank_id;
+		MembershipGasUsage::<T>::mutate(membership_id, |maybe_usage| {
+			let usage = maybe_usage.get_or_insert_with(Default::default);
+			usage.capacity = capacity;
+			usage.used = Weight::zero();
+		});
+		MembershipGasPeriods::<T>::set(
+			membership_id,
+			periodicity.map(|periodicity| (frame_system::Pallet::<T>::block_number(), periodicity)),
+		);
 
 		Ok(())
 	}
 
-	fn default_tack(name: &str) -> TrackInfoOf<T> {
-		use sp_runtime::Perbill;
-		TrackInfo {
-			name: str_array(name),
-			max_deciding: 1,
-			decision_deposit: 0u8.into(),
-			prepare_period: 1u8.into(),
-			decision_period: u8::MAX.into(),
-			confirm_period: 1u8.into(),
-			min_enactment_period: 1u8.into(),
-			min_approval: pallet_referenda::Curve::LinearDecreasing {
-				length: Perbill::from_percent(100),
-				floor: Perbill::from_percent(50),
-				ceil: Perbill::from_percent(100),
-			},
-			min_support: pallet_referenda::Curve::LinearDecreasing {
-				length: Perbill::from_percent(100),
-				floor: Perbill::from_percent(0),
-				ceil: Perbill::from_percent(50),
-			},
+	/// The community a track belongs to. Besides the tracks added via
+	/// [`add_track`](Pallet::add_track), the id of a track is the id of its
+	/// community.
+	pub fn community_of_track(track_id: CommunityIdOf<T>) -> CommunityIdOf<T> {
+		TrackCommunity::<T>::get(track_id).unwrap_or(track_id)
+	}
+
+	fn community_name(community_id: CommunityIdOf<T>) -> Result<String, DispatchError> {
+		let name = Metadata::<T>::get(community_id).map(|m| m.name).unwrap_or_default();
+		String::from_utf8(name.into_inner()).map_err(|_| Error::<T>::InvalidCommunityName.into())
+	}
+
+	fn track_info(name: &str, track: CommunityTrackOf<T>) -> TrackInfoOf<T> {
+		match track {
+			CommunityTrack::Template(template) => template.track_info(name, T::BlocksPerHour::get()),
+			CommunityTrack::Custom(track_info) => track_info,
 		}
 	}
 }
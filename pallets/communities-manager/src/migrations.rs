@@ -0,0 +1,60 @@
+//! Storage migrations of the pallet.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+use sp_runtime::traits::MaybeSerializeDeserialize;
+
+/// Sets the block of the upgrade as the registration block of the
+/// communities registered before it was tracked, so their age starts counting
+/// from it, and takes the name of the communities without [`Metadata`] from
+/// their track.
+pub struct InitRegisteredAt<T>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InitRegisteredAt<T>
+where
+	AssetIdOf<T>: MaybeSerializeDeserialize,
+{
+	fn on_runtime_upgrade() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (mut reads, mut writes) = (0u64, 0u64);
+
+		// Every community has a memberships collection with its own id.
+		for community_id in T::CreateCollection::collections() {
+			reads.saturating_inc();
+			if community_id == T::MembershipsManagerCollectionId::get() {
+				continue;
+			}
+
+			reads.saturating_inc();
+			if !RegisteredAt::<T>::contains_key(&community_id) {
+				RegisteredAt::<T>::insert(community_id, now);
+				writes.saturating_inc();
+			}
+
+			// Tracks are renamed after the community name, so it has to be known
+			// before any of them gets updated.
+			reads.saturating_accrue(2);
+			if Metadata::<T>::contains_key(&community_id) {
+				continue;
+			}
+			if let Some(track) = T::Tracks::info(community_id) {
+				let name = track.name.iter().take_while(|b| **b != 0).copied().collect::<Vec<_>>();
+				Metadata::<T>::insert(
+					community_id,
+					CommunityMetadata {
+						name: BoundedVec::truncate_from(name),
+						..Default::default()
+					},
+				);
+				writes.saturating_inc();
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+/// Initializes [`RegisteredAt`] and [`Metadata`] for the existing communities.
+pub type MigrateV0ToV1<T> =
+	VersionedMigration<0, 1, InitRegisteredAt<T>, Pallet<T>, <T as frame_system::Config>::DbWeight>;
@@ -2,14 +2,17 @@ use super::*;
 use frame_contrib_traits::{gas_tank::NonFungibleGasTank, memberships::NonFungiblesMemberships};
 use frame_support::{
 	assert_ok, derive_impl, parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, EitherOf, EqualPrivilegeOnly, VariantCountOf},
+	traits::{
+		AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, EitherOf, EqualPrivilegeOnly, MapSuccess, VariantCountOf,
+	},
 	PalletId,
 };
 use frame_system::{EnsureNever, EnsureRoot, EnsureRootWithSuccess, EnsureSigned};
 use pallet_communities::{origin::EnsureCommunity, Tally, VoteWeight};
 use sp_io::TestExternalities;
 use sp_runtime::{
-	traits::{IdentifyAccount, IdentityLookup, Verify},
+	morph_types,
+	traits::{Convert, IdentifyAccount, IdentityLookup, Verify},
 	MultiSignature,
 };
 pub use virto_common::{CommunityId, MembershipId};
@@ -32,6 +35,7 @@ type WeightInfo = ();
 pub type AccountPublic = <MultiSignature as Verify>::Signer;
 pub type AccountId = <AccountPublic as IdentifyAccount>::AccountId;
 pub type Balance = <Test as pallet_balances::Config>::Balance;
+pub type AssetId = <Test as pallet_assets::Config>::AssetId;
 
 // Configure a mock runtime to test the pallet.
 #[frame_support::runtime]
@@ -79,6 +83,39 @@ mod runtime {
 
 parameter_types! {
 	pub const RootAccount: AccountId = AccountId::new([0xff; 32]);
+	pub static MembersCount: u32 = 0;
+	pub static PaymentsVolume: Balance = 0;
+	pub static CreatedInventories: Vec<(CommunityId, u32, AccountId)> = vec![];
+}
+
+pub struct CommunityAssetId;
+impl Convert<CommunityId, AssetId> for CommunityAssetId {
+	fn convert(id: CommunityId) -> AssetId {
+		1_000 + id as AssetId
+	}
+}
+
+pub struct MockInventories;
+impl CommunityInventories<CommunityId, AccountId> for MockInventories {
+	type InventoryId = u32;
+
+	fn create_inventory(community_id: CommunityId, id: u32, owner: &AccountId) -> DispatchResult {
+		let mut inventories = CreatedInventories::get();
+		inventories.push((community_id, id, owner.clone()));
+		CreatedInventories::set(inventories);
+		Ok(())
+	}
+}
+
+pub struct MockMetrics;
+impl MetricsProvider<CommunityId, Balance> for MockMetrics {
+	fn members(_: &CommunityId) -> u32 {
+		MembersCount::get()
+	}
+
+	fn payments_volume(_: &CommunityId) -> Balance {
+		PaymentsVolume::get()
+	}
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -167,6 +204,20 @@ parameter_types! {
 	pub const MembershipNftAttr: &'static [u8; 10] = b"membership";
 	pub const TestCommunity: CommunityId = 1;
 	pub const NoDepositOnRootRegistration: Deposit = None;
+	pub const AnyCommunity: Option<CommunityId> = None;
+	pub const RenewalPeriod: BlockNumber = 100;
+	pub const ReclaimGracePeriod: BlockNumber = 10;
+	pub const ReclaimReward: Balance = 5;
+	pub const BlocksPerHour: u32 = 600;
+	pub const MaxCommunityTracks: u32 = 2;
+	pub const MaxRoles: u32 = 4;
+	pub const MaxMembershipLookups: u32 = 2;
+	pub const MaxMintsPerBlock: u32 = 1024;
+	pub const MaxQueuedMints: u32 = 4;
+}
+
+morph_types! {
+	pub type AsSomeCommunity: Morph = |id: CommunityId| -> Option<CommunityId> { Some(id) };
 }
 
 impl pallet_nfts::Config for Test {
@@ -224,15 +275,33 @@ impl Config for Test {
 	type CreateCollection = Memberships;
 	type MakeTank = NonFungibleGasTank<Test, System, Memberships, pallet_nfts::ItemConfig>;
 	type Tracks = Tracks;
+	type BlocksPerHour = BlocksPerHour;
+	type MaxCommunityTracks = MaxCommunityTracks;
+	type MaxRoles = MaxRoles;
+	type MaxMembershipLookups = MaxMembershipLookups;
 	type RankedCollective = Collective;
+	type CommunityAssetId = CommunityAssetId;
+	type RankRequirementsOrigin = EnsureRoot<AccountId>;
+	type CommunityMetrics = MockMetrics;
 	type WeightInfo = WeightInfo;
 	type RegisterOrigin = EnsureRootWithSuccess<AccountId, NoDepositOnRootRegistration>;
+	type DeregisterOrigin =
+		EitherOf<EnsureRootWithSuccess<AccountId, AnyCommunity>, MapSuccess<EnsureCommunity<Self>, AsSomeCommunity>>;
 
 	// Types to support memberships creation
 	type CreateMembershipsOrigin = EnsureRoot<AccountId>;
 	type MembershipsManagerCollectionId = MembershipsManagerCollectionId;
 	type MembershipsManagerOwner = RootAccount;
 	type ItemConfig = pallet_nfts::ItemConfig;
+	type RenewalPeriod = RenewalPeriod;
+	type ReclaimGracePeriod = ReclaimGracePeriod;
+	type ReclaimReward = ReclaimReward;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = AccountPublic;
+	type MaxMintsPerBlock = MaxMintsPerBlock;
+	type MaxQueuedMints = MaxQueuedMints;
+	type MembershipPayments = TransferAssets<Assets>;
+	type Inventories = MockInventories;
 
 	type CreateMemberships = Memberships;
 }
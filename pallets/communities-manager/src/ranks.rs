@@ -0,0 +1,48 @@
+use super::*;
+
+/// The maximum amount of ranks that can be governed by rank requirements.
+pub const MAX_RANK_REQUIREMENTS: u32 = 16;
+
+/// The metrics of a community that are taken into account to rank it in the
+/// collective. Also used to express the minimum metrics required to hold a
+/// rank.
+#[derive(Default, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct CommunityMetrics<Balance, BlockNumber> {
+	/// The amount of members of the community.
+	pub members: u32,
+	/// The volume of payments received by the community.
+	pub payments_volume: Balance,
+	/// How long ago the community was registered.
+	pub age: BlockNumber,
+}
+
+impl<Balance: PartialOrd, BlockNumber: PartialOrd> CommunityMetrics<Balance, BlockNumber> {
+	/// Whether these metrics are at least those of `requirement`.
+	pub fn meets(&self, requirement: &Self) -> bool {
+		self.members >= requirement.members
+			&& self.payments_volume >= requirement.payments_volume
+			&& self.age >= requirement.age
+	}
+}
+
+/// Provides the metrics of a community that are not tracked by this pallet.
+///
+/// These are read by permissionless calls, so implementations must take a
+/// bounded amount of reads, as they're only weighed by the benchmarks of
+/// those calls.
+pub trait MetricsProvider<CommunityId, Balance> {
+	/// The amount of members of the community.
+	fn members(community_id: &CommunityId) -> u32;
+	/// The volume of payments received by the community.
+	fn payments_volume(community_id: &CommunityId) -> Balance;
+}
+
+impl<CommunityId, Balance: Default> MetricsProvider<CommunityId, Balance> for () {
+	fn members(_: &CommunityId) -> u32 {
+		0
+	}
+
+	fn payments_volume(_: &CommunityId) -> Balance {
+		Balance::default()
+	}
+}
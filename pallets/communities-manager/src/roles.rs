@@ -0,0 +1,51 @@
+use super::*;
+
+use core::marker::PhantomData;
+use frame_support::traits::EnsureOriginWithArg;
+
+/// The id of a role within a community.
+pub type RoleId = u8;
+
+pub type RoleName = BoundedVec<u8, ConstU32<32>>;
+
+/// Ensures the origin is signed by a member of the community that holds a
+/// membership with the role `R`.
+pub struct EnsureCommunityRole<T, R>(PhantomData<(T, R)>);
+
+impl<T: Config, R: Get<RoleId>> EnsureOriginWithArg<OriginFor<T>, CommunityIdOf<T>> for EnsureCommunityRole<T, R> {
+	type Success = AccountIdOf<T>;
+
+	fn try_origin(o: OriginFor<T>, community_id: &CommunityIdOf<T>) -> Result<Self::Success, OriginFor<T>> {
+		let Ok(who) = frame_system::ensure_signed(o.clone()) else {
+			return Err(o);
+		};
+		if Pallet::<T>::has_role(community_id, &who, R::get()) {
+			Ok(who)
+		} else {
+			Err(o)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(community_id: &CommunityIdOf<T>) -> Result<OriginFor<T>, ()> {
+		let who: AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+		let membership_id: T::MembershipId = u32::MAX.into();
+		T::CreateMemberships::mint_into(community_id, &membership_id, &who, &Default::default(), true)
+			.map_err(|_| ())?;
+		CommunityRoles::<T>::insert(community_id, R::get(), RoleName::default());
+		MembershipRoles::<T>::insert((community_id, R::get(), membership_id), ());
+		Ok(frame_system::RawOrigin::Signed(who).into())
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether `who` holds a membership of the community with the given role.
+	/// Only the first [`MaxMembershipLookups`](Config::MaxMembershipLookups)
+	/// memberships of `who` are checked.
+	pub fn has_role(community_id: &CommunityIdOf<T>, who: &AccountIdOf<T>, role: RoleId) -> bool {
+		CommunityRoles::<T>::contains_key(community_id, role)
+			&& T::CreateMemberships::owned_in_collection(community_id, who)
+				.take(T::MaxMembershipLookups::get() as usize)
+				.any(|membership_id| MembershipRoles::<T>::contains_key((community_id, role, membership_id)))
+	}
+}
@@ -0,0 +1,29 @@
+//! Runtime API definition for the communities manager pallet.
+
+use crate::{CommunityMetadata, GasUsage};
+use frame_support::weights::Weight;
+use parity_scale_codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// An API to query the identity of the communities registered via the
+	/// manager.
+	pub trait CommunitiesManagerApi<CommunityId, MembershipId>
+	where
+		CommunityId: Codec,
+		MembershipId: Codec,
+	{
+		/// Returns the metadata of a community, if any.
+		fn community_metadata(id: CommunityId) -> Option<CommunityMetadata>;
+
+		/// Returns how many memberships of the queued batch starting at
+		/// `starting_at` have been minted so far, or `None` if the batch is not
+		/// queued (e.g. it has been fully minted).
+		fn minted_memberships(starting_at: MembershipId) -> Option<u32>;
+
+		/// Returns the gas consumption of a membership, if it has a tank.
+		fn membership_gas_usage(membership_id: MembershipId) -> Option<GasUsage>;
+
+		/// Returns the gas burned by the members of a community.
+		fn community_gas_usage(id: CommunityId) -> Weight;
+	}
+}
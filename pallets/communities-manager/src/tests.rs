@@ -1,11 +1,46 @@
 use crate::mock::new_test_ext;
+use crate::GenesisConfig;
 use crate::{
 	mock::*,
 	weights::{SubstrateWeight, WeightInfo},
-	TankConfig,
+	AdditionalTracks, AssetPrices, ClaimedInvitations, CommunityAdmin, CommunityGasUsage, CommunityMetadata,
+	CommunityMetrics, CommunityRoles, CommunityToken, CommunityTrack, EnsureCommunityRole, Error, Event, GasUsage,
+	GenesisTrack, Invitation, InvitationSigners, MembershipGasUsage, MembershipRoles, Metadata, MintQueue,
+	PendingAdmin, RankRequirements, RegisteredAt, RegistrationDeposit, RenewalPrice, RenewalPrices, RoleId, RoleName,
+	TankConfig, TankPolicy, TrackCommunity, TrackTemplate, MEMBERSHIP_EXPIRATION, MEMBERSHIP_RANK,
+};
+use frame_support::traits::{
+	fungible::{Inspect, Mutate},
+	fungibles::{
+		metadata::Inspect as FunsMetadataInspect, roles::Inspect as FunsRolesInspect, Inspect as FunsInspect,
+		Mutate as FunsMutate,
+	},
+	nonfungibles_v2::{Inspect as NftInspect, Mutate as NftMutate},
+	BuildGenesisConfig, Hooks, RankedMembers,
 };
-use frame_support::assert_ok;
 use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{DispatchError, DispatchResult};
+
+const COMMUNITY: CommunityId = 1;
+const ADMIN: AccountId = AccountId::new([1; 32]);
+
+fn community_origin(id: CommunityId) -> RuntimeOrigin {
+	pallet_communities::Origin::<Test>::new(id).into()
+}
+
+fn register() {
+	System::set_block_number(1);
+	assert_ok!(CommunitiesManager::register(
+		RuntimeOrigin::root(),
+		COMMUNITY,
+		BoundedVec::truncate_from(b"Test Community".to_vec()),
+		ADMIN.into(),
+		None,
+		None,
+		None,
+	));
+}
 
 #[test]
 fn create_membership_works() {
@@ -14,6 +49,7 @@ fn create_membership_works() {
 		let config = TankConfig {
 			capacity: Some(Weight::MAX),
 			periodicity: Some(7 * DAYS),
+			policy: TankPolicy::Standard,
 		};
 
 		assert_ok!(CommunitiesManager::create_memberships(
@@ -27,6 +63,1894 @@ fn create_membership_works() {
 	})
 }
 
+mod create_memberships {
+	use super::*;
+
+	fn create_memberships(amount: u32, starting_at: MembershipId) -> DispatchResult {
+		CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			amount,
+			starting_at,
+			1,
+			TankConfig::default(),
+			None,
+		)
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+
+	fn is_minted(id: MembershipId) -> bool {
+		Memberships::owner(&MembershipsManagerCollectionId::get(), &id).is_some()
+	}
+
+	#[test]
+	fn mints_small_batches_right_away() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(create_memberships(10, 1));
+
+			assert!((1..=10).all(is_minted));
+			assert!(MintQueue::<Test>::get().is_empty());
+			System::assert_last_event(
+				Event::<Test>::MembershipsCreated {
+					starting_at: 1,
+					amount: 10,
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_ranges_overlap() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(create_memberships(10, 1));
+			assert_noop!(create_memberships(10, 5), Error::<Test>::MembershipsOverlap);
+			assert_noop!(create_memberships(1, 10), Error::<Test>::MembershipsOverlap);
+			assert_ok!(create_memberships(1, 11));
+		})
+	}
+
+	#[test]
+	fn fails_if_range_overlaps_existing_items() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Memberships::mint_into(
+				&MembershipsManagerCollectionId::get(),
+				&20,
+				&RootAccount::get(),
+				&Default::default(),
+				true
+			));
+			assert_noop!(create_memberships(20, 1), Error::<Test>::MembershipsOverlap);
+			// The whole range is checked, not only its bounds.
+			assert_noop!(create_memberships(20, 11), Error::<Test>::MembershipsOverlap);
+		})
+	}
+
+	#[test]
+	fn fills_gaps_between_ranges() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(create_memberships(10, 1));
+			assert_ok!(create_memberships(10, 21));
+			assert_ok!(create_memberships(10, 11));
+			assert!((1..=30).all(is_minted));
+		})
+	}
+
+	#[test]
+	fn fails_if_range_overlaps_queued_batches() {
+		new_test_ext().execute_with(|| {
+			let amount = MaxMintsPerBlock::get() + 10;
+			assert_ok!(create_memberships(amount, 100));
+
+			assert_noop!(create_memberships(10, 95), Error::<Test>::MembershipsOverlap);
+			assert_noop!(
+				create_memberships(10, 100 + amount - 1),
+				Error::<Test>::MembershipsOverlap
+			);
+			assert_ok!(create_memberships(10, 90));
+			assert_ok!(create_memberships(10, 100 + amount));
+		})
+	}
+
+	#[test]
+	fn fails_if_batch_exceeds_the_queue_capacity() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				create_memberships(MaxMintsPerBlock::get() * MaxQueuedMints::get() + 1, 1),
+				Error::<Test>::CreatingTooManyMemberships
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_range_exceeds_available_ids() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				create_memberships(2, MembershipId::MAX),
+				Error::<Test>::CreatingTooManyMemberships
+			);
+		})
+	}
+
+	#[test]
+	fn mints_large_batches_across_blocks() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let max = MaxMintsPerBlock::get();
+			let amount = 2 * max + 10;
+			assert_ok!(create_memberships(amount, 1));
+
+			System::assert_last_event(Event::<Test>::MembershipsQueued { starting_at: 1, amount }.into());
+			assert!(!is_minted(1));
+			assert_eq!(CommunitiesManager::minted_memberships(1), Some(0));
+
+			CommunitiesManager::on_idle(1, Weight::MAX);
+			assert!(is_minted(max));
+			assert!(!is_minted(max + 1));
+			assert_eq!(CommunitiesManager::minted_memberships(1), Some(max));
+			System::assert_last_event(
+				Event::<Test>::MembershipsMinted {
+					starting_at: 1,
+					minted: max,
+					remaining: max + 10,
+				}
+				.into(),
+			);
+
+			CommunitiesManager::on_idle(2, Weight::MAX);
+			CommunitiesManager::on_idle(3, Weight::MAX);
+			assert!((1..=amount).all(is_minted));
+			assert_eq!(CommunitiesManager::minted_memberships(1), None);
+			assert!(MintQueue::<Test>::get().is_empty());
+			System::assert_last_event(Event::<Test>::MembershipsCreated { starting_at: 1, amount }.into());
+		})
+	}
+
+	#[test]
+	fn on_idle_respects_the_weight_limit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(create_memberships(2 * MaxMintsPerBlock::get(), 1));
+
+			assert_eq!(CommunitiesManager::on_idle(1, Weight::zero()), Weight::zero());
+			assert_eq!(CommunitiesManager::minted_memberships(1), Some(0));
+
+			let limit = <() as WeightInfo>::create_memberships(10)
+				.saturating_add(<Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1));
+			assert!(CommunitiesManager::on_idle(1, limit).all_lte(limit));
+			assert_eq!(CommunitiesManager::minted_memberships(1), Some(10));
+		})
+	}
+}
+
+mod deregister {
+	use super::*;
+
+	#[test]
+	fn fails_if_community_does_not_exist() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				CommunitiesManager::deregister(RuntimeOrigin::root(), COMMUNITY, 0, 0, 0),
+				Error::<Test>::UnknownCommunity
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_called_by_another_community() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::deregister(community_origin(COMMUNITY + 1), COMMUNITY, 0, 0, 0),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		new_test_ext().execute_with(|| {
+			register();
+			let community_account = Communities::community_account(&COMMUNITY);
+			assert!(Collective::rank_of(&community_account).is_some());
+
+			assert_ok!(CommunitiesManager::deregister(
+				community_origin(COMMUNITY),
+				COMMUNITY,
+				0,
+				0,
+				0
+			));
+
+			System::assert_last_event(Event::<Test>::CommunityDeregistered { id: COMMUNITY }.into());
+			assert_eq!(Collective::rank_of(&community_account), None);
+			assert_eq!(Memberships::collection_owner(&COMMUNITY), None);
+		})
+	}
+
+	#[test]
+	fn locks_remaining_memberships() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(Memberships::mint_into(
+				&COMMUNITY,
+				&1,
+				&ADMIN,
+				&Default::default(),
+				true
+			));
+
+			assert_noop!(
+				CommunitiesManager::deregister(RuntimeOrigin::root(), COMMUNITY, 0, 0, 0),
+				Error::<Test>::InvalidMembershipsWitness
+			);
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				1,
+				0,
+				0
+			));
+
+			assert!(Memberships::collection_owner(&COMMUNITY).is_some());
+			assert!(!Memberships::can_transfer(&COMMUNITY, &1));
+		})
+	}
+
+	#[test]
+	fn returns_the_registration_deposit() {
+		new_test_ext().execute_with(|| {
+			const PAYER: AccountId = AccountId::new([2; 32]);
+			const TREASURY: AccountId = AccountId::new([3; 32]);
+
+			register();
+			assert_ok!(Balances::mint_into(&TREASURY, 100));
+			RegistrationDeposit::<Test>::insert(COMMUNITY, (10, PAYER, TREASURY));
+
+			assert_ok!(CommunitiesManager::deregister(
+				community_origin(COMMUNITY),
+				COMMUNITY,
+				0,
+				0,
+				0
+			));
+
+			assert_eq!(Balances::balance(&PAYER), 10);
+			assert_eq!(Balances::balance(&TREASURY), 90);
+			assert!(!RegistrationDeposit::<Test>::contains_key(COMMUNITY));
+		})
+	}
+}
+
+mod metadata {
+	use super::*;
+
+	fn metadata() -> CommunityMetadata {
+		CommunityMetadata {
+			name: BoundedVec::truncate_from(b"Virto".to_vec()),
+			description: BoundedVec::truncate_from(b"The Virto community".to_vec()),
+			logo: BoundedVec::truncate_from(b"ipfs://logo".to_vec()),
+			website: BoundedVec::truncate_from(b"https://virto.network".to_vec()),
+			matrix_room: BoundedVec::truncate_from(b"!virto:matrix.org".to_vec()),
+		}
+	}
+
+	#[test]
+	fn register_stores_the_community_name() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_eq!(
+				Metadata::<Test>::get(COMMUNITY).map(|m| m.name.into_inner()),
+				Some(b"Test Community".to_vec())
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_not_called_by_the_community() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::set_metadata(RuntimeOrigin::signed(ADMIN), metadata()),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_community_does_not_exist() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::set_metadata(community_origin(COMMUNITY + 1), metadata()),
+				Error::<Test>::UnknownCommunity
+			);
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(CommunitiesManager::set_metadata(
+				community_origin(COMMUNITY),
+				metadata()
+			));
+
+			assert_eq!(Metadata::<Test>::get(COMMUNITY), Some(metadata()));
+			System::assert_last_event(
+				Event::<Test>::MetadataSet {
+					id: COMMUNITY,
+					metadata: metadata(),
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn is_removed_on_deregister() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				0,
+				0,
+				0
+			));
+			assert_eq!(Metadata::<Test>::get(COMMUNITY), None);
+		})
+	}
+}
+
+mod tracks {
+	use super::*;
+	use pallet_communities::origin::Subset;
+	use pallet_referenda::TracksInfo;
+
+	fn track_of(id: CommunityId) -> Option<pallet_referenda::TrackInfo<Balance, BlockNumber>> {
+		pallet_referenda_tracks::Tracks::<Test>::get(id)
+	}
+
+	#[test]
+	fn register_uses_the_basic_template_by_default() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_eq!(
+				track_of(COMMUNITY),
+				Some(TrackTemplate::Basic.track_info("Test Community", BlocksPerHour::get()))
+			);
+		})
+	}
+
+	#[test]
+	fn register_with_a_template() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CommunitiesManager::register(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				BoundedVec::truncate_from(b"Test Community".to_vec()),
+				ADMIN.into(),
+				None,
+				Some(CommunityTrack::Template(TrackTemplate::Apps)),
+				None,
+			));
+			assert_eq!(
+				track_of(COMMUNITY),
+				Some(TrackTemplate::Apps.track_info("Test Community", BlocksPerHour::get()))
+			);
+		})
+	}
+
+	#[test]
+	fn update_track_fails_if_not_called_by_the_community() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::update_track(
+					RuntimeOrigin::signed(ADMIN),
+					COMMUNITY,
+					CommunityTrack::Template(TrackTemplate::Treasury)
+				),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn update_track_works() {
+		new_test_ext().execute_with(|| {
+			register();
+
+			assert_ok!(CommunitiesManager::update_track(
+				community_origin(COMMUNITY),
+				COMMUNITY,
+				CommunityTrack::Template(TrackTemplate::Treasury)
+			));
+			assert_eq!(
+				track_of(COMMUNITY),
+				Some(TrackTemplate::Treasury.track_info("Test Community", BlocksPerHour::get()))
+			);
+			System::assert_last_event(
+				Event::<Test>::TrackUpdated {
+					id: COMMUNITY,
+					track: COMMUNITY,
+				}
+				.into(),
+			);
+
+			let custom = TrackTemplate::Memberships.track_info("Custom", BlocksPerHour::get());
+			assert_ok!(CommunitiesManager::update_track(
+				community_origin(COMMUNITY),
+				COMMUNITY,
+				CommunityTrack::Custom(custom.clone())
+			));
+			assert_eq!(track_of(COMMUNITY), Some(custom));
+		})
+	}
+
+	const TRACK: CommunityId = 10;
+
+	fn add_track(id: CommunityId, member: MembershipId) -> DispatchResult {
+		CommunitiesManager::add_track(
+			community_origin(COMMUNITY),
+			id,
+			Subset::Member(member),
+			CommunityTrack::Template(TrackTemplate::Treasury),
+		)
+	}
+
+	#[test]
+	fn add_track_maps_a_sub_origin_of_the_community() {
+		new_test_ext().execute_with(|| {
+			register();
+
+			assert_ok!(add_track(TRACK, 1));
+			assert_eq!(
+				track_of(TRACK),
+				Some(TrackTemplate::Treasury.track_info("Test Community", BlocksPerHour::get()))
+			);
+			System::assert_last_event(
+				Event::<Test>::TrackAdded {
+					id: COMMUNITY,
+					track: TRACK,
+				}
+				.into(),
+			);
+
+			let mut sub_origin = pallet_communities::Origin::<Test>::new(COMMUNITY);
+			sub_origin.with_subset(Subset::Member(1));
+			let sub_origin: RuntimeOrigin = sub_origin.into();
+			assert_eq!(Tracks::track_for(&sub_origin.caller), Ok(TRACK));
+			assert_eq!(CommunitiesManager::community_of_track(TRACK), COMMUNITY);
+			assert_eq!(CommunitiesManager::community_of_track(COMMUNITY), COMMUNITY);
+
+			// The community can update the added track.
+			assert_ok!(CommunitiesManager::update_track(
+				community_origin(COMMUNITY),
+				TRACK,
+				CommunityTrack::Template(TrackTemplate::Apps)
+			));
+			assert_eq!(
+				track_of(TRACK),
+				Some(TrackTemplate::Apps.track_info("Test Community", BlocksPerHour::get()))
+			);
+		})
+	}
+
+	#[test]
+	fn add_track_fails_for_taken_ids() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert!(add_track(COMMUNITY, 1).is_err());
+
+			assert_ok!(add_track(TRACK, 1));
+			assert!(add_track(TRACK, 2).is_err());
+		})
+	}
+
+	#[test]
+	fn add_track_fails_if_too_many_tracks() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(add_track(TRACK, 1));
+			assert_ok!(add_track(TRACK + 1, 2));
+			assert_noop!(add_track(TRACK + 2, 3), Error::<Test>::TooManyTracks);
+		})
+	}
+
+	#[test]
+	fn tracks_of_other_communities_cannot_be_changed() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(add_track(TRACK, 1));
+
+			assert_noop!(
+				CommunitiesManager::update_track(
+					community_origin(COMMUNITY + 1),
+					TRACK,
+					CommunityTrack::Template(TrackTemplate::Apps)
+				),
+				Error::<Test>::UnknownTrack
+			);
+			assert_noop!(
+				CommunitiesManager::remove_track(community_origin(COMMUNITY + 1), TRACK),
+				Error::<Test>::UnknownTrack
+			);
+			// The main track can't be removed on its own.
+			assert_noop!(
+				CommunitiesManager::remove_track(community_origin(COMMUNITY), COMMUNITY),
+				Error::<Test>::UnknownTrack
+			);
+		})
+	}
+
+	#[test]
+	fn remove_track_works() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(add_track(TRACK, 1));
+
+			assert_ok!(CommunitiesManager::remove_track(community_origin(COMMUNITY), TRACK));
+			assert_eq!(track_of(TRACK), None);
+			assert!(AdditionalTracks::<Test>::get(COMMUNITY).is_empty());
+			assert_eq!(TrackCommunity::<Test>::get(TRACK), None);
+			System::assert_last_event(
+				Event::<Test>::TrackRemoved {
+					id: COMMUNITY,
+					track: TRACK,
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn deregister_removes_every_track() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(add_track(TRACK, 1));
+
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				0,
+				0,
+				0
+			));
+			assert_eq!(track_of(COMMUNITY), None);
+			assert_eq!(track_of(TRACK), None);
+			assert!(AdditionalTracks::<Test>::get(COMMUNITY).is_empty());
+			assert_eq!(TrackCommunity::<Test>::get(TRACK), None);
+		})
+	}
+}
+
+mod renewals {
+	use super::*;
+
+	const MEMBERSHIP: MembershipId = 7;
+	const BOB: AccountId = AccountId::new([2; 32]);
+	const CHARLIE: AccountId = AccountId::new([3; 32]);
+
+	fn expiration_of(id: MembershipId) -> Option<BlockNumber> {
+		Memberships::typed_system_attribute(&COMMUNITY, Some(&id), &MEMBERSHIP_EXPIRATION)
+	}
+
+	fn set_expiration(id: MembershipId, expiration: BlockNumber) {
+		assert_ok!(Memberships::set_typed_attribute(
+			&COMMUNITY,
+			&id,
+			&MEMBERSHIP_EXPIRATION,
+			&expiration
+		));
+	}
+
+	/// Registers the community, and assigns it a membership that belongs to
+	/// `BOB`, expiring at block `50`.
+	fn setup() {
+		register();
+		let community_account = Communities::community_account(&COMMUNITY);
+		assert_ok!(Balances::mint_into(&community_account, 100));
+		assert_ok!(Balances::mint_into(&BOB, 100));
+
+		assert_ok!(Memberships::mint_into(
+			&MembershipsManagerCollectionId::get(),
+			&MEMBERSHIP,
+			&community_account,
+			&Default::default(),
+			true
+		));
+		assert_ok!(Communities::add_member(community_origin(COMMUNITY), BOB));
+		set_expiration(MEMBERSHIP, 50);
+	}
+
+	#[test]
+	fn set_renewal_price_works() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::set_renewal_price(RuntimeOrigin::signed(ADMIN), None),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(CommunitiesManager::set_renewal_price(
+				community_origin(COMMUNITY),
+				Some(RenewalPrice::Native(10))
+			));
+			assert_eq!(RenewalPrices::<Test>::get(COMMUNITY), Some(RenewalPrice::Native(10)));
+
+			assert_ok!(CommunitiesManager::set_renewal_price(community_origin(COMMUNITY), None));
+			assert_eq!(RenewalPrices::<Test>::get(COMMUNITY), None);
+		})
+	}
+
+	#[test]
+	fn renew_fails_if_renewals_are_not_available() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::renew_membership(RuntimeOrigin::signed(BOB), COMMUNITY, MEMBERSHIP),
+				Error::<Test>::RenewalNotAvailable
+			);
+		})
+	}
+
+	#[test]
+	fn renew_fails_if_membership_does_not_expire() {
+		new_test_ext().execute_with(|| {
+			setup();
+			RenewalPrices::<Test>::insert(COMMUNITY, RenewalPrice::Native(10));
+			assert_noop!(
+				CommunitiesManager::renew_membership(RuntimeOrigin::signed(BOB), COMMUNITY, MEMBERSHIP + 1),
+				Error::<Test>::MembershipDoesNotExpire
+			);
+		})
+	}
+
+	#[test]
+	fn renew_extends_the_expiration() {
+		new_test_ext().execute_with(|| {
+			setup();
+			RenewalPrices::<Test>::insert(COMMUNITY, RenewalPrice::Native(10));
+			let community_account = Communities::community_account(&COMMUNITY);
+
+			// Before expiring, the renewal period is added to the current expiration.
+			System::set_block_number(20);
+			assert_ok!(CommunitiesManager::renew_membership(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY,
+				MEMBERSHIP
+			));
+			assert_eq!(expiration_of(MEMBERSHIP), Some(50 + RenewalPeriod::get()));
+			assert_eq!(Balances::balance(&BOB), 90);
+			assert_eq!(Balances::balance(&community_account), 110);
+			System::assert_last_event(
+				Event::<Test>::MembershipRenewed {
+					id: COMMUNITY,
+					membership: MEMBERSHIP,
+					expiration: 50 + RenewalPeriod::get(),
+				}
+				.into(),
+			);
+
+			// After expiring, the renewal period starts from the current block.
+			System::set_block_number(1_000);
+			assert_ok!(CommunitiesManager::renew_membership(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY,
+				MEMBERSHIP
+			));
+			assert_eq!(expiration_of(MEMBERSHIP), Some(1_000 + RenewalPeriod::get()));
+		})
+	}
+
+	#[test]
+	fn reclaim_fails_before_the_grace_period_ends() {
+		new_test_ext().execute_with(|| {
+			setup();
+			System::set_block_number(50 + ReclaimGracePeriod::get());
+			assert_noop!(
+				CommunitiesManager::reclaim_expired(RuntimeOrigin::signed(CHARLIE), COMMUNITY, MEMBERSHIP),
+				Error::<Test>::MembershipNotExpired
+			);
+		})
+	}
+
+	#[test]
+	fn reclaim_fails_if_membership_is_not_assigned() {
+		new_test_ext().execute_with(|| {
+			setup();
+			set_expiration(MEMBERSHIP + 1, 1);
+			System::set_block_number(50 + ReclaimGracePeriod::get() + 1);
+			assert_noop!(
+				CommunitiesManager::reclaim_expired(RuntimeOrigin::signed(CHARLIE), COMMUNITY, MEMBERSHIP + 1),
+				Error::<Test>::MembershipNotAssigned
+			);
+		})
+	}
+
+	#[test]
+	fn reclaim_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			System::set_block_number(50 + ReclaimGracePeriod::get() + 1);
+
+			assert_ok!(CommunitiesManager::reclaim_expired(
+				RuntimeOrigin::signed(CHARLIE),
+				COMMUNITY,
+				MEMBERSHIP
+			));
+
+			assert_ne!(Memberships::owner(&COMMUNITY, &MEMBERSHIP), Some(BOB));
+			assert_eq!(
+				Memberships::owner(&MembershipsManagerCollectionId::get(), &MEMBERSHIP),
+				Some(RootAccount::get())
+			);
+			assert_eq!(Balances::balance(&CHARLIE), ReclaimReward::get());
+			System::assert_last_event(
+				Event::<Test>::MembershipReclaimed {
+					id: COMMUNITY,
+					membership: MEMBERSHIP,
+					reward: ReclaimReward::get(),
+				}
+				.into(),
+			);
+		})
+	}
+}
+
+mod genesis {
+	use super::*;
+	use parity_scale_codec::Encode;
+
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	#[test]
+	fn builds_communities_with_members_tokens_and_tracks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Balances::mint_into(&Communities::community_account(&COMMUNITY), 100));
+
+			GenesisConfig::<Test> {
+				communities: vec![(COMMUNITY, "Test Community".into(), ADMIN, None, None)],
+				memberships: vec![(1, 10, 0, (None, None), None)],
+				tracks: vec![(COMMUNITY, GenesisTrack::Template(TrackTemplate::Treasury))],
+				tokens: vec![(
+					COMMUNITY,
+					CommunityToken {
+						name: BoundedVec::truncate_from(b"Test Community Token".to_vec()),
+						symbol: BoundedVec::truncate_from(b"TCT".to_vec()),
+						decimals: 10,
+						min_balance: 1,
+						initial_supply: 1_000,
+					},
+				)],
+				members: vec![(COMMUNITY, BOB, 1, 0)],
+				gas_tanks: vec![(COMMUNITY, 1, (None, None))],
+				inventories: vec![(COMMUNITY, 1)],
+			}
+			.build();
+
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), Some(ADMIN));
+			assert_eq!(
+				pallet_referenda_tracks::Tracks::<Test>::get(COMMUNITY),
+				Some(TrackTemplate::Treasury.track_info("Test Community", BlocksPerHour::get()))
+			);
+			assert_eq!(
+				<Assets as FunsInspect<AccountId>>::balance(
+					1_000 + COMMUNITY as AssetId,
+					&Communities::community_account(&COMMUNITY)
+				),
+				1_000
+			);
+			assert_eq!(Memberships::owner(&COMMUNITY, &1), Some(BOB));
+			assert_eq!(
+				CreatedInventories::get(),
+				vec![(COMMUNITY, 1, Communities::community_account(&COMMUNITY))]
+			);
+		})
+	}
+
+	#[test]
+	fn builds_communities_with_custom_tracks() {
+		new_test_ext().execute_with(|| {
+			let track_info = TrackTemplate::Apps.track_info("Custom Track", 1);
+
+			GenesisConfig::<Test> {
+				communities: vec![(COMMUNITY, "Test Community".into(), ADMIN, None, None)],
+				tracks: vec![(COMMUNITY, GenesisTrack::Custom(track_info.encode()))],
+				..Default::default()
+			}
+			.build();
+
+			assert_eq!(
+				pallet_referenda_tracks::Tracks::<Test>::get(COMMUNITY),
+				Some(track_info)
+			);
+		})
+	}
+}
+
+mod transfer_admin {
+	use super::*;
+
+	const BOB: AccountId = AccountId::new([2; 32]);
+	const CHARLIE: AccountId = AccountId::new([3; 32]);
+
+	#[test]
+	fn fails_if_not_called_by_admin_or_community() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), Some(ADMIN));
+
+			assert_noop!(
+				CommunitiesManager::transfer_admin(RuntimeOrigin::signed(BOB), COMMUNITY, BOB.into(), false),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				CommunitiesManager::transfer_admin(community_origin(COMMUNITY + 1), COMMUNITY, BOB.into(), false),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn admin_can_transfer_right_away() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(CommunitiesManager::transfer_admin(
+				RuntimeOrigin::signed(ADMIN),
+				COMMUNITY,
+				BOB.into(),
+				false
+			));
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), Some(BOB));
+			System::assert_last_event(
+				Event::<Test>::AdminTransferred {
+					id: COMMUNITY,
+					admin: BOB,
+				}
+				.into(),
+			);
+
+			// The former admin is no longer allowed to transfer it
+			assert_noop!(
+				CommunitiesManager::transfer_admin(RuntimeOrigin::signed(ADMIN), COMMUNITY, ADMIN.into(), false),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn community_can_transfer_with_acceptance() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(CommunitiesManager::transfer_admin(
+				community_origin(COMMUNITY),
+				COMMUNITY,
+				BOB.into(),
+				true
+			));
+			assert_eq!(PendingAdmin::<Test>::get(COMMUNITY), Some(BOB));
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), Some(ADMIN));
+			System::assert_last_event(
+				Event::<Test>::AdminTransferRequested {
+					id: COMMUNITY,
+					new_admin: BOB,
+				}
+				.into(),
+			);
+
+			assert_noop!(
+				CommunitiesManager::accept_admin(RuntimeOrigin::signed(CHARLIE), COMMUNITY),
+				Error::<Test>::NoPendingAdminTransfer
+			);
+
+			assert_ok!(CommunitiesManager::accept_admin(RuntimeOrigin::signed(BOB), COMMUNITY));
+			assert_eq!(PendingAdmin::<Test>::get(COMMUNITY), None);
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), Some(BOB));
+			System::assert_last_event(
+				Event::<Test>::AdminTransferred {
+					id: COMMUNITY,
+					admin: BOB,
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn deregistering_clears_the_admin() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_ok!(CommunitiesManager::transfer_admin(
+				RuntimeOrigin::signed(ADMIN),
+				COMMUNITY,
+				BOB.into(),
+				true
+			));
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				0,
+				0,
+				0
+			));
+			assert_eq!(CommunityAdmin::<Test>::get(COMMUNITY), None);
+			assert_eq!(PendingAdmin::<Test>::get(COMMUNITY), None);
+		})
+	}
+}
+
+mod invitations {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use sp_core::{sr25519, Pair};
+	use sp_runtime::{traits::IdentifyAccount, MultiSignature};
+
+	const MEMBERSHIP: MembershipId = 1;
+	const BOB: AccountId = AccountId::new([2; 32]);
+	const CHARLIE: AccountId = AccountId::new([3; 32]);
+
+	fn signer() -> sr25519::Pair {
+		sr25519::Pair::from_seed(&[7; 32])
+	}
+
+	fn signer_account() -> AccountId {
+		AccountPublic::from(signer().public()).into_account()
+	}
+
+	/// The one-time key handed out along with the invitations.
+	fn invitation_key() -> sr25519::Pair {
+		sr25519::Pair::from_seed(&[8; 32])
+	}
+
+	fn invitation(nonce: u32) -> Invitation<CommunityId, BlockNumber, AccountId> {
+		Invitation {
+			community_id: COMMUNITY,
+			nonce,
+			expires_at: 10,
+			key: AccountPublic::from(invitation_key().public()).into_account(),
+		}
+	}
+
+	fn sign(invitation: &Invitation<CommunityId, BlockNumber, AccountId>) -> MultiSignature {
+		signer()
+			.sign(&CommunitiesManager::invitation_payload(invitation))
+			.into()
+	}
+
+	fn sign_claim(invitation: &Invitation<CommunityId, BlockNumber, AccountId>, who: &AccountId) -> MultiSignature {
+		invitation_key()
+			.sign(&CommunitiesManager::claim_payload(invitation, who))
+			.into()
+	}
+
+	fn claim(who: AccountId, nonce: u32) -> DispatchResult {
+		CommunitiesManager::claim_invitation(
+			RuntimeOrigin::signed(who.clone()),
+			invitation(nonce),
+			signer_account(),
+			sign(&invitation(nonce)),
+			sign_claim(&invitation(nonce), &who),
+		)
+	}
+
+	/// Registers the community, gives it a membership to hand out, and
+	/// allows [`signer`] to sign its invitations.
+	fn setup() {
+		register();
+		let community_account = Communities::community_account(&COMMUNITY);
+		assert_ok!(Balances::mint_into(&community_account, 100));
+		assert_ok!(Memberships::mint_into(
+			&MembershipsManagerCollectionId::get(),
+			&MEMBERSHIP,
+			&community_account,
+			&Default::default(),
+			true
+		));
+		assert_ok!(CommunitiesManager::set_invitation_signer(
+			community_origin(COMMUNITY),
+			signer_account(),
+			true
+		));
+	}
+
+	#[test]
+	fn set_invitation_signer_works() {
+		new_test_ext().execute_with(|| {
+			register();
+			assert_noop!(
+				CommunitiesManager::set_invitation_signer(RuntimeOrigin::signed(ADMIN), signer_account(), true),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(CommunitiesManager::set_invitation_signer(
+				community_origin(COMMUNITY),
+				signer_account(),
+				true
+			));
+			assert!(InvitationSigners::<Test>::contains_key(COMMUNITY, signer_account()));
+
+			assert_ok!(CommunitiesManager::set_invitation_signer(
+				community_origin(COMMUNITY),
+				signer_account(),
+				false
+			));
+			assert!(!InvitationSigners::<Test>::contains_key(COMMUNITY, signer_account()));
+		})
+	}
+
+	#[test]
+	fn fails_if_signer_is_not_allowed() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::set_invitation_signer(
+				community_origin(COMMUNITY),
+				signer_account(),
+				false
+			));
+			assert_noop!(claim(BOB, 1), Error::<Test>::InvalidInvitationSignature);
+		})
+	}
+
+	#[test]
+	fn fails_if_signature_is_invalid() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::claim_invitation(
+					RuntimeOrigin::signed(BOB),
+					invitation(1),
+					signer_account(),
+					sign(&invitation(2)),
+					sign_claim(&invitation(1), &BOB)
+				),
+				Error::<Test>::InvalidInvitationSignature
+			);
+
+			// Signatures of the bare invitation, without the prefix and genesis
+			// hash, are not valid
+			assert_noop!(
+				CommunitiesManager::claim_invitation(
+					RuntimeOrigin::signed(BOB),
+					invitation(1),
+					signer_account(),
+					signer().sign(&invitation(1).encode()).into(),
+					sign_claim(&invitation(1), &BOB)
+				),
+				Error::<Test>::InvalidInvitationSignature
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_claimant_is_not_signed_with_the_invitation_key() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			// A claim signed for `BOB` can't be taken by `CHARLIE`
+			assert_noop!(
+				CommunitiesManager::claim_invitation(
+					RuntimeOrigin::signed(CHARLIE),
+					invitation(1),
+					signer_account(),
+					sign(&invitation(1)),
+					sign_claim(&invitation(1), &BOB)
+				),
+				Error::<Test>::InvalidClaimSignature
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_invitation_expired() {
+		new_test_ext().execute_with(|| {
+			setup();
+			System::set_block_number(11);
+			assert_noop!(claim(BOB, 1), Error::<Test>::InvitationExpired);
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(claim(BOB, 1));
+
+			assert_eq!(Memberships::owner(&COMMUNITY, &MEMBERSHIP), Some(BOB));
+			assert_eq!(ClaimedInvitations::<Test>::get(COMMUNITY, 1), Some(10));
+			System::assert_last_event(
+				Event::<Test>::InvitationClaimed {
+					id: COMMUNITY,
+					nonce: 1,
+					who: BOB,
+				}
+				.into(),
+			);
+
+			// Invitations are single-use
+			assert_noop!(claim(CHARLIE, 1), Error::<Test>::InvitationAlreadyClaimed);
+		})
+	}
+
+	#[test]
+	fn prune_invitation_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::prune_invitation(RuntimeOrigin::signed(CHARLIE), COMMUNITY, 1),
+				Error::<Test>::UnknownInvitation
+			);
+
+			assert_ok!(claim(BOB, 1));
+			assert_noop!(
+				CommunitiesManager::prune_invitation(RuntimeOrigin::signed(CHARLIE), COMMUNITY, 1),
+				Error::<Test>::InvitationNotExpired
+			);
+
+			System::set_block_number(11);
+			assert_ok!(CommunitiesManager::prune_invitation(
+				RuntimeOrigin::signed(CHARLIE),
+				COMMUNITY,
+				1
+			));
+			assert!(!ClaimedInvitations::<Test>::contains_key(COMMUNITY, 1));
+		})
+	}
+
+	#[test]
+	fn deregistering_clears_the_signers_and_claimed_invitations() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(claim(BOB, 1));
+
+			assert_noop!(
+				CommunitiesManager::deregister(RuntimeOrigin::root(), COMMUNITY, 1, 1, 0),
+				Error::<Test>::InvalidInvitationsWitness
+			);
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				1,
+				2,
+				0
+			));
+			assert!(!InvitationSigners::<Test>::contains_key(COMMUNITY, signer_account()));
+			assert!(!ClaimedInvitations::<Test>::contains_key(COMMUNITY, 1));
+		})
+	}
+}
+
+mod roles {
+	use super::*;
+	use frame_support::traits::{ConstU8, EnsureOriginWithArg};
+
+	const CASHIER: u8 = 1;
+	const MEMBERSHIP: MembershipId = 1;
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	type EnsureCashier = EnsureCommunityRole<Test, ConstU8<CASHIER>>;
+
+	fn cashier() -> RoleName {
+		BoundedVec::truncate_from(b"cashier".to_vec())
+	}
+
+	/// Registers the community, with a membership that belongs to `BOB`.
+	fn setup() {
+		register();
+		assert_ok!(Memberships::mint_into(
+			&COMMUNITY,
+			&MEMBERSHIP,
+			&BOB,
+			&Default::default(),
+			true
+		));
+	}
+
+	#[test]
+	fn define_and_remove_role_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::define_role(RuntimeOrigin::signed(ADMIN), CASHIER, cashier()),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert_eq!(CommunityRoles::<Test>::get(COMMUNITY, CASHIER), Some(cashier()));
+
+			assert_ok!(CommunitiesManager::remove_role(community_origin(COMMUNITY), CASHIER, 0));
+			assert_eq!(CommunityRoles::<Test>::get(COMMUNITY, CASHIER), None);
+			assert_noop!(
+				CommunitiesManager::remove_role(community_origin(COMMUNITY), CASHIER, 0),
+				Error::<Test>::UnknownRole
+			);
+		})
+	}
+
+	#[test]
+	fn define_role_fails_if_there_are_too_many_roles() {
+		new_test_ext().execute_with(|| {
+			setup();
+			for role in 0..MaxRoles::get() as RoleId {
+				assert_ok!(CommunitiesManager::define_role(
+					community_origin(COMMUNITY),
+					role,
+					cashier()
+				));
+			}
+
+			assert_noop!(
+				CommunitiesManager::define_role(community_origin(COMMUNITY), MaxRoles::get() as RoleId, cashier()),
+				Error::<Test>::TooManyRoles
+			);
+			// Existing roles can still be renamed
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				0,
+				BoundedVec::truncate_from(b"manager".to_vec())
+			));
+		})
+	}
+
+	#[test]
+	fn assign_role_fails_if_role_or_membership_are_unknown() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::assign_role(community_origin(COMMUNITY), MEMBERSHIP, CASHIER),
+				Error::<Test>::UnknownRole
+			);
+
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert_noop!(
+				CommunitiesManager::assign_role(community_origin(COMMUNITY), MEMBERSHIP + 1, CASHIER),
+				Error::<Test>::MembershipNotInCommunity
+			);
+		})
+	}
+
+	#[test]
+	fn ensure_community_role_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &COMMUNITY).is_err());
+
+			assert_ok!(CommunitiesManager::assign_role(
+				community_origin(COMMUNITY),
+				MEMBERSHIP,
+				CASHIER
+			));
+			assert!(MembershipRoles::<Test>::contains_key((COMMUNITY, CASHIER, MEMBERSHIP)));
+			assert_eq!(
+				EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &COMMUNITY).ok(),
+				Some(BOB)
+			);
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(ADMIN), &COMMUNITY).is_err());
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &(COMMUNITY + 1)).is_err());
+
+			// Removing the role revokes it from every membership
+			assert_noop!(
+				CommunitiesManager::remove_role(community_origin(COMMUNITY), CASHIER, 0),
+				Error::<Test>::InvalidHoldersWitness
+			);
+			assert_ok!(CommunitiesManager::remove_role(community_origin(COMMUNITY), CASHIER, 1));
+			assert!(!MembershipRoles::<Test>::contains_key((COMMUNITY, CASHIER, MEMBERSHIP)));
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &COMMUNITY).is_err());
+
+			// Defining it again doesn't bring back the revoked holders
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &COMMUNITY).is_err());
+		})
+	}
+
+	#[test]
+	fn deregistering_clears_the_roles() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert_ok!(CommunitiesManager::assign_role(
+				community_origin(COMMUNITY),
+				MEMBERSHIP,
+				CASHIER
+			));
+
+			assert_noop!(
+				CommunitiesManager::deregister(RuntimeOrigin::root(), COMMUNITY, 1, 0, 0),
+				Error::<Test>::InvalidRoleAssignmentsWitness
+			);
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				1,
+				0,
+				1
+			));
+			assert_eq!(CommunityRoles::<Test>::get(COMMUNITY, CASHIER), None);
+			assert!(!MembershipRoles::<Test>::contains_key((COMMUNITY, CASHIER, MEMBERSHIP)));
+		})
+	}
+
+	#[test]
+	fn revoke_role_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::define_role(
+				community_origin(COMMUNITY),
+				CASHIER,
+				cashier()
+			));
+			assert_ok!(CommunitiesManager::assign_role(
+				community_origin(COMMUNITY),
+				MEMBERSHIP,
+				CASHIER
+			));
+
+			assert_ok!(CommunitiesManager::revoke_role(
+				community_origin(COMMUNITY),
+				MEMBERSHIP,
+				CASHIER
+			));
+			assert!(EnsureCashier::try_origin(RuntimeOrigin::signed(BOB), &COMMUNITY).is_err());
+			assert_noop!(
+				CommunitiesManager::revoke_role(community_origin(COMMUNITY), MEMBERSHIP, CASHIER),
+				Error::<Test>::UnknownRole
+			);
+		})
+	}
+}
+
+mod gas_tanks {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	const MEMBERSHIP: MembershipId = 1;
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	/// Registers the community, with a membership that belongs to `BOB`.
+	fn setup() {
+		register();
+		assert_ok!(Memberships::mint_into(
+			&COMMUNITY,
+			&MEMBERSHIP,
+			&BOB,
+			&Default::default(),
+			true
+		));
+	}
+
+	fn set_gas_tank(capacity: u64, policy: TankPolicy) -> DispatchResult {
+		CommunitiesManager::set_gas_tank(
+			RuntimeOrigin::root(),
+			COMMUNITY,
+			MEMBERSHIP,
+			TankConfig {
+				capacity: Some(Weight::from_parts(capacity, 0)),
+				periodicity: None,
+				policy,
+			},
+		)
+	}
+
+	fn capacity() -> Option<Weight> {
+		MembershipGasUsage::<Test>::get(MEMBERSHIP).and_then(|usage| usage.capacity)
+	}
+
+	#[test]
+	fn records_gas_burned_by_members() {
+		new_test_ext().execute_with(|| {
+			setup();
+			// Nothing is recorded until the membership has a tank
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(10, 0));
+			assert_eq!(MembershipGasUsage::<Test>::get(MEMBERSHIP), None);
+			assert_eq!(CommunityGasUsage::<Test>::get(COMMUNITY), Weight::zero());
+
+			assert_ok!(set_gas_tank(100, TankPolicy::Standard));
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(10, 0));
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(20, 0));
+			assert_eq!(
+				MembershipGasUsage::<Test>::get(MEMBERSHIP),
+				Some(GasUsage {
+					capacity: Some(Weight::from_parts(100, 0)),
+					used: Weight::from_parts(30, 0),
+					total_used: Weight::from_parts(30, 0),
+				})
+			);
+			assert_eq!(CommunityGasUsage::<Test>::get(COMMUNITY), Weight::from_parts(30, 0));
+
+			// Reconfiguring the tank starts a new period
+			assert_ok!(set_gas_tank(100, TankPolicy::Standard));
+			assert_eq!(
+				MembershipGasUsage::<Test>::get(MEMBERSHIP),
+				Some(GasUsage {
+					capacity: Some(Weight::from_parts(100, 0)),
+					used: Weight::zero(),
+					total_used: Weight::from_parts(30, 0),
+				})
+			);
+		})
+	}
+
+	#[test]
+	fn rollover_adds_unused_gas() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(set_gas_tank(100, TankPolicy::Rollover));
+			assert_eq!(capacity(), Some(Weight::from_parts(100, 0)));
+
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(40, 0));
+			assert_ok!(set_gas_tank(100, TankPolicy::Rollover));
+			assert_eq!(capacity(), Some(Weight::from_parts(160, 0)));
+		})
+	}
+
+	#[test]
+	fn rollover_counts_periodic_refills() {
+		new_test_ext().execute_with(|| {
+			setup();
+			let periodic_tank = |policy| {
+				CommunitiesManager::set_gas_tank(
+					RuntimeOrigin::root(),
+					COMMUNITY,
+					MEMBERSHIP,
+					TankConfig {
+						capacity: Some(Weight::from_parts(100, 0)),
+						periodicity: Some(10),
+						policy,
+					},
+				)
+			};
+			assert_ok!(periodic_tank(TankPolicy::Standard));
+
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(40, 0));
+			// The tank was refilled twice, the gas burned before that is no longer used
+			System::set_block_number(25);
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(30, 0));
+			let usage = MembershipGasUsage::<Test>::get(MEMBERSHIP).expect("tank is configured");
+			assert_eq!(usage.used, Weight::from_parts(30, 0));
+			assert_eq!(usage.total_used, Weight::from_parts(70, 0));
+			assert_eq!(MembershipGasPeriods::<Test>::get(MEMBERSHIP), Some((21, 10)));
+
+			// A refill in between leaves the whole capacity unused
+			System::set_block_number(31);
+			assert_ok!(periodic_tank(TankPolicy::Rollover));
+			assert_eq!(capacity(), Some(Weight::from_parts(200, 0)));
+			assert_eq!(MembershipGasPeriods::<Test>::get(MEMBERSHIP), Some((31, 10)));
+		})
+	}
+
+	#[test]
+	fn rank_multiplier_scales_capacity() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(Memberships::set_typed_attribute(
+				&COMMUNITY,
+				&MEMBERSHIP,
+				&MEMBERSHIP_RANK,
+				&3u8
+			));
+
+			assert_ok!(set_gas_tank(100, TankPolicy::RankMultiplier(Perbill::from_percent(50))));
+			assert_eq!(capacity(), Some(Weight::from_parts(250, 0)));
+		})
+	}
+
+	#[test]
+	fn bonus_is_only_granted_once() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(set_gas_tank(100, TankPolicy::Bonus(Weight::from_parts(50, 0))));
+			assert_eq!(capacity(), Some(Weight::from_parts(150, 0)));
+
+			assert_ok!(set_gas_tank(100, TankPolicy::Bonus(Weight::from_parts(50, 0))));
+			assert_eq!(capacity(), Some(Weight::from_parts(100, 0)));
+		})
+	}
+
+	#[test]
+	fn deregistering_clears_the_community_usage() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(set_gas_tank(100, TankPolicy::Standard));
+			CommunitiesManager::note_gas_burned(&BOB, Weight::from_parts(10, 0));
+
+			assert_ok!(CommunitiesManager::deregister(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				1,
+				0,
+				0
+			));
+			assert_eq!(CommunityGasUsage::<Test>::get(COMMUNITY), Weight::zero());
+		})
+	}
+}
+
+mod community_token {
+	use super::*;
+
+	const ASSET: AssetId = 1_000 + COMMUNITY as AssetId;
+
+	fn token() -> CommunityToken<Balance> {
+		CommunityToken {
+			name: BoundedVec::truncate_from(b"Test Community Token".to_vec()),
+			symbol: BoundedVec::truncate_from(b"TCT".to_vec()),
+			decimals: 10,
+			min_balance: 1,
+			initial_supply: 1_000,
+		}
+	}
+
+	fn register_with_token() -> DispatchResult {
+		CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			COMMUNITY,
+			BoundedVec::truncate_from(b"Test Community".to_vec()),
+			ADMIN.into(),
+			None,
+			None,
+			Some(token()),
+		)
+	}
+
+	#[test]
+	fn fails_if_community_cannot_pay_the_metadata_deposit() {
+		new_test_ext().execute_with(|| {
+			assert!(register_with_token().is_err());
+			assert!(!<Assets as FunsInspect<AccountId>>::asset_exists(ASSET));
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		new_test_ext().execute_with(|| {
+			let community_account = Communities::community_account(&COMMUNITY);
+			assert_ok!(Balances::mint_into(&community_account, 100));
+
+			System::set_block_number(1);
+			assert_ok!(register_with_token());
+
+			assert_eq!(
+				<Assets as FunsRolesInspect<AccountId>>::owner(ASSET),
+				Some(community_account.clone())
+			);
+			assert_eq!(
+				<Assets as FunsRolesInspect<AccountId>>::admin(ASSET),
+				Some(community_account.clone())
+			);
+			assert_eq!(
+				<Assets as FunsMetadataInspect<AccountId>>::symbol(ASSET),
+				b"TCT".to_vec()
+			);
+			assert_eq!(<Assets as FunsMetadataInspect<AccountId>>::decimals(ASSET), 10);
+			assert_eq!(
+				<Assets as FunsInspect<AccountId>>::balance(ASSET, &community_account),
+				1_000
+			);
+			System::assert_has_event(
+				Event::<Test>::CommunityTokenCreated {
+					id: COMMUNITY,
+					asset: ASSET,
+				}
+				.into(),
+			);
+		})
+	}
+}
+
+mod buy_membership {
+	use super::*;
+
+	const ASSET: u32 = 1;
+	const MEMBERSHIP: MembershipId = 1;
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	/// Registers the community, creates a membership owned by the manager
+	/// and gives `BOB` enough of `ASSET` to pay for it.
+	fn setup() {
+		register();
+		assert_ok!(CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			1,
+			MEMBERSHIP,
+			1,
+			TankConfig::default(),
+			None,
+		));
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			ASSET,
+			ADMIN.into(),
+			true,
+			1
+		));
+		assert_ok!(<Assets as FunsMutate<AccountId>>::mint_into(ASSET, &BOB, 100));
+	}
+
+	#[test]
+	fn set_asset_price_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::set_asset_price(RuntimeOrigin::signed(ADMIN), MEMBERSHIP, Some((ASSET, 10))),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				CommunitiesManager::set_asset_price(RuntimeOrigin::root(), MEMBERSHIP + 1, Some((ASSET, 10))),
+				Error::<Test>::MembershipNotForSale
+			);
+
+			assert_ok!(CommunitiesManager::set_asset_price(
+				RuntimeOrigin::root(),
+				MEMBERSHIP,
+				Some((ASSET, 10))
+			));
+			assert_eq!(AssetPrices::<Test>::get(MEMBERSHIP), Some((ASSET, 10)));
+
+			assert_ok!(CommunitiesManager::set_asset_price(
+				RuntimeOrigin::root(),
+				MEMBERSHIP,
+				None
+			));
+			assert_eq!(AssetPrices::<Test>::get(MEMBERSHIP), None);
+		})
+	}
+
+	#[test]
+	fn fails_if_membership_is_not_for_sale() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::buy_membership(RuntimeOrigin::signed(BOB), MEMBERSHIP, COMMUNITY),
+				Error::<Test>::MembershipNotForSale
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_community_is_unknown() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::set_asset_price(
+				RuntimeOrigin::root(),
+				MEMBERSHIP,
+				Some((ASSET, 10))
+			));
+			assert_noop!(
+				CommunitiesManager::buy_membership(RuntimeOrigin::signed(BOB), MEMBERSHIP, COMMUNITY + 1),
+				Error::<Test>::UnknownCommunity
+			);
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_ok!(CommunitiesManager::set_asset_price(
+				RuntimeOrigin::root(),
+				MEMBERSHIP,
+				Some((ASSET, 10))
+			));
+
+			assert_ok!(CommunitiesManager::buy_membership(
+				RuntimeOrigin::signed(BOB),
+				MEMBERSHIP,
+				COMMUNITY
+			));
+
+			assert_eq!(
+				Memberships::owner(&MembershipsManagerCollectionId::get(), &MEMBERSHIP),
+				Some(Communities::community_account(&COMMUNITY))
+			);
+			assert_eq!(<Assets as FunsInspect<AccountId>>::balance(ASSET, &BOB), 90);
+			assert_eq!(
+				<Assets as FunsInspect<AccountId>>::balance(ASSET, &RootAccount::get()),
+				10
+			);
+			assert_eq!(AssetPrices::<Test>::get(MEMBERSHIP), None);
+			System::assert_last_event(
+				Event::<Test>::MembershipBought {
+					membership: MEMBERSHIP,
+					community: COMMUNITY,
+					buyer: BOB,
+				}
+				.into(),
+			);
+		})
+	}
+}
+
+mod ranks {
+	use super::*;
+
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	fn community_rank() -> Option<u16> {
+		Collective::rank_of(&Communities::community_account(&COMMUNITY))
+	}
+
+	/// Registers the community, and requires at least `3` members to hold
+	/// rank `1`, and `10` members, after `100` blocks, to hold rank `2`.
+	fn setup() {
+		register();
+		assert_ok!(CommunitiesManager::set_rank_requirements(
+			RuntimeOrigin::root(),
+			BoundedVec::truncate_from(vec![
+				CommunityMetrics {
+					members: 3,
+					..Default::default()
+				},
+				CommunityMetrics {
+					members: 10,
+					payments_volume: 0,
+					age: 100,
+				},
+			])
+		));
+	}
+
+	#[test]
+	fn set_rank_requirements_works() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				CommunitiesManager::set_rank_requirements(RuntimeOrigin::signed(ADMIN), Default::default()),
+				DispatchError::BadOrigin
+			);
+
+			setup();
+			assert_eq!(RankRequirements::<Test>::get().len(), 2);
+		})
+	}
+
+	#[test]
+	fn fails_if_community_is_not_in_collective() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_noop!(
+				CommunitiesManager::claim_promotion(RuntimeOrigin::signed(BOB), COMMUNITY + 1),
+				Error::<Test>::NotInCollective
+			);
+			assert_noop!(
+				CommunitiesManager::enforce_demotion(RuntimeOrigin::signed(BOB), COMMUNITY + 1),
+				Error::<Test>::NotInCollective
+			);
+		})
+	}
+
+	#[test]
+	fn claim_promotion_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			assert_eq!(community_rank(), Some(0));
+
+			MembersCount::set(2);
+			assert_noop!(
+				CommunitiesManager::claim_promotion(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::<Test>::RankRequirementsNotMet
+			);
+
+			MembersCount::set(10);
+			assert_ok!(CommunitiesManager::claim_promotion(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY
+			));
+			assert_eq!(community_rank(), Some(1));
+			System::assert_last_event(Event::<Test>::CommunityPromoted { id: COMMUNITY, rank: 1 }.into());
+
+			// Not old enough for rank 2
+			assert_noop!(
+				CommunitiesManager::claim_promotion(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::<Test>::RankRequirementsNotMet
+			);
+
+			System::set_block_number(101);
+			assert_ok!(CommunitiesManager::claim_promotion(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY
+			));
+			assert_eq!(community_rank(), Some(2));
+
+			// There are no requirements for rank 3
+			assert_noop!(
+				CommunitiesManager::claim_promotion(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::<Test>::RankRequirementsNotMet
+			);
+		})
+	}
+
+	#[test]
+	fn enforce_demotion_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			MembersCount::set(3);
+			assert_ok!(CommunitiesManager::claim_promotion(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY
+			));
+
+			assert_noop!(
+				CommunitiesManager::enforce_demotion(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::<Test>::RankRequirementsMet
+			);
+
+			MembersCount::set(2);
+			assert_ok!(CommunitiesManager::enforce_demotion(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY
+			));
+			assert_eq!(community_rank(), Some(0));
+			System::assert_last_event(Event::<Test>::CommunityDemoted { id: COMMUNITY, rank: 0 }.into());
+
+			// Communities are never removed from the collective
+			assert_noop!(
+				CommunitiesManager::enforce_demotion(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::<Test>::RankRequirementsMet
+			);
+		})
+	}
+
+	#[test]
+	fn migration_sets_the_registration_of_existing_communities() {
+		use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+		new_test_ext().execute_with(|| {
+			register();
+			RegisteredAt::<Test>::remove(COMMUNITY);
+
+			System::set_block_number(10);
+			crate::migrations::InitRegisteredAt::<Test>::on_runtime_upgrade();
+			assert_eq!(RegisteredAt::<Test>::get(COMMUNITY), Some(10));
+			assert_eq!(RegisteredAt::<Test>::get(MembershipsManagerCollectionId::get()), None);
+
+			// Communities that already have it are left untouched
+			System::set_block_number(20);
+			crate::migrations::InitRegisteredAt::<Test>::on_runtime_upgrade();
+			assert_eq!(RegisteredAt::<Test>::get(COMMUNITY), Some(10));
+		})
+	}
+
+	#[test]
+	fn migration_takes_the_name_of_existing_communities_from_their_track() {
+		use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+		new_test_ext().execute_with(|| {
+			register();
+			Metadata::<Test>::remove(COMMUNITY);
+
+			crate::migrations::InitRegisteredAt::<Test>::on_runtime_upgrade();
+			assert_eq!(
+				Metadata::<Test>::get(COMMUNITY).map(|m| m.name.into_inner()),
+				Some(b"Test Community".to_vec())
+			);
+			assert_eq!(Metadata::<Test>::get(MembershipsManagerCollectionId::get()), None);
+		})
+	}
+}
+
 #[test]
 fn weights() {
 	let max_total_extrinsics = MAX_BLOCK_WEIGHT * NORMAL_DISPATCH_RATIO;
@@ -49,6 +1973,41 @@ fn weights() {
 			SubstrateWeight::<Test>::create_memberships(1024),
 		),
 		("set_gas_tank", SubstrateWeight::<Test>::set_gas_tank()),
+		(
+			"deregister(1024, 1024)",
+			SubstrateWeight::<Test>::deregister(1024, 1024),
+		),
+		("set_metadata", SubstrateWeight::<Test>::set_metadata()),
+		("update_track", SubstrateWeight::<Test>::update_track()),
+		("set_renewal_price", SubstrateWeight::<Test>::set_renewal_price()),
+		("renew_membership", SubstrateWeight::<Test>::renew_membership()),
+		("reclaim_expired", SubstrateWeight::<Test>::reclaim_expired()),
+		("set_asset_price", SubstrateWeight::<Test>::set_asset_price()),
+		("buy_membership", SubstrateWeight::<Test>::buy_membership()),
+		(
+			"set_rank_requirements",
+			SubstrateWeight::<Test>::set_rank_requirements(),
+		),
+		("claim_promotion", SubstrateWeight::<Test>::claim_promotion()),
+		("enforce_demotion", SubstrateWeight::<Test>::enforce_demotion()),
+		(
+			"create_community_token",
+			SubstrateWeight::<Test>::create_community_token(),
+		),
+		("transfer_admin", SubstrateWeight::<Test>::transfer_admin()),
+		("accept_admin", SubstrateWeight::<Test>::accept_admin()),
+		(
+			"set_invitation_signer",
+			SubstrateWeight::<Test>::set_invitation_signer(),
+		),
+		("claim_invitation", SubstrateWeight::<Test>::claim_invitation()),
+		("prune_invitation", SubstrateWeight::<Test>::prune_invitation()),
+		("define_role", SubstrateWeight::<Test>::define_role()),
+		("remove_role(1024)", SubstrateWeight::<Test>::remove_role(1024)),
+		("assign_role", SubstrateWeight::<Test>::assign_role()),
+		("revoke_role", SubstrateWeight::<Test>::revoke_role()),
+		("add_track", SubstrateWeight::<Test>::add_track()),
+		("remove_track", SubstrateWeight::<Test>::remove_track()),
 	];
 
 	for (function, weight) in calls {
@@ -0,0 +1,110 @@
+use super::*;
+use sp_runtime::Perbill;
+
+/// A set of predefined track configurations, tuned for the most common kinds
+/// of decisions a community takes.
+#[derive(
+	Default,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Debug,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub enum TrackTemplate {
+	/// A general purpose track: decisions are taken quickly, requiring a
+	/// simple majority.
+	#[default]
+	Basic,
+	/// A track for treasury spends: a longer decision period, and a
+	/// qualified majority.
+	Treasury,
+	/// A track for managing memberships: allows several decisions at the same
+	/// time, with a short confirmation period.
+	Memberships,
+	/// A track for installing applications: requires a broad support of the
+	/// community.
+	Apps,
+}
+
+/// The track of a community: either one of the [`TrackTemplate`]s, or a
+/// custom [`TrackInfo`].
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum CommunityTrack<TrackInfo> {
+	Template(TrackTemplate),
+	Custom(TrackInfo),
+}
+
+/// The track of an initial community in the genesis config. Custom tracks
+/// are given as their SCALE-encoded [`TrackInfo`], as it can't be serialized.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum GenesisTrack {
+	Template(TrackTemplate),
+	Custom(Vec<u8>),
+}
+
+impl GenesisTrack {
+	/// Decodes the [`CommunityTrack`] given by this genesis track.
+	pub fn community_track<TrackInfo: Decode>(&self) -> Result<CommunityTrack<TrackInfo>, parity_scale_codec::Error> {
+		Ok(match self {
+			Self::Template(template) => CommunityTrack::Template(*template),
+			Self::Custom(encoded) => CommunityTrack::Custom(TrackInfo::decode(&mut &encoded[..])?),
+		})
+	}
+}
+
+impl<TrackInfo> Default for CommunityTrack<TrackInfo> {
+	fn default() -> Self {
+		Self::Template(TrackTemplate::default())
+	}
+}
+
+impl TrackTemplate {
+	/// Builds the [`TrackInfo`] of this template, using `name` as the name of
+	/// the track, and `hour` as the amount of blocks in an hour.
+	pub fn track_info<Balance: From<u8>, Moment: From<u32>>(
+		&self,
+		name: &str,
+		hour: u32,
+	) -> TrackInfo<Balance, Moment> {
+		let day = 24 * hour;
+		let (max_deciding, decision_period, confirm_period, min_enactment_period) = match self {
+			TrackTemplate::Basic => (1, u8::MAX as u32, 1, 1),
+			TrackTemplate::Treasury => (1, 7 * day, day, day),
+			TrackTemplate::Memberships => (5, 3 * day, hour, 1),
+			TrackTemplate::Apps => (1, 7 * day, day, hour),
+		};
+		let (approval_floor, support_floor, support_ceil) = match self {
+			TrackTemplate::Basic | TrackTemplate::Memberships => (50, 0, 50),
+			TrackTemplate::Treasury => (60, 10, 50),
+			TrackTemplate::Apps => (66, 25, 75),
+		};
+
+		TrackInfo {
+			name: str_array(name),
+			max_deciding,
+			decision_deposit: 0u8.into(),
+			prepare_period: 1u32.into(),
+			decision_period: decision_period.into(),
+			confirm_period: confirm_period.into(),
+			min_enactment_period: min_enactment_period.into(),
+			min_approval: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(approval_floor),
+				ceil: Perbill::from_percent(100),
+			},
+			min_support: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(support_floor),
+				ceil: Perbill::from_percent(support_ceil),
+			},
+		}
+	}
+}
@@ -0,0 +1,30 @@
+//! This forwards its input to the Kreivo APIs chain extension, and returns the
+//! status code of the call, followed by its output.
+#![no_std]
+#![no_main]
+
+use common::input;
+use uapi::{HostFn, HostFnImpl as api, ReturnFlags};
+
+const BUFFER_SIZE: usize = 1024;
+
+#[no_mangle]
+#[polkavm_derive::polkavm_export]
+pub extern "C" fn deploy() {}
+
+#[no_mangle]
+#[polkavm_derive::polkavm_export]
+pub extern "C" fn call() {
+	input!(buffer, BUFFER_SIZE, func_id: u32, api_input: [u8],);
+
+	let mut output = [0u8; BUFFER_SIZE];
+	let output = &mut &mut output[..];
+	let status = api::call_chain_extension(func_id, api_input, Some(output));
+
+	let mut result = [0u8; 4 + BUFFER_SIZE];
+	let len = 4 + output.len();
+	result[..4].copy_from_slice(&status.to_le_bytes());
+	result[4..len].copy_from_slice(output);
+
+	api::return_value(ReturnFlags::empty(), &result[..len]);
+}
@@ -160,5 +160,38 @@ pub mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn bind_contract() -> Result<(), BenchmarkError> {
+		let caller = <<T as Config>::InstantiateOrigin>::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("Couldn't get successful origin"))?;
+		let (_, merchant_id) = <<T as Config>::InstantiateOrigin>::ensure_origin(caller.clone())
+			.map_err(|_| BenchmarkError::Stop("Invalid origin"))?;
+		let contract: AccountIdOf<T> = T::BenchmarkHelper::bindable_contract();
+
+		#[extrinsic_call]
+		_(caller, T::Lookup::unlookup(contract.clone()));
+
+		assert_has_event::<T>(Event::<T>::ContractBound { contract, merchant_id }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unbind_contract() -> Result<(), BenchmarkError> {
+		let caller = <<T as Config>::InstantiateOrigin>::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("Couldn't get successful origin"))?;
+		let (_, merchant_id) = <<T as Config>::InstantiateOrigin>::ensure_origin(caller.clone())
+			.map_err(|_| BenchmarkError::Stop("Invalid origin"))?;
+		let contract: AccountIdOf<T> = account("contract", 0, 0);
+		ContractMerchantId::<T>::insert(&contract, merchant_id.clone());
+
+		#[extrinsic_call]
+		_(caller, T::Lookup::unlookup(contract.clone()));
+
+		assert_has_event::<T>(Event::<T>::ContractUnbound { contract, merchant_id }.into());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, sp_io::TestExternalities::default(), mock::Test);
 }
@@ -29,6 +29,11 @@
 //!   instances for an application.
 //! - [set_determinism][Pallet::set_determinism] sets the determinism for an
 //!   application.
+//! - [bind_contract][Pallet::bind_contract] binds a contract that was not
+//!   instantiated from the store (e.g. a `pallet-revive` contract) to the
+//!   merchant of the caller.
+//! - [unbind_contract][Pallet::unbind_contract] unbinds a contract from the
+//!   merchant of the caller.
 //!
 //! ## Contract Fixtures
 //!
@@ -41,7 +46,10 @@ extern crate core;
 
 use alloc::vec::Vec;
 use frame_contrib_traits::listings::{item::Item, InspectInventory, InspectItem, InventoryLifecycle, MutateItem};
-use frame_support::{pallet_prelude::*, traits::Incrementable};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Contains, Incrementable},
+};
 use frame_system::pallet_prelude::*;
 use pallet_contracts::{Code, CodeUploadReturnValue, CollectEvents, DebugInfo, Determinism, InstantiateReturnValue};
 use parity_scale_codec::HasCompact;
@@ -62,6 +70,12 @@ pub use pallet::*;
 pub use types::*;
 pub use weights::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId> {
+	/// Returns the account of a contract that can be bound to a merchant.
+	fn bindable_contract() -> AccountId;
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -102,11 +116,19 @@ pub mod pallet {
 				ItemId = Self::LicenseId,
 			> + MutateItem<Self::AccountId>;
 
+		/// The accounts of contracts that can be bound to a merchant via
+		/// [`bind_contract`][Pallet::bind_contract], like `pallet-revive`
+		/// contracts.
+		type BindableContracts: Contains<Self::AccountId>;
+
 		// Parameters: A set of constant parameters to configure limits.
 
 		/// The `MerchantId` associated to the contracts store.
 		#[pallet::constant]
 		type ContractsStoreMerchantId: Get<ListingsMerchantIdOf<Self>>;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
 	}
 
 	#[pallet::pallet]
@@ -138,6 +160,13 @@ pub mod pallet {
 		ContractReverted,
 		/// The application instance is up to date.
 		AppInstanceUpToDate,
+		/// The given account is not a contract that can be bound to a
+		/// merchant.
+		NotAContract,
+		/// The contract is already bound to a merchant.
+		ContractAlreadyBound,
+		/// The contract is not bound to the merchant of the caller.
+		ContractNotBound,
 	}
 
 	#[pallet::event]
@@ -168,6 +197,16 @@ pub mod pallet {
 			license_id: T::LicenseId,
 			caller: T::AccountId,
 		},
+		/// A contract has been bound to a merchant.
+		ContractBound {
+			contract: T::AccountId,
+			merchant_id: ListingsMerchantIdOf<T>,
+		},
+		/// A contract has been unbound from its merchant.
+		ContractUnbound {
+			contract: T::AccountId,
+			merchant_id: ListingsMerchantIdOf<T>,
+		},
 	}
 
 	/// The next `AppId` to be used when publishing a new app.
@@ -384,6 +423,52 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Binds a contract that was not instantiated from the store to the
+		/// merchant of the caller, so the contract acts on behalf of the
+		/// merchant when calling the Kreivo APIs.
+		///
+		/// The caller must be a valid
+		/// [`InstantiateOrigin`][T::InstantiateOrigin], and the contract
+		/// must be one of the [`BindableContracts`][T::BindableContracts],
+		/// not bound to any merchant yet.
+		#[pallet::call_index(6)]
+		pub fn bind_contract(origin: OriginFor<T>, contract: AccountIdLookupOf<T>) -> DispatchResult {
+			let (_, merchant_id) = <<T as Config>::InstantiateOrigin>::ensure_origin(origin)?;
+			let contract = T::Lookup::lookup(contract)?;
+
+			ensure!(T::BindableContracts::contains(&contract), Error::<T>::NotAContract);
+			ensure!(
+				!ContractMerchantId::<T>::contains_key(&contract),
+				Error::<T>::ContractAlreadyBound
+			);
+
+			ContractMerchantId::<T>::insert(&contract, merchant_id.clone());
+			Self::deposit_event(Event::<T>::ContractBound { contract, merchant_id });
+
+			Ok(())
+		}
+
+		/// Unbinds a contract from the merchant of the caller.
+		///
+		/// The caller must be a valid
+		/// [`InstantiateOrigin`][T::InstantiateOrigin], whose merchant the
+		/// contract is bound to.
+		#[pallet::call_index(7)]
+		pub fn unbind_contract(origin: OriginFor<T>, contract: AccountIdLookupOf<T>) -> DispatchResult {
+			let (_, merchant_id) = <<T as Config>::InstantiateOrigin>::ensure_origin(origin)?;
+			let contract = T::Lookup::lookup(contract)?;
+
+			ensure!(
+				ContractMerchantId::<T>::get(&contract) == Some(merchant_id.clone()),
+				Error::<T>::ContractNotBound
+			);
+
+			ContractMerchantId::<T>::remove(&contract);
+			Self::deposit_event(Event::<T>::ContractUnbound { contract, merchant_id });
+
+			Ok(())
+		}
 	}
 }
 
@@ -4,7 +4,11 @@ use crate as pallet_contracts_store;
 
 use frame_contrib_traits::listings::test_utils::{self, MockListings};
 use frame_support::traits::Time;
-use frame_support::{derive_impl, pallet_prelude::ConstU32, traits::EnsureOrigin};
+use frame_support::{
+	derive_impl,
+	pallet_prelude::ConstU32,
+	traits::{Contains, EnsureOrigin},
+};
 use frame_system::pallet_prelude::BlockNumberFor;
 use frame_system::EnsureSigned;
 use pallet_contracts::{AddressGenerator, Frame, Schedule};
@@ -136,12 +140,53 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureSignedMerchant {
 	}
 }
 
+/// Contracts instantiated via `pallet-contracts` stand in for the contracts
+/// that can be bound to a merchant.
+pub struct InstantiatedContracts;
+
+impl Contains<AccountId> for InstantiatedContracts {
+	fn contains(who: &AccountId) -> bool {
+		Contracts::code_hash(who).is_some()
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ContractsStoreBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_contracts_store::BenchmarkHelper<AccountId> for ContractsStoreBenchmarkHelper {
+	fn bindable_contract() -> AccountId {
+		use frame_support::{traits::fungible::Mutate, weights::Weight};
+		use pallet_contracts::{Code, CollectEvents, DebugInfo};
+
+		let (code, _) = pallet_contracts_fixtures::compile_module::<Test>("dummy").expect("fixture exists; qed");
+		Balances::set_balance(&ALICE, Balance::MAX / 2);
+		Contracts::bare_instantiate(
+			ALICE,
+			0,
+			Weight::MAX,
+			None,
+			Code::Upload(code),
+			vec![],
+			vec![],
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+		)
+		.result
+		.expect("the fixture can be instantiated; qed")
+		.account_id
+	}
+}
+
 impl pallet_contracts_store::Config for Test {
 	type WeightInfo = ();
 	type InstantiateOrigin = EnsureSignedMerchant;
 	type AppId = AppId;
 	type LicenseId = LicenseId;
 	type Listings = Listings;
+	type BindableContracts = InstantiatedContracts;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ContractsStoreBenchmarkHelper;
 	type ContractsStoreMerchantId = ConstU32<0>;
 }
 
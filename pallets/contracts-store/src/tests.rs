@@ -384,23 +384,27 @@ mod instantiate {
 	}
 }
 
+fn test_ext_post_instantiate() -> TestExternalities {
+	let mut t = test_ext_post_license();
+	t.execute_with(|| {
+		Balances::set_balance(&BOB, Balance::MAX / 2);
+		assert_ok!(ContractStore::instantiate(
+			RuntimeOrigin::signed(BOB),
+			APP_ID,
+			LICENSE_ID,
+			0,
+			vec![],
+			vec![]
+		));
+	});
+	t
+}
+
 mod upgrade {
 	use super::*;
 
 	fn new_test_ext() -> TestExternalities {
-		let mut t = test_ext_post_license();
-		t.execute_with(|| {
-			Balances::set_balance(&BOB, Balance::MAX / 2);
-			assert_ok!(ContractStore::instantiate(
-				RuntimeOrigin::signed(BOB),
-				APP_ID,
-				LICENSE_ID,
-				0,
-				vec![],
-				vec![]
-			));
-		});
-		t
+		test_ext_post_instantiate()
 	}
 
 	#[test]
@@ -441,3 +445,80 @@ mod upgrade {
 		})
 	}
 }
+
+mod bind_contract {
+	use super::*;
+	use crate::ContractMerchantId;
+	use pallet_contracts::AddressGenerator;
+
+	fn instance() -> AccountId {
+		SimpleAddressGenerator::contract_address(&BOB, &code_hash("call"), &[], &[])
+	}
+
+	#[test]
+	fn fails_if_bad_origin() {
+		test_ext_post_instantiate().execute_with(|| {
+			assert_noop!(
+				ContractStore::bind_contract(RuntimeOrigin::root(), instance()),
+				DispatchError::BadOrigin
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_not_a_contract() {
+		test_ext_post_instantiate().execute_with(|| {
+			assert_noop!(
+				ContractStore::bind_contract(RuntimeOrigin::signed(ALICE), BOB),
+				Error::<Test>::NotAContract
+			);
+		})
+	}
+
+	#[test]
+	fn fails_if_already_bound() {
+		test_ext_post_instantiate().execute_with(|| {
+			assert_noop!(
+				ContractStore::bind_contract(RuntimeOrigin::signed(ALICE), instance()),
+				Error::<Test>::ContractAlreadyBound
+			);
+		})
+	}
+
+	#[test]
+	fn unbinding_fails_if_not_bound_to_the_caller() {
+		test_ext_post_instantiate().execute_with(|| {
+			assert_noop!(
+				ContractStore::unbind_contract(RuntimeOrigin::signed(ALICE), instance()),
+				Error::<Test>::ContractNotBound
+			);
+		})
+	}
+
+	#[test]
+	fn it_works() {
+		test_ext_post_instantiate().execute_with(|| {
+			let contract = instance();
+
+			assert_ok!(ContractStore::unbind_contract(RuntimeOrigin::signed(BOB), contract));
+			System::assert_has_event(
+				Event::<Test>::ContractUnbound {
+					contract,
+					merchant_id: 2,
+				}
+				.into(),
+			);
+			assert!(!ContractMerchantId::<Test>::contains_key(contract));
+
+			assert_ok!(ContractStore::bind_contract(RuntimeOrigin::signed(ALICE), contract));
+			System::assert_has_event(
+				Event::<Test>::ContractBound {
+					contract,
+					merchant_id: 1,
+				}
+				.into(),
+			);
+			assert_eq!(ContractStore::maybe_merchant_id(&contract), Some(1));
+		})
+	}
+}
@@ -3,9 +3,11 @@ use super::*;
 use frame_contrib_traits::listings::item::ItemPrice;
 use frame_support::traits::fungible::Inspect;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use sp_runtime::traits::StaticLookup;
 
 pub type CodeHash<T> = <T as frame_system::Config>::Hash;
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+pub type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 pub type AppInfoFor<T> = AppInfo<CodeHash<T>, AccountIdOf<T>, ItemPriceOf<T>>;
 pub type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Inspect<AccountIdOf<T>>>::Balance;
 pub type Contracts<T> = pallet_contracts::Pallet<T>;
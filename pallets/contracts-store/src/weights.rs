@@ -10,6 +10,8 @@ pub trait WeightInfo {
 	fn set_parameters() -> Weight;
 	fn publish_upgrade() -> Weight;
 	fn request_license() -> Weight;
+	fn bind_contract() -> Weight;
+	fn unbind_contract() -> Weight;
 }
 
 /// Weights for pallet_contracts_store using the Substrate node and recommended hardware.
@@ -39,6 +41,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8))
 			.saturating_add(T::DbWeight::get().writes(14))
 	}
+	fn bind_contract() -> Weight {
+		Weight::from_parts(17_572_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn unbind_contract() -> Weight {
+		Weight::from_parts(14_800_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 impl WeightInfo for () {
@@ -66,4 +80,16 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8))
 			.saturating_add(RocksDbWeight::get().writes(14))
 	}
+	fn bind_contract() -> Weight {
+		Weight::from_parts(17_572_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn unbind_contract() -> Weight {
+		Weight::from_parts(14_800_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
\ No newline at end of file
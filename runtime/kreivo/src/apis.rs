@@ -177,6 +177,24 @@ pallet_revive::impl_runtime_apis_plus_revive! {
 		}
 	}
 
+	impl pallet_communities_manager::runtime_api::CommunitiesManagerApi<Block, CommunityId, virto_common::MembershipId> for Runtime {
+		fn community_metadata(id: CommunityId) -> Option<pallet_communities_manager::CommunityMetadata> {
+			pallet_communities_manager::Metadata::<Runtime>::get(id)
+		}
+
+		fn minted_memberships(starting_at: virto_common::MembershipId) -> Option<u32> {
+			CommunitiesManager::minted_memberships(starting_at)
+		}
+
+		fn membership_gas_usage(membership_id: virto_common::MembershipId) -> Option<pallet_communities_manager::GasUsage> {
+			pallet_communities_manager::MembershipGasUsage::<Runtime>::get(membership_id)
+		}
+
+		fn community_gas_usage(id: CommunityId) -> Weight {
+			pallet_communities_manager::CommunityGasUsage::<Runtime>::get(id)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -48,6 +48,9 @@ frame_benchmarking::define_benchmarks!(
 
 	// Merchants
 	[pallet_payments, Payments]
+	[pallet_payment_fees, PaymentFees]
+	[pallet_payment_disputes, PaymentDisputes]
+	[pallet_recurring_payments, RecurringPayments]
 	[pallet_listings, Listings]
 	[pallet_nfts, ListingsCatalog]
 	[pallet_orders, Orders]
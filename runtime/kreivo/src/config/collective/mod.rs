@@ -20,9 +20,9 @@ pub type KreivoCollectiveInstance = pallet_ranked_collective::Instance1;
 impl pallet_ranked_collective::Config<KreivoCollectiveInstance> for Runtime {
 	type WeightInfo = weights::pallet_ranked_collective::WeightInfo<Self>;
 	type RuntimeEvent = RuntimeEvent;
-	// Initially, members of kreivo collective are managed via governance action
-	// In the future, it's expected to have an auxiliary pallet to observe the
-	// criteria for ranking
+	// Members of kreivo collective are managed via governance action. Besides,
+	// communities can claim promotions (or be demoted) through the communities
+	// manager, according to the ranking criteria set by governance.
 	type AddOrigin = EnsureRootWithSuccess<Self::AccountId, ConstU16<65535>>;
 	type RemoveOrigin = Self::AddOrigin;
 	type PromoteOrigin = Self::AddOrigin;
@@ -34,7 +34,7 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 	type RuntimeOrigin = <RuntimeOrigin as frame_support::traits::OriginTrait>::PalletsOrigin;
 
 	fn tracks() -> impl Iterator<Item = Cow<'static, Track<TrackId, Balance, BlockNumber>>> {
-		const DATA: [Track<TrackId, Balance, BlockNumber>; 5] = [
+		const DATA: [Track<TrackId, Balance, BlockNumber>; 6] = [
 			Track {
 				id: 0,
 				info: pallet_referenda::TrackInfo {
@@ -137,6 +137,24 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 					min_support: pallet_referenda::Curve::make_linear(28, 28, percent(50), percent(100)),
 				},
 			},
+			Track {
+				id: 5,
+				info: pallet_referenda::TrackInfo {
+					name: s("Payment Arbitration"),
+					max_deciding: 10,
+					decision_deposit: UNITS,
+					prepare_period: period::PREPARE,
+					decision_period: period::DECISION,
+					confirm_period: period::CONFIRM,
+					min_enactment_period: 1,
+					min_approval: pallet_referenda::Curve::LinearDecreasing {
+						length: Perbill::from_percent(100),
+						floor: Perbill::from_percent(50),
+						ceil: Perbill::from_percent(100),
+					},
+					min_support: pallet_referenda::Curve::make_linear(28, 28, percent(10), percent(100)),
+				},
+			},
 		];
 		DATA.iter().map(Borrowed)
 	}
@@ -153,6 +171,7 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 				Origin::ReferendumKiller => Ok(2),
 				Origin::CreateMemberships => Ok(3),
 				Origin::BlackHoleEventHorizon => Ok(4),
+				Origin::PaymentArbitration => Ok(5),
 			}
 		} else {
 			Err(())
@@ -1,8 +1,10 @@
 use super::*;
 
 use core::marker::PhantomData;
+use frame_support::traits::{PollStatus, Polling};
 use frame_system::{pallet_prelude::BlockNumberFor, EnsureRootWithSuccess};
-use pallet_communities::RuntimeOriginFor;
+use pallet_communities::{types::Tally, AssetIdOf, RuntimeOriginFor};
+use sp_runtime::{traits::MaybeSerializeDeserialize, DispatchError};
 
 use pallet_referenda::{BalanceOf, PalletsOriginOf, TrackIdOf, TracksInfo};
 
@@ -51,8 +53,9 @@ pub struct EnsureCommunityMember<T, I: 'static = ()>(PhantomData<T>, PhantomData
 
 impl<T, I> EnsureOriginWithArg<RuntimeOriginFor<T>, PalletsOriginOf<T>> for EnsureCommunityMember<T, I>
 where
-	T: pallet_communities::Config + pallet_referenda::Config<I>,
-	T::Tracks: TracksInfo<
+	T: pallet_communities_manager::Config + pallet_referenda::Config<I>,
+	AssetIdOf<T>: MaybeSerializeDeserialize,
+	<T as pallet_referenda::Config<I>>::Tracks: TracksInfo<
 		BalanceOf<T, I>,
 		BlockNumberFor<T>,
 		RuntimeOrigin = PalletsOriginOf<T>,
@@ -67,7 +70,8 @@ where
 	) -> Result<Self::Success, RuntimeOriginFor<T>> {
 		use frame_contrib_traits::memberships::Inspect;
 		use frame_system::RawOrigin::Signed;
-		let community_id = T::Tracks::track_for(track_origin).map_err(|_| o.clone())?;
+		let track_id = <T as pallet_referenda::Config<I>>::Tracks::track_for(track_origin).map_err(|_| o.clone())?;
+		let community_id = pallet_communities_manager::Pallet::<T>::community_of_track(track_id);
 
 		match o.clone().into() {
 			Ok(Signed(who)) => {
@@ -87,6 +91,75 @@ where
 	}
 }
 
+/// Exposes the community referenda to `pallet_communities`, using the
+/// community a referendum belongs to as its class, as a community can have
+/// several tracks.
+pub struct CommunityPolls;
+
+impl CommunityPolls {
+	fn community_of<V, M>(status: PollStatus<V, M, CommunityId>) -> PollStatus<V, M, CommunityId> {
+		match status {
+			PollStatus::Ongoing(tally, track_id) => {
+				PollStatus::Ongoing(tally, CommunitiesManager::community_of_track(track_id))
+			}
+			status => status,
+		}
+	}
+}
+
+impl Polling<Tally<Runtime>> for CommunityPolls {
+	type Index = <CommunityReferenda as Polling<Tally<Runtime>>>::Index;
+	type Votes = <CommunityReferenda as Polling<Tally<Runtime>>>::Votes;
+	type Class = CommunityId;
+	type Moment = BlockNumber;
+
+	fn classes() -> Vec<Self::Class> {
+		let mut classes: Vec<_> = CommunityReferenda::classes()
+			.into_iter()
+			.map(CommunitiesManager::community_of_track)
+			.collect();
+		classes.sort();
+		classes.dedup();
+		classes
+	}
+
+	fn as_ongoing(index: Self::Index) -> Option<(Tally<Runtime>, Self::Class)> {
+		CommunityReferenda::as_ongoing(index)
+			.map(|(tally, track_id)| (tally, CommunitiesManager::community_of_track(track_id)))
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally<Runtime>, Self::Moment, Self::Class>) -> R,
+	) -> R {
+		CommunityReferenda::access_poll(index, |status| f(Self::community_of(status)))
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally<Runtime>, Self::Moment, Self::Class>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		CommunityReferenda::try_access_poll(index, |status| f(Self::community_of(status)))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		// The main track of a community has the id of the community.
+		CommunityReferenda::create_ongoing(class)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		CommunityReferenda::end_ongoing(index, approved)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		let (track_id, max) = CommunityReferenda::max_ongoing();
+		(CommunitiesManager::community_of_track(track_id), max)
+	}
+}
+
 // Paritally from https://github.com/polkadot-fellows/runtimes/blob/b5ba0e91d5dd3c4020e848b27be5f2b47e16f281/relay/kusama/src/governance/mod.rs#L75
 impl pallet_referenda::Config<CommunityReferendaInstance> for Runtime {
 	type RuntimeCall = RuntimeCall;
@@ -13,7 +13,8 @@ parameter_types! {
 	pub const MaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
 }
 
-const WELL_KNOWN_ATTR_KEYS: [&[u8]; 3] = [b"membership_member_rank", b"membership_gas", b"membership_expiration"];
+pub(crate) const WELL_KNOWN_ATTR_KEYS: [&[u8]; 3] =
+	[b"membership_member_rank", b"membership_gas", b"membership_expiration"];
 
 parameter_types! {
 	pub CopySystemAttributesOnAssign: Box<dyn OnMembershipAssigned<AccountId, CommunityId, MembershipId>> =
@@ -1,10 +1,13 @@
 use super::*;
 
 use frame_contrib_traits::memberships::{NonFungiblesMemberships, WithHooks};
-use frame_support::traits::TryMapSuccess;
+use frame_support::traits::{MapSuccess, TryMapSuccess};
 use frame_system::{EnsureRootWithSuccess, EnsureSigned};
 use pallet_communities::origin::{EnsureCommunity, EnsureSignedPays};
-use sp_runtime::{morph_types, traits::AccountIdConversion};
+use sp_runtime::{
+	morph_types,
+	traits::{AccountIdConversion, Verify},
+};
 use virto_common::{CommunityId, MembershipId};
 
 use memberships::CopySystemAttributesOnAssign;
@@ -22,18 +25,125 @@ parameter_types! {
 	pub const MembershipNftAttr: &'static [u8; 10] = b"membership";
 	pub const CommunityDepositAmount: Balance = UNITS / 2;
 	pub const NoPay: CreationPayment = None;
+	pub const AnyCommunity: Option<CommunityId> = None;
+	pub const MembershipRenewalPeriod: BlockNumber = 30 * DAYS;
+	pub const MembershipReclaimGracePeriod: BlockNumber = 7 * DAYS;
+	pub const MembershipReclaimReward: Balance = CENTS;
+	pub const CommunityTrackHour: BlockNumber = HOURS;
+	pub const MaxCommunityTracks: u32 = 8;
+	pub const MaxCommunityRoles: u32 = 16;
+	pub const MaxMembershipLookups: u32 = 4;
+	pub const MaxMembershipMintsPerBlock: u32 = 1024;
+	pub const MaxQueuedMembershipMints: u32 = 16;
+	/// USDT, as held in Asset Hub.
+	pub const RankingVolumeAsset: FungibleAssetLocation = FungibleAssetLocation::Sibling(virto_common::Para {
+		id: 1000,
+		pallet: 50,
+		index: 1984,
+	});
 }
 
 morph_types! {
 	pub type AccountToCommunityId: TryMorph = |a: AccountId| -> Result<CommunityId, ()> {
 		PalletId::try_from_sub_account(&a).map(|(_, id)| id).ok_or(())
 	};
+	pub type AsSomeCommunity: Morph = |id: CommunityId| -> Option<CommunityId> { Some(id) };
 }
 type EnsureCommunityAccount = TryMapSuccess<EnsureSigned<AccountId>, AccountToCommunityId>;
 
 type RootCreatesCommunitiesForFree = EnsureRootWithSuccess<AccountId, NoPay>;
 type AnyoneElsePays = EnsureSignedPays<Runtime, CommunityDepositAmount, TreasuryAccount>;
 
+type RootDeregistersAnyCommunity = EnsureRootWithSuccess<AccountId, AnyCommunity>;
+type CommunityDeregistersItself =
+	MapSuccess<EitherOf<EnsureCommunity<Runtime>, EnsureCommunityAccount>, AsSomeCommunity>;
+
+/// Settles the payment of memberships bought with assets through
+/// `pallet_payments`, releasing the payment right away, in the same
+/// dispatch.
+pub struct SettleThroughPayments;
+
+impl pallet_communities_manager::MembershipPayments<AccountId, FungibleAssetLocation, Balance>
+	for SettleThroughPayments
+{
+	fn settle(
+		who: &AccountId,
+		beneficiary: &AccountId,
+		asset: FungibleAssetLocation,
+		amount: Balance,
+	) -> sp_runtime::DispatchResult {
+		use sp_runtime::traits::{Dispatchable, StaticLookup};
+
+		RuntimeCall::Payments(pallet_payments::Call::pay {
+			beneficiary: system::CommunityLookup::unlookup(beneficiary.clone()),
+			asset,
+			amount,
+			remark: None,
+		})
+		.dispatch(RuntimeOrigin::signed(who.clone()))
+		.map_err(|e| e.error)?;
+		let payment_id = PaymentIndices::last_payment_id(who, beneficiary)?;
+		RuntimeCall::Payments(pallet_payments::Call::release { payment_id })
+			.dispatch(RuntimeOrigin::signed(who.clone()))
+			.map_err(|e| e.error)?;
+
+		Ok(())
+	}
+
+	fn settle_weight() -> Weight {
+		use pallet_payments::WeightInfo;
+		<Runtime as pallet_payments::Config>::WeightInfo::pay(0)
+			.saturating_add(<Runtime as pallet_payments::Config>::WeightInfo::release())
+	}
+}
+
+/// Creates the inventories of communities in [`Listings`].
+pub struct ListingsInventories;
+
+impl pallet_communities_manager::CommunityInventories<CommunityId, AccountId> for ListingsInventories {
+	type InventoryId = virto_common::listings::InventoryId;
+
+	fn create_inventory(
+		community_id: CommunityId,
+		id: Self::InventoryId,
+		owner: &AccountId,
+	) -> sp_runtime::DispatchResult {
+		use frame_contrib_traits::listings::InventoryLifecycle;
+		Listings::create((community_id, id), owner)
+	}
+}
+
+/// The first `FungibleAssetLocation::Here` index reserved for community
+/// tokens. Every community gets the index resulting of adding its id.
+const COMMUNITY_ASSETS_START: u32 = 0x8000_0000;
+
+/// Maps each community to its own token, within the range of local asset
+/// indices reserved for community tokens.
+pub struct CommunityAssetId;
+
+impl sp_runtime::traits::Convert<CommunityId, FungibleAssetLocation> for CommunityAssetId {
+	fn convert(community_id: CommunityId) -> FungibleAssetLocation {
+		FungibleAssetLocation::Here(COMMUNITY_ASSETS_START + community_id as u32)
+	}
+}
+
+/// Provides the metrics used to rank communities in the collective. Members
+/// are the items of the memberships collection of the community, as counted
+/// by `pallet_nfts`, while the volume of payments is the one tracked by
+/// [`PaymentFees`] in [`RankingVolumeAsset`], since volumes in different assets
+/// can't be added up.
+pub struct CommunityMetrics;
+
+impl pallet_communities_manager::MetricsProvider<CommunityId, Balance> for CommunityMetrics {
+	fn members(community_id: &CommunityId) -> u32 {
+		CommunityMemberships::collection_items(*community_id).unwrap_or_default()
+	}
+
+	fn payments_volume(community_id: &CommunityId) -> Balance {
+		PaymentFees::payments_volume(community_id, &RankingVolumeAsset::get())
+	}
+}
+
 impl pallet_communities::Config for Runtime {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type WeightInfo = weights::pallet_communities::WeightInfo<Runtime>;
@@ -49,7 +159,7 @@ impl pallet_communities::Config for Runtime {
 
 	type MembershipId = MembershipId;
 	type MemberMgmt = WithHooks<Memberships, CopySystemAttributesOnAssign>;
-	type Polls = CommunityReferenda;
+	type Polls = governance::CommunityPolls;
 	type Assets = Assets;
 
 	type AssetsFreezer = AssetsFreezer;
@@ -66,13 +176,30 @@ impl pallet_communities_manager::Config for Runtime {
 	type CreateCollection = CommunityMemberships;
 	type MakeTank = MembershipsGasTank;
 	type Tracks = CommunityTracks;
+	type BlocksPerHour = CommunityTrackHour;
+	type MaxCommunityTracks = MaxCommunityTracks;
+	type MaxRoles = MaxCommunityRoles;
+	type MaxMembershipLookups = MaxMembershipLookups;
 	type RankedCollective = KreivoCollective;
+	type CommunityAssetId = CommunityAssetId;
+	type RankRequirementsOrigin = EnsureRoot<AccountId>;
+	type CommunityMetrics = CommunityMetrics;
 	type WeightInfo = weights::pallet_communities_manager::WeightInfo<Self>;
 	type RegisterOrigin = EitherOf<RootCreatesCommunitiesForFree, AnyoneElsePays>;
+	type DeregisterOrigin = EitherOf<RootDeregistersAnyCommunity, CommunityDeregistersItself>;
 	type CreateMembershipsOrigin = EitherOf<EnsureRoot<AccountId>, CreateMemberships>;
 	type MembershipsManagerCollectionId = MembershipsCollectionId;
 	type MembershipsManagerOwner = TreasuryAccount;
 	type ItemConfig = pallet_nfts::ItemConfig;
+	type RenewalPeriod = MembershipRenewalPeriod;
+	type ReclaimGracePeriod = MembershipReclaimGracePeriod;
+	type ReclaimReward = MembershipReclaimReward;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	type MaxMintsPerBlock = MaxMembershipMintsPerBlock;
+	type MaxQueuedMints = MaxQueuedMembershipMints;
+	type MembershipPayments = SettleThroughPayments;
+	type Inventories = ListingsInventories;
 	type CreateMemberships = CommunityMemberships;
 }
 
@@ -2,10 +2,9 @@ use super::*;
 
 use frame_support::{
 	parameter_types,
-	traits::{ConstBool, ConstU32, MapSuccess, Randomness},
+	traits::{ConstBool, ConstU32, Get, MapSuccess},
 };
-use frame_system::pallet_prelude::BlockNumberFor;
-use sp_runtime::morph_types;
+use sp_runtime::{morph_types, traits::Convert};
 
 use kreivo_apis::KreivoChainExtensions;
 use pallet_balances::Call as BalancesCall;
@@ -26,16 +25,64 @@ impl Contains<RuntimeCall> for CallFilter {
 	}
 }
 
-// randomness-collective-flip is insecure. Provide dummy randomness as
-// placeholder for the deprecated trait. https://github.com/paritytech/polkadot-sdk/blob/9bf1a5e23884921498b381728bfddaae93f83744/substrate/frame/contracts/mock-network/src/parachain/contracts_config.rs#L45
-pub struct DummyRandomness<T: pallet_contracts::Config>(core::marker::PhantomData<T>);
-impl<T: pallet_contracts::Config> Randomness<T::Hash, BlockNumberFor<T>> for DummyRandomness<T> {
-	fn random(_subject: &[u8]) -> (T::Hash, BlockNumberFor<T>) {
-		(Default::default(), Default::default())
+// Use Kreivo APIs for Chain Extensions
+parameter_types! {
+	pub const MaxBatchedCalls: u32 = 256;
+	pub const KreivoApisInputByteWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
+/// Charges each call to the Kreivo APIs with the benchmarked weight of the
+/// heaviest dispatch its `func_id` maps to.
+pub struct KreivoApisCallWeight;
+
+impl Convert<u16, Weight> for KreivoApisCallWeight {
+	fn convert(func_id: u16) -> Weight {
+		use super::{
+			communities::memberships::WELL_KNOWN_ATTR_KEYS,
+			listings_orders::{KeyLimit, ValueLimit},
+		};
+		use pallet_assets::WeightInfo as _;
+		use pallet_listings::WeightInfo as _;
+		use pallet_nfts::WeightInfo as _;
+
+		type AssetsWeight = weights::pallet_assets::WeightInfo<Runtime>;
+		type ListingsWeight = weights::pallet_listings::WeightInfo<Runtime>;
+		type NftsWeight = pallet_nfts::weights::SubstrateWeight<Runtime>;
+
+		let (key_limit, value_limit) = (KeyLimit::get(), ValueLimit::get());
+
+		match func_id {
+			// Assets
+			0x0001 | 0x0002 => AssetsWeight::transfer_keep_alive(),
+			// Inventories
+			0x0103 => ListingsWeight::create_inventory(),
+			0x0104 => ListingsWeight::archive_inventory(),
+			0x0105 => NftsWeight::set_attribute(),
+			0x0106 => NftsWeight::clear_attribute(),
+			0x0107 => NftsWeight::set_collection_metadata(),
+			0x0108 => NftsWeight::clear_collection_metadata(),
+			// Items
+			0x0114 => ListingsWeight::publish_item(value_limit),
+			0x0115 => ListingsWeight::set_item_price(),
+			0x0116 => ListingsWeight::clear_item_price(),
+			0x0117 | 0x0118 => ListingsWeight::mark_item_not_for_resale(),
+			0x0119 | 0x011a => ListingsWeight::mark_item_can_transfer(),
+			0x011b => ListingsWeight::set_item_attribute(key_limit, value_limit),
+			0x011c => ListingsWeight::clear_item_attribute(key_limit, value_limit),
+			0x011d | 0x011e => NftsWeight::transfer(),
+			0x011f => NftsWeight::set_metadata(),
+			0x0120 => NftsWeight::clear_metadata(),
+			// Memberships: assigning a membership copies its system attributes.
+			0x0200 => NftsWeight::transfer()
+				.saturating_add(NftsWeight::set_attribute().saturating_mul(WELL_KNOWN_ATTR_KEYS.len() as u64)),
+			0x0204 => NftsWeight::set_attribute(),
+			0x0205 => NftsWeight::clear_attribute(),
+			// Queries, and randomness
+			_ => <Runtime as frame_system::Config>::DbWeight::get().reads(2),
+		}
 	}
 }
 
-// Use Kreivo APIs for Chain Extensions
 impl kreivo_apis::Config for Runtime {
 	type Balances = Balances;
 	type Assets = Assets;
@@ -43,6 +90,10 @@ impl kreivo_apis::Config for Runtime {
 	type Listings = Listings;
 	type GroupInfo = Self;
 	type Memberships = Memberships;
+	type MaxBatchedCalls = MaxBatchedCalls;
+	type CallWeight = KreivoApisCallWeight;
+	type InputByteWeight = KreivoApisInputByteWeight;
+	type Randomness = RelayRandomness;
 }
 
 impl kreivo_apis::GroupInfo<AccountId> for Runtime {
@@ -89,7 +140,7 @@ morph_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = DummyRandomness<Self>;
+	type Randomness = RelayRandomness;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -173,5 +224,48 @@ impl pallet_contracts_store::Config for Runtime {
 	type AppId = listings::InventoryId;
 	type LicenseId = listings::ItemId;
 	type Listings = Listings;
+	type BindableContracts = super::revive::ReviveContracts;
 	type ContractsStoreMerchantId = ContractsStoreMerchantId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = benchmarks::ContractsStoreBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarks {
+	use super::*;
+
+	use frame_support::traits::fungible::Mutate;
+	use pallet_revive::AddressMapper;
+
+	pub struct ContractsStoreBenchmarkHelper;
+
+	impl pallet_contracts_store::BenchmarkHelper<AccountId> for ContractsStoreBenchmarkHelper {
+		fn bindable_contract() -> AccountId {
+			// The init code of an EVM contract whose code is a single `STOP`.
+			const CODE: [u8; 12] = [0x60, 0x01, 0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3, 0x00];
+			const SALT: [u8; 32] = [0; 32];
+			type Mapper = <Runtime as pallet_revive::Config>::AddressMapper;
+
+			let deployer: AccountId = frame_benchmarking::account("deployer", 0, 0);
+			Balances::set_balance(&deployer, 1_000 * UNITS);
+			Revive::map_account(RuntimeOrigin::signed(deployer.clone())).expect("the account is not mapped yet; qed");
+			Revive::instantiate_with_code(
+				RuntimeOrigin::signed(deployer.clone()),
+				0,
+				Weight::MAX,
+				100 * UNITS,
+				CODE.to_vec(),
+				vec![],
+				Some(SALT),
+			)
+			.expect("the contract can be instantiated; qed");
+
+			Mapper::to_account_id(&pallet_revive::create2(
+				&Mapper::to_address(&deployer),
+				&CODE,
+				&[],
+				&SALT,
+			))
+		}
+	}
 }
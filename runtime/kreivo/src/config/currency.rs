@@ -94,7 +94,8 @@ impl pallet_assets::Config<KreivoAssetsInstance> for Runtime {
 	type AssetId = FungibleAssetLocation;
 	type AssetIdParameter = FungibleAssetLocation;
 	type Currency = Balances;
-	/// Only root can create assets and force state changes.
+	/// Only root can create assets and force state changes. Community tokens
+	/// are created by the communities manager, within a reserved range of ids.
 	#[cfg(not(feature = "runtime-benchmarks"))]
 	type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -170,8 +171,10 @@ parameter_types! {
 		});
 }
 
-pub type MembershipsGasTank =
-	NonFungibleGasTank<Runtime, RelaychainData, CommunityMemberships, pallet_nfts::ItemConfig, MembershipIsNotExpired>;
+pub type MembershipsGasTank = pallet_communities_manager::RecordGasUsage<
+	Runtime,
+	NonFungibleGasTank<Runtime, RelaychainData, CommunityMemberships, pallet_nfts::ItemConfig, MembershipIsNotExpired>,
+>;
 
 impl pallet_gas_transaction_payment::Config for Runtime {
 	type WeightInfo = weights::pallet_gas_transaction_payment::WeightInfo<Self>;
@@ -37,6 +37,8 @@ pub mod pallet_custom_origins {
 		ReferendumKiller,
 		/// Origin able to control the black hole event horizon
 		BlackHoleEventHorizon,
+		/// Origin able to resolve payment disputes.
+		PaymentArbitration,
 	}
 
 	macro_rules! decl_unit_ensures {
@@ -74,6 +76,7 @@ pub mod pallet_custom_origins {
 		CreateMemberships,
 		ReferendumCanceller,
 		ReferendumKiller,
-		BlackHoleEventHorizon
+		BlackHoleEventHorizon,
+		PaymentArbitration
 	);
 }
@@ -22,8 +22,11 @@ pub use {
 	communities::Memberships,
 	currency::{KreivoAssetsCall, KreivoAssetsInstance, MembershipsGasTank},
 	governance::{pallet_custom_origins, TreasuryAccount},
-	payments::pallet_payment_indices,
-	system::{RelaychainData, RuntimeBlockWeights},
+	payments::{
+		pallet_payment_disputes, pallet_payment_fees, pallet_payment_indices, pallet_payment_milestones,
+		pallet_recurring_payments,
+	},
+	system::{pallet_relay_randomness, RelaychainData, RuntimeBlockWeights},
 };
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1,13 +1,27 @@
 use super::*;
 
+mod disputes;
+mod fees;
 mod indices;
+mod milestones;
+mod recurring;
 
-use frame_support::traits::EitherOf;
-use frame_system::EnsureSigned;
+use super::listings_orders::ListingsInstance;
+use core::marker::PhantomData;
+use frame_contrib_traits::memberships::{GenericRank, InspectEnumerable, Rank};
+use frame_support::traits::{fungible::HoldConsideration, EitherOf, LinearStoragePrice};
+use frame_system::{EnsureRoot, EnsureSigned};
 use pallet_communities::origin::AsSignedByCommunity;
-use sp_runtime::traits::AccountIdConversion;
+use pallet_listings::InventoryId;
+use pallet_payments::types::OnPaymentStatusChanged;
+use sp_runtime::traits::{AccountIdConversion, Convert, Zero};
+use virto_common::PaymentId;
 
+pub use disputes::pallet_payment_disputes;
+pub use fees::pallet_payment_fees;
 pub use indices::pallet_payment_indices;
+pub use milestones::pallet_payment_milestones;
+pub use recurring::pallet_recurring_payments;
 
 parameter_types! {
 	pub const MaxRemarkLength: u8 = 50;
@@ -22,6 +36,15 @@ pub const SENDER_FEE: Percent = Percent::from_percent(1);
 pub const BENEFICIARY_FEE: Percent = Percent::from_percent(3);
 pub const INCENTIVE_PERCENTAGE: u8 = 10;
 
+parameter_types! {
+	pub const DefaultFeeSchedule: pallet_payment_fees::FeeSchedule = pallet_payment_fees::FeeSchedule {
+		sender_fee: SENDER_FEE,
+		beneficiary_fee: BENEFICIARY_FEE,
+		community_fee: Percent::from_percent(0),
+	};
+	pub const MaxCommunityFee: Percent = Percent::from_percent(10);
+}
+
 impl FeeHandler<Runtime> for KreivoFeeHandler {
 	fn apply_fees(
 		asset: &AssetIdOf<Runtime>,
@@ -31,19 +54,46 @@ impl FeeHandler<Runtime> for KreivoFeeHandler {
 		_remark: Option<&[u8]>,
 	) -> Fees<Runtime> {
 		let min = <Assets as fungibles::Inspect<AccountId>>::minimum_balance(*asset);
-		let pallet_id = communities::CommunityPalletId::get();
-		let default_fee = |fee: Percent| (TreasuryAccount::get(), min.max(fee.mul_floor(*amount)), MANDATORY_FEE);
-		let is_community =
-			|who| matches!(PalletId::try_from_sub_account::<CommunityId>(who), Some((pid, _)) if pallet_id == pid );
+		let community_of = |who: &AccountId| CommunityOfAccount::convert(who.clone());
+		// Members get the discount that matches the rank of their membership
+		let discount_of = |community_id: &CommunityId, who| {
+			Memberships::memberships_of(who, Some(*community_id))
+				.take(1)
+				.filter_map(|(_, membership)| Memberships::rank_of(community_id, &membership))
+				.map(|rank| PaymentFees::discount_for(community_id, &rank))
+				.next()
+				.unwrap_or_default()
+		};
+		let fee = |to: AccountId, fee: Percent, discount: Percent| {
+			let fee = fee.mul_floor(*amount);
+			(to, min.max(fee.saturating_sub(discount.mul_floor(fee))), MANDATORY_FEE)
+		};
+
+		let sender_community = community_of(sender);
+		let beneficiary_community = community_of(beneficiary);
+		let community = beneficiary_community.or(sender_community);
+		let schedule = PaymentFees::schedule_for(community.as_ref());
 
 		let mut sender_fees = vec![];
 		let mut beneficiary_fees = vec![];
 
-		if !is_community(sender) {
-			sender_fees.push(default_fee(SENDER_FEE))
+		// Communities don't pay fees to the treasury
+		if sender_community.is_none() {
+			let discount = community.map(|id| discount_of(&id, sender)).unwrap_or_default();
+			if !schedule.sender_fee.is_zero() {
+				sender_fees.push(fee(TreasuryAccount::get(), schedule.sender_fee, discount));
+			}
+			if let Some(id) = beneficiary_community.filter(|_| !schedule.community_fee.is_zero()) {
+				sender_fees.push(fee(
+					Communities::community_account(&id),
+					schedule.community_fee,
+					discount,
+				));
+			}
 		}
-		if !is_community(beneficiary) {
-			beneficiary_fees.push(default_fee(BENEFICIARY_FEE))
+		if beneficiary_community.is_none() && !schedule.beneficiary_fee.is_zero() {
+			let discount = community.map(|id| discount_of(&id, beneficiary)).unwrap_or_default();
+			beneficiary_fees.push(fee(TreasuryAccount::get(), schedule.beneficiary_fee, discount));
 		}
 		Fees {
 			sender_pays: BoundedVec::try_from(sender_fees).unwrap(),
@@ -52,7 +102,118 @@ impl FeeHandler<Runtime> for KreivoFeeHandler {
 	}
 }
 
-impl pallet_payment_indices::Config for Runtime {}
+/// Adds the payments released to communities to their volume, before passing
+/// the status changes on to `Inner`.
+pub struct TrackCommunityVolume<Inner>(PhantomData<Inner>);
+
+impl<Inner: OnPaymentStatusChanged<PaymentId, Balance>> OnPaymentStatusChanged<PaymentId, Balance>
+	for TrackCommunityVolume<Inner>
+{
+	fn on_payment_charge_success(id: &PaymentId, fees: Balance, resulting_amount: Balance) {
+		Inner::on_payment_charge_success(id, fees, resulting_amount)
+	}
+
+	fn on_payment_cancelled(id: &PaymentId) {
+		Inner::on_payment_cancelled(id)
+	}
+
+	fn on_payment_released(id: &PaymentId) {
+		if let Ok((sender, beneficiary)) = pallet_payments::PaymentParties::<Runtime>::try_get(id) {
+			if let Some(community_id) = CommunityOfAccount::convert(beneficiary.clone()) {
+				if let Ok(payment) = pallet_payments::Payment::<Runtime>::try_get((sender, beneficiary), id) {
+					PaymentFees::note_payment_released(&community_id, &payment.asset, payment.amount);
+				}
+			}
+		}
+		Inner::on_payment_released(id)
+	}
+}
+
+impl pallet_payment_indices::Config for Runtime {
+	type Prefix = pallet_payment_indices::BeneficiaryPrefix;
+}
+
+pub struct CommunityOfAccount;
+impl Convert<AccountId, Option<CommunityId>> for CommunityOfAccount {
+	fn convert(who: AccountId) -> Option<CommunityId> {
+		match PalletId::try_from_sub_account::<CommunityId>(&who) {
+			Some((pid, id)) if pid == communities::CommunityPalletId::get() => Some(id),
+			_ => None,
+		}
+	}
+}
+
+pub struct CommunityAccount;
+impl Convert<CommunityId, AccountId> for CommunityAccount {
+	fn convert(id: CommunityId) -> AccountId {
+		Communities::community_account(&id)
+	}
+}
+
+impl pallet_payment_disputes::Config for Runtime {
+	type CommunityId = CommunityId;
+	type MerchantCommunity = CommunityOfAccount;
+	type CommunityAccount = CommunityAccount;
+	type CommunityOrigin = pallet_communities::origin::EnsureCommunity<Self>;
+	type ArbitrationOrigin = EitherOf<EnsureRoot<AccountId>, pallet_custom_origins::PaymentArbitration>;
+	type ArbitrationAccount = TreasuryAccount;
+	type WeightInfo = weights::pallet_payment_disputes::WeightInfo<Self>;
+}
+
+impl pallet_payment_fees::Config for Runtime {
+	type CommunityId = CommunityId;
+	type Rank = GenericRank;
+	type AssetId = FungibleAssetLocation;
+	type Balance = Balance;
+	type DefaultSchedule = DefaultFeeSchedule;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type CommunityOrigin = <Runtime as pallet_communities::Config>::AdminOrigin;
+	type MaxCommunityFee = MaxCommunityFee;
+	type MaxDiscounts = <Runtime as pallet_payments::Config>::MaxDiscounts;
+	type WeightInfo = weights::pallet_payment_fees::WeightInfo<Self>;
+}
+
+parameter_types! {
+	pub const RecurringPaymentHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::RecurringPayments(pallet_recurring_payments::HoldReason::RecurringPayment);
+	pub const RecurringPaymentBaseDeposit: Balance = deposit(1, 0);
+	pub const RecurringPaymentByteDeposit: Balance = deposit(0, 1);
+}
+
+impl pallet_recurring_payments::Config for Runtime {
+	type MaxRecurringPayments = ConstU32<32>;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		RecurringPaymentHoldReason,
+		LinearStoragePrice<RecurringPaymentBaseDeposit, RecurringPaymentByteDeposit, Balance>,
+	>;
+	type WeightInfo = weights::pallet_recurring_payments::WeightInfo<Self>;
+}
+
+type OrderId = <Runtime as pallet_orders::Config<ListingsInstance>>::OrderId;
+
+/// The buyer and the merchant of the orders of [`Orders`], where the
+/// merchant is the account of the community selling every item of the order.
+pub struct ExistingOrders;
+impl pallet_payment_milestones::OrderParties<OrderId, AccountId> for ExistingOrders {
+	fn parties_of(id: &OrderId) -> Option<(AccountId, AccountId)> {
+		let order = pallet_orders::Order::<Runtime, ListingsInstance>::get(id)?;
+		let mut merchants = order.items.iter().map(|(InventoryId(merchant, _), _)| *merchant);
+		let merchant = merchants.next()?;
+		merchants
+			.all(|m| m == merchant)
+			.then(|| (order.owner, Communities::community_account(&merchant)))
+	}
+}
+
+impl pallet_payment_milestones::Config for Runtime {
+	type MaxMilestones = ConstU32<16>;
+	type MaxNameLength = ConstU32<{ MaxRemarkLength::get() as u32 }>;
+	type OrderId = OrderId;
+	type Orders = ExistingOrders;
+	type OnStatusChanged = Orders;
+}
 
 impl pallet_payments::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
@@ -60,15 +221,19 @@ impl pallet_payments::Config for Runtime {
 	type WeightInfo = weights::pallet_payments::WeightInfo<Self>;
 	type SenderOrigin = EitherOf<AsSignedByCommunity<Self>, EnsureSigned<AccountId>>;
 	type BeneficiaryOrigin = EnsureSigned<AccountId>;
-	type DisputeResolver = frame_system::EnsureRootWithSuccess<AccountId, TreasuryAccount>;
-	type PaymentId = virto_common::PaymentId;
+	type DisputeResolver = EitherOf<
+		frame_system::EnsureRootWithSuccess<AccountId, TreasuryAccount>,
+		pallet_payment_disputes::EnsureResolvedBy<Self>,
+	>;
+	type PaymentId = PaymentId;
 	type Assets = Assets;
 	type AssetsHold = AssetsHolder;
 	type BlockNumberProvider = System;
 	type FeeHandler = KreivoFeeHandler;
 	type Scheduler = Scheduler;
 	type Preimages = Preimage;
-	type OnPaymentStatusChanged = Orders;
+	type OnPaymentStatusChanged =
+		TrackCommunityVolume<pallet_payment_disputes::ClearEscalations<Runtime, PaymentMilestones>>;
 	type GeneratePaymentId = PaymentIndices;
 	type PalletId = PaymentPalletId;
 	type IncentivePercentage = IncentivePercentage;
@@ -0,0 +1,225 @@
+//! Routes the resolution of payment disputes to whoever arbitrates the
+//! merchant.
+//!
+//! A community merchant resolves the disputes on the payments it receives
+//! through its own governance, so the payer has to trust the community
+//! to rule fairly on its own case. Payers that don't can escalate the
+//! payment to arbitration, after which only the arbitration origin (i.e.
+//! `PaymentArbitration`, or root) can resolve its dispute. Disputes with
+//! merchants that are not a community are always resolved via arbitration.
+
+#[frame_support::pallet]
+pub mod pallet_payment_disputes {
+	use frame_support::{pallet_prelude::*, weights::constants::RocksDbWeight};
+	use frame_system::pallet_prelude::*;
+	use pallet_payments::{
+		types::{BalanceOf, DisputeResult, OnPaymentStatusChanged},
+		WeightInfo as _,
+	};
+	use sp_runtime::traits::{Convert, StaticLookup};
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	/// The origin `pallet_payments` gets when a dispute is resolved through
+	/// this pallet, carrying the account that receives the dispute fee.
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, DecodeWithMemTracking, TypeInfo, RuntimeDebug)]
+	pub struct ResolvedBy<AccountId>(pub AccountId);
+
+	#[pallet::origin]
+	pub type Origin<T> = ResolvedBy<<T as frame_system::Config>::AccountId>;
+
+	pub trait WeightInfo {
+		fn escalate_dispute() -> Weight;
+	}
+
+	impl WeightInfo for () {
+		fn escalate_dispute() -> Weight {
+			Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config<RuntimeOrigin: From<Origin<Self>>, RuntimeEvent: From<Event<Self>>>
+		+ pallet_payments::Config
+	{
+		type CommunityId: Parameter + MaxEncodedLen;
+		/// The community a merchant account belongs to, if any.
+		type MerchantCommunity: Convert<Self::AccountId, Option<Self::CommunityId>>;
+		/// The account of a community, which receives the fee of the disputes
+		/// it resolves.
+		type CommunityAccount: Convert<Self::CommunityId, Self::AccountId>;
+		/// The governance origin of a community.
+		type CommunityOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::CommunityId>;
+		/// The origin able to resolve any dispute.
+		type ArbitrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The account that receives the fee of the disputes resolved via
+		/// arbitration.
+		type ArbitrationAccount: Get<Self::AccountId>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The payments their payer escalated to arbitration, until they're
+	/// released or cancelled.
+	#[pallet::storage]
+	pub type EscalatedPayments<T: Config> = StorageMap<_, Blake2_128Concat, T::PaymentId, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		DisputeEscalated { payment_id: T::PaymentId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The payment doesn't exist.
+		UnknownPayment,
+		/// Only the payer of a payment can escalate it.
+		NotPayer,
+		/// The payment was already escalated to arbitration.
+		AlreadyEscalated,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Resolves a payment dispute. Disputes with a community merchant are
+		/// resolved by the community's governance, unless the payer escalated
+		/// the payment, while any dispute can be resolved via arbitration.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as pallet_payments::Config>::WeightInfo::resolve_dispute()
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			sender: AccountIdLookupOf<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			payment_id: T::PaymentId,
+			dispute_result: DisputeResult,
+		) -> DispatchResultWithPostInfo {
+			let merchant = T::Lookup::lookup(beneficiary.clone())?;
+			let resolver = Self::resolver_of(origin, &merchant, &payment_id)?;
+
+			pallet_payments::Pallet::<T>::resolve_dispute(
+				Origin::<T>::from(ResolvedBy(resolver)).into(),
+				sender,
+				beneficiary,
+				payment_id.clone(),
+				dispute_result,
+			)?;
+			EscalatedPayments::<T>::remove(&payment_id);
+			Ok(().into())
+		}
+
+		/// Escalates a payment of the caller to arbitration, so the merchant
+		/// community can no longer resolve its dispute.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::escalate_dispute())]
+		pub fn escalate_dispute(origin: OriginFor<T>, payment_id: T::PaymentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (payer, _) =
+				pallet_payments::PaymentParties::<T>::try_get(&payment_id).map_err(|_| Error::<T>::UnknownPayment)?;
+			ensure!(payer == who, Error::<T>::NotPayer);
+			ensure!(
+				!EscalatedPayments::<T>::contains_key(&payment_id),
+				Error::<T>::AlreadyEscalated
+			);
+
+			EscalatedPayments::<T>::insert(&payment_id, ());
+			Self::deposit_event(Event::<T>::DisputeEscalated { payment_id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account resolving a dispute on a payment to `merchant`, if
+		/// `origin` is allowed to.
+		pub(crate) fn resolver_of(
+			origin: OriginFor<T>,
+			merchant: &T::AccountId,
+			payment_id: &T::PaymentId,
+		) -> Result<T::AccountId, DispatchError> {
+			if T::ArbitrationOrigin::try_origin(origin.clone()).is_ok() {
+				return Ok(T::ArbitrationAccount::get());
+			}
+
+			let community_id = T::CommunityOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::MerchantCommunity::convert(merchant.clone()) == Some(community_id.clone())
+					&& !EscalatedPayments::<T>::contains_key(payment_id),
+				DispatchError::BadOrigin
+			);
+			Ok(T::CommunityAccount::convert(community_id))
+		}
+	}
+
+	/// Ensures the origin comes from a dispute resolved through this pallet,
+	/// and returns the account of its resolver.
+	pub struct EnsureResolvedBy<T>(PhantomData<T>);
+
+	impl<T: Config, O: Into<Result<Origin<T>, O>> + From<Origin<T>>> EnsureOrigin<O> for EnsureResolvedBy<T> {
+		type Success = T::AccountId;
+
+		fn try_origin(o: O) -> Result<Self::Success, O> {
+			o.into().map(|ResolvedBy(resolver)| resolver)
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<O, ()> {
+			Ok(O::from(ResolvedBy(T::ArbitrationAccount::get())))
+		}
+	}
+
+	/// Forgets the escalation of the payments that get released or cancelled,
+	/// before passing the status changes on to `Inner`.
+	pub struct ClearEscalations<T, Inner>(PhantomData<(T, Inner)>);
+
+	impl<T: Config, Inner: OnPaymentStatusChanged<T::PaymentId, BalanceOf<T>>>
+		OnPaymentStatusChanged<T::PaymentId, BalanceOf<T>> for ClearEscalations<T, Inner>
+	{
+		fn on_payment_charge_success(id: &T::PaymentId, fees: BalanceOf<T>, resulting_amount: BalanceOf<T>) {
+			Inner::on_payment_charge_success(id, fees, resulting_amount)
+		}
+
+		fn on_payment_cancelled(id: &T::PaymentId) {
+			EscalatedPayments::<T>::remove(id);
+			Inner::on_payment_cancelled(id)
+		}
+
+		fn on_payment_released(id: &T::PaymentId) {
+			EscalatedPayments::<T>::remove(id);
+			Inner::on_payment_released(id)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarks {
+		use super::*;
+		use frame_benchmarking::v2::*;
+		use frame_system::RawOrigin;
+		use sp_runtime::traits::TrailingZeroInput;
+
+		#[benchmarks]
+		mod benchmarks {
+			use super::*;
+
+			#[benchmark]
+			fn escalate_dispute() -> Result<(), BenchmarkError> {
+				let payer: T::AccountId = whitelisted_caller();
+				let merchant: T::AccountId = account("merchant", 0, 0);
+				let payment_id =
+					T::PaymentId::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| BenchmarkError::Weightless)?;
+				pallet_payments::PaymentParties::<T>::insert(&payment_id, (payer.clone(), merchant));
+
+				#[extrinsic_call]
+				_(RawOrigin::Signed(payer), payment_id.clone());
+
+				assert!(EscalatedPayments::<T>::contains_key(&payment_id));
+				Ok(())
+			}
+		}
+	}
+}
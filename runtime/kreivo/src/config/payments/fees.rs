@@ -0,0 +1,243 @@
+//! On-chain schedules for the fees charged on payments.
+
+#[frame_support::pallet]
+pub mod pallet_payment_fees {
+	use frame_support::{pallet_prelude::*, weights::constants::RocksDbWeight};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{traits::Saturating, Percent};
+
+	/// The fees charged on a payment, as a share of its amount.
+	#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+	pub struct FeeSchedule {
+		/// Charged to the sender, and paid to the treasury.
+		pub sender_fee: Percent,
+		/// Charged to the beneficiary, and paid to the treasury.
+		pub beneficiary_fee: Percent,
+		/// Charged to whoever pays a community, and paid to the community.
+		/// Communities can set their own, up to
+		/// [`MaxCommunityFee`](Config::MaxCommunityFee).
+		pub community_fee: Percent,
+	}
+
+	pub trait WeightInfo {
+		fn set_global_schedule() -> Weight;
+		fn set_community_fee() -> Weight;
+		fn set_member_discounts() -> Weight;
+	}
+
+	impl WeightInfo for () {
+		fn set_global_schedule() -> Weight {
+			Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+		}
+		fn set_community_fee() -> Weight {
+			Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+		}
+		fn set_member_discounts() -> Weight {
+			Weight::from_parts(10_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+		}
+	}
+
+	/// The discounts a community grants to its members on the fees they pay,
+	/// given as the minimum rank required for each discount.
+	pub type DiscountsOf<T> = BoundedVec<(<T as Config>::Rank, Percent), <T as Config>::MaxDiscounts>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+		type CommunityId: Parameter + MaxEncodedLen;
+		type Rank: Parameter + MaxEncodedLen + PartialOrd;
+		type AssetId: Parameter + MaxEncodedLen;
+		type Balance: Parameter + MaxEncodedLen + Default + Copy + Saturating;
+		/// The schedule used until governance sets one.
+		type DefaultSchedule: Get<FeeSchedule>;
+		/// The origin allowed to set the global fee schedule.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The origin allowed to set the fee charged by a community, and the
+		/// discounts it grants.
+		type CommunityOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::CommunityId>;
+		/// The highest fee a community can charge on the payments it takes
+		/// part in.
+		#[pallet::constant]
+		type MaxCommunityFee: Get<Percent>;
+		#[pallet::constant]
+		type MaxDiscounts: Get<u32>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The fee schedule applied to every payment. Communities can only
+	/// replace its community fee with their own.
+	#[pallet::storage]
+	pub type GlobalSchedule<T: Config> = StorageValue<_, FeeSchedule, ValueQuery, T::DefaultSchedule>;
+
+	/// The fees set by communities for the payments they take part in.
+	#[pallet::storage]
+	pub type CommunityFees<T: Config> = StorageMap<_, Blake2_128Concat, T::CommunityId, Percent>;
+
+	/// The discounts on fees a community grants to its members.
+	#[pallet::storage]
+	pub type MemberDiscounts<T: Config> = StorageMap<_, Blake2_128Concat, T::CommunityId, DiscountsOf<T>, ValueQuery>;
+
+	/// The volume of the payments released to each community, for each of
+	/// the assets they were made with.
+	#[pallet::storage]
+	pub type PaymentsVolume<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::CommunityId, Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		GlobalScheduleSet {
+			schedule: FeeSchedule,
+		},
+		CommunityFeeSet {
+			id: T::CommunityId,
+			fee: Option<Percent>,
+		},
+		MemberDiscountsSet {
+			id: T::CommunityId,
+			discounts: DiscountsOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The fee is higher than the maximum a community can charge.
+		CommunityFeeTooHigh,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sets the fee schedule applied to every payment.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_global_schedule())]
+		pub fn set_global_schedule(origin: OriginFor<T>, schedule: FeeSchedule) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			GlobalSchedule::<T>::put(schedule);
+			Self::deposit_event(Event::<T>::GlobalScheduleSet { schedule });
+			Ok(())
+		}
+
+		/// Sets the fee the calling community charges on the payments it takes
+		/// part in. `None` falls back to the community fee of the global
+		/// schedule.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_community_fee())]
+		pub fn set_community_fee(origin: OriginFor<T>, fee: Option<Percent>) -> DispatchResult {
+			let id = T::CommunityOrigin::ensure_origin(origin)?;
+			ensure!(
+				fee.is_none_or(|fee| fee <= T::MaxCommunityFee::get()),
+				Error::<T>::CommunityFeeTooHigh
+			);
+			CommunityFees::<T>::set(&id, fee);
+			Self::deposit_event(Event::<T>::CommunityFeeSet { id, fee });
+			Ok(())
+		}
+
+		/// Sets the discounts on fees the calling community grants to its
+		/// members, given as the minimum rank required for each discount.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_member_discounts())]
+		pub fn set_member_discounts(origin: OriginFor<T>, discounts: DiscountsOf<T>) -> DispatchResult {
+			let id = T::CommunityOrigin::ensure_origin(origin)?;
+			MemberDiscounts::<T>::insert(&id, discounts.clone());
+			Self::deposit_event(Event::<T>::MemberDiscountsSet { id, discounts });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The fee schedule for a payment, given the community taking part in
+		/// it, if any. The fees paid to the treasury always come from the
+		/// global schedule.
+		pub fn schedule_for(maybe_community: Option<&T::CommunityId>) -> FeeSchedule {
+			let mut schedule = GlobalSchedule::<T>::get();
+			if let Some(fee) = maybe_community.and_then(CommunityFees::<T>::get) {
+				schedule.community_fee = fee.min(T::MaxCommunityFee::get());
+			}
+			schedule
+		}
+
+		/// The largest discount of a community a member of the given rank is
+		/// entitled to.
+		pub fn discount_for(community_id: &T::CommunityId, rank: &T::Rank) -> Percent {
+			MemberDiscounts::<T>::get(community_id)
+				.into_iter()
+				.filter(|(min_rank, _)| min_rank <= rank)
+				.map(|(_, discount)| discount)
+				.max()
+				.unwrap_or_default()
+		}
+
+		/// The volume of the payments released to a community in the given
+		/// asset.
+		pub fn payments_volume(community_id: &T::CommunityId, asset: &T::AssetId) -> T::Balance {
+			PaymentsVolume::<T>::get(community_id, asset)
+		}
+
+		/// Adds the amount of a payment released to a community to its volume
+		/// in the asset of the payment.
+		pub fn note_payment_released(community_id: &T::CommunityId, asset: &T::AssetId, amount: T::Balance) {
+			PaymentsVolume::<T>::mutate(community_id, asset, |volume| volume.saturating_accrue(amount));
+		}
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarks {
+		use super::*;
+		use frame_benchmarking::v2::*;
+		use sp_runtime::traits::TrailingZeroInput;
+
+		#[benchmarks]
+		mod benchmarks {
+			use super::*;
+
+			#[benchmark]
+			fn set_global_schedule() -> Result<(), BenchmarkError> {
+				let origin = T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+				let schedule = FeeSchedule {
+					sender_fee: Percent::from_percent(1),
+					beneficiary_fee: Percent::from_percent(1),
+					community_fee: Percent::from_percent(1),
+				};
+
+				#[extrinsic_call]
+				_(origin as T::RuntimeOrigin, schedule);
+
+				assert_eq!(GlobalSchedule::<T>::get(), schedule);
+				Ok(())
+			}
+
+			#[benchmark]
+			fn set_community_fee() -> Result<(), BenchmarkError> {
+				let origin = T::CommunityOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+				let id = T::CommunityOrigin::ensure_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+				let fee = T::MaxCommunityFee::get();
+
+				#[extrinsic_call]
+				_(origin as T::RuntimeOrigin, Some(fee));
+
+				assert_eq!(CommunityFees::<T>::get(id), Some(fee));
+				Ok(())
+			}
+
+			#[benchmark]
+			fn set_member_discounts() -> Result<(), BenchmarkError> {
+				let origin = T::CommunityOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+				let id = T::CommunityOrigin::ensure_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+				let rank = T::Rank::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| BenchmarkError::Weightless)?;
+				let discounts: DiscountsOf<T> = BoundedVec::truncate_from(
+					(0..T::MaxDiscounts::get())
+						.map(|_| (rank.clone(), Percent::from_percent(10)))
+						.collect(),
+				);
+
+				#[extrinsic_call]
+				_(origin as T::RuntimeOrigin, discounts.clone());
+
+				assert_eq!(MemberDiscounts::<T>::get(id), discounts);
+				Ok(())
+			}
+		}
+	}
+}
@@ -14,41 +14,106 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
 
-//! Temporary storage for payment indices.
+//! Generation of unique payment ids.
 
 #[frame_support::pallet]
 pub mod pallet_payment_indices {
-	use frame_support::{pallet_prelude::*, traits::Hooks};
+	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use sp_core::U256;
+	use sp_runtime::traits::{Convert, UniqueSaturatedInto};
+	use virto_common::PaymentId;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config + pallet_payments::Config<PaymentId = PaymentId> {
+		/// Derives the prefix of the ids of payments, given their sender and
+		/// beneficiary.
+		type Prefix: Convert<(Self::AccountId, Self::AccountId), [u8; 2]>;
+	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	/// The block and index of the last generated id.
 	#[pallet::storage]
-	pub(crate) type Index<T: Config> = StorageValue<_, u32, ValueQuery>;
+	pub(crate) type LastIndex<T: Config> = StorageValue<_, (u32, u16)>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The block number doesn't fit in a payment id.
+		BlockNumberOverflow,
+		/// No more payment ids are available within this block.
+		IndicesExhausted,
+		/// The next payment id is already taken by an existing payment.
+		PaymentIdTaken,
+		/// No payment from the sender to the beneficiary has been created with
+		/// the last generated id.
+		UnknownPayment,
+	}
+
+	/// Uses the first bytes of the encoded beneficiary as the prefix.
+	pub struct BeneficiaryPrefix;
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_finalize(_: BlockNumberFor<T>) {
-			Index::<T>::kill()
+	impl<AccountId: Encode> Convert<(AccountId, AccountId), [u8; 2]> for BeneficiaryPrefix {
+		fn convert((_, beneficiary): (AccountId, AccountId)) -> [u8; 2] {
+			let bytes = beneficiary.encode();
+			[
+				bytes.first().copied().unwrap_or_default(),
+				bytes.get(1).copied().unwrap_or_default(),
+			]
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The id the next payment from `sender` to `beneficiary` will get.
+		pub fn next_payment_id(sender: &T::AccountId, beneficiary: &T::AccountId) -> Result<PaymentId, Error<T>> {
+			Self::find_next(sender, beneficiary).map(|(_, id)| id)
+		}
+
+		/// The id of the last payment created within this block, as long as it
+		/// was created by `sender` to `beneficiary`.
+		pub fn last_payment_id(sender: &T::AccountId, beneficiary: &T::AccountId) -> Result<PaymentId, Error<T>> {
+			let block: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+			let (last_block, index) = LastIndex::<T>::get()
+				.filter(|(last_block, _)| u64::from(*last_block) == block)
+				.ok_or(Error::<T>::UnknownPayment)?;
+			let prefix = T::Prefix::convert((sender.clone(), beneficiary.clone()));
+			let id = PaymentId::from((last_block, index.into(), &prefix[..]));
+			ensure!(
+				pallet_payments::PaymentParties::<T>::contains_key(id),
+				Error::<T>::UnknownPayment
+			);
+			Ok(id)
+		}
+
+		/// Finds the next id, along with its index, as long as it is not taken
+		/// by an existing payment.
+		fn find_next(sender: &T::AccountId, beneficiary: &T::AccountId) -> Result<(u16, PaymentId), Error<T>> {
+			let block: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+			let block = u32::try_from(block).map_err(|_| Error::<T>::BlockNumberOverflow)?;
+			let index = match LastIndex::<T>::get() {
+				Some((last_block, last_index)) if last_block == block => {
+					last_index.checked_add(1).ok_or(Error::<T>::IndicesExhausted)?
+				}
+				_ => 0,
+			};
+			let prefix = T::Prefix::convert((sender.clone(), beneficiary.clone()));
+
+			let id = PaymentId::from((block, index.into(), &prefix[..]));
+			ensure!(
+				!pallet_payments::PaymentParties::<T>::contains_key(id),
+				Error::<T>::PaymentIdTaken
+			);
+			Ok((index, id))
 		}
 	}
 
 	impl<T: Config> pallet_payments::GeneratePaymentId<T::AccountId> for Pallet<T> {
-		type PaymentId = virto_common::PaymentId;
-
-		fn generate(_: &T::AccountId, beneficiary: &T::AccountId) -> Option<virto_common::PaymentId> {
-			let block: U256 = frame_system::Pallet::<T>::block_number().into();
-			let idx = Index::<T>::mutate(|index| {
-				let ix = *index;
-				*index += 1;
-				ix
-			});
-			Some((block.as_u32(), idx, beneficiary.encode().as_slice()).into())
+		type PaymentId = PaymentId;
+
+		fn generate(sender: &T::AccountId, beneficiary: &T::AccountId) -> Option<PaymentId> {
+			let (index, id) = Self::find_next(sender, beneficiary).ok()?;
+			LastIndex::<T>::put((id.block_number(), index));
+			Some(id)
 		}
 	}
 }
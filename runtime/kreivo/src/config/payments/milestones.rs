@@ -0,0 +1,402 @@
+//! Escrow payments released in milestones, where every milestone is a
+//! payment of `pallet_payments` on its own. This way every milestone can be
+//! released, refunded or disputed independently, and disputes are resolved
+//! by the `DisputeResolver` per milestone.
+//!
+//! An escrow can pay for an order, in which case the progress of the order is
+//! tracked as its milestones are settled.
+
+#[frame_support::pallet]
+pub mod pallet_payment_milestones {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_payments::{
+		types::{AssetIdOf, BalanceOf, OnPaymentStatusChanged},
+		WeightInfo as _,
+	};
+	use sp_runtime::traits::{Dispatchable, StaticLookup};
+	use virto_common::PaymentId;
+
+	use super::super::pallet_payment_indices;
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	/// The parties of the orders an escrow can pay for.
+	pub trait OrderParties<OrderId, AccountId> {
+		/// The buyer and the merchant of an order, if it exists.
+		fn parties_of(order: &OrderId) -> Option<(AccountId, AccountId)>;
+	}
+
+	pub type EscrowId = u32;
+	pub type MilestoneNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+	pub enum MilestoneStatus {
+		/// The funds of the milestone are held until it's delivered.
+		Pending,
+		/// The beneficiary marked the milestone as delivered, and awaits the
+		/// payer to approve or dispute it.
+		Delivered,
+		/// The payer disputed the delivery and requested a refund.
+		Disputed,
+		/// The funds were released to the beneficiary.
+		Released,
+		/// The funds were returned to the payer.
+		Refunded,
+	}
+
+	#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+	pub struct Milestone<Name, Balance> {
+		pub name: Name,
+		pub amount: Balance,
+		/// The payment holding the funds of the milestone.
+		pub payment_id: PaymentId,
+		pub status: MilestoneStatus,
+	}
+
+	#[derive(Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+	pub struct Escrow<AccountId, AssetId, Milestones, OrderId> {
+		pub payer: AccountId,
+		pub beneficiary: AccountId,
+		pub asset: AssetId,
+		pub milestones: Milestones,
+		/// The order paid by the escrow, if any.
+		pub order: Option<OrderId>,
+	}
+
+	pub type MilestoneOf<T> = Milestone<MilestoneNameOf<T>, BalanceOf<T>>;
+	pub type EscrowOf<T> = Escrow<
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+		BoundedVec<MilestoneOf<T>, <T as Config>::MaxMilestones>,
+		<T as Config>::OrderId,
+	>;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config<RuntimeEvent: From<Event<Self>>, RuntimeCall: From<pallet_payments::Call<Self>>>
+		+ pallet_payment_indices::Config
+	{
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+		/// The maximum length of the name of a milestone, which is used as the
+		/// remark of its payment.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		type OrderId: Parameter + MaxEncodedLen;
+		/// The orders an escrow can pay for, which are paid by their buyer to
+		/// their merchant.
+		type Orders: OrderParties<Self::OrderId, Self::AccountId>;
+		/// Notified of the status changes of the payments that don't hold a
+		/// milestone, e.g. so orders keep following the payments they make.
+		/// The payments holding a milestone are followed through its status
+		/// instead.
+		type OnStatusChanged: OnPaymentStatusChanged<PaymentId, BalanceOf<Self>>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	pub type NextEscrowId<T: Config> = StorageValue<_, EscrowId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Escrows<T: Config> = StorageMap<_, Blake2_128Concat, EscrowId, EscrowOf<T>>;
+
+	/// The escrow and milestone every payment holds the funds of.
+	#[pallet::storage]
+	pub type PaymentMilestone<T: Config> = StorageMap<_, Blake2_128Concat, PaymentId, (EscrowId, u32)>;
+
+	/// The escrow paying for every order, until all its milestones are
+	/// settled.
+	#[pallet::storage]
+	pub type OrderEscrow<T: Config> = StorageMap<_, Blake2_128Concat, T::OrderId, EscrowId>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		EscrowCreated {
+			id: EscrowId,
+			payer: T::AccountId,
+			beneficiary: T::AccountId,
+		},
+		MilestoneStatusChanged {
+			id: EscrowId,
+			milestone: u32,
+			status: MilestoneStatus,
+		},
+		/// A milestone of the escrow paying for an order was settled.
+		OrderProgressed {
+			order: T::OrderId,
+			settled: u32,
+			total: u32,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An escrow needs at least one milestone.
+		NoMilestones,
+		/// The escrow or milestone doesn't exist, or the caller doesn't take
+		/// part in it.
+		UnknownMilestone,
+		/// The milestone isn't in the status the action requires.
+		InvalidMilestoneStatus,
+		/// The order doesn't exist, or another escrow already pays for it.
+		InvalidOrder,
+		/// The payer isn't the buyer of the order, or the beneficiary isn't
+		/// its merchant.
+		NotOrderParty,
+		/// No more escrow ids are available.
+		EscrowIdsExhausted,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Creates an escrow paying `beneficiary` in milestones, optionally
+		/// for an `order`. The funds of every milestone are held in a payment
+		/// of its own.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as pallet_payments::Config>::WeightInfo::pay(T::MaxNameLength::get())
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+				.saturating_mul(milestones.len() as u64)
+				.saturating_add(T::DbWeight::get().reads_writes(3, 3))
+		)]
+		pub fn create(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			milestones: BoundedVec<(MilestoneNameOf<T>, BalanceOf<T>), T::MaxMilestones>,
+			order: Option<T::OrderId>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let beneficiary_account = T::Lookup::lookup(beneficiary.clone())?;
+			ensure!(!milestones.is_empty(), Error::<T>::NoMilestones);
+
+			let id = NextEscrowId::<T>::try_mutate(|id| {
+				let current = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::EscrowIdsExhausted)?;
+				Ok::<_, Error<T>>(current)
+			})?;
+			if let Some(order) = &order {
+				let (buyer, merchant) = T::Orders::parties_of(order).ok_or(Error::<T>::InvalidOrder)?;
+				ensure!(!OrderEscrow::<T>::contains_key(order), Error::<T>::InvalidOrder);
+				ensure!(
+					buyer == payer && merchant == beneficiary_account,
+					Error::<T>::NotOrderParty
+				);
+				OrderEscrow::<T>::insert(order, id);
+			}
+
+			let mut escrow_milestones = BoundedVec::new();
+			for (index, (name, amount)) in milestones.into_iter().enumerate() {
+				Self::dispatch_as(
+					&payer,
+					pallet_payments::Call::<T>::pay {
+						beneficiary: beneficiary.clone(),
+						asset: asset.clone(),
+						amount,
+						remark: name.to_vec().try_into().ok(),
+					},
+				)?;
+				let payment_id = pallet_payment_indices::Pallet::<T>::last_payment_id(&payer, &beneficiary_account)?;
+
+				PaymentMilestone::<T>::insert(payment_id, (id, index as u32));
+				escrow_milestones
+					.try_push(Milestone {
+						name,
+						amount,
+						payment_id,
+						status: MilestoneStatus::Pending,
+					})
+					.expect("as many milestones as given; qed");
+			}
+
+			Escrows::<T>::insert(
+				id,
+				Escrow {
+					payer: payer.clone(),
+					beneficiary: beneficiary_account.clone(),
+					asset,
+					milestones: escrow_milestones,
+					order,
+				},
+			);
+			Self::deposit_event(Event::<T>::EscrowCreated {
+				id,
+				payer,
+				beneficiary: beneficiary_account,
+			});
+			Ok(())
+		}
+
+		/// Marks a milestone as delivered. Called by the beneficiary.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn deliver(origin: OriginFor<T>, id: EscrowId, milestone: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::update_milestone(id, milestone, MilestoneStatus::Delivered, |escrow, m| {
+				ensure!(escrow.beneficiary == who, Error::<T>::UnknownMilestone);
+				ensure!(m.status == MilestoneStatus::Pending, Error::<T>::InvalidMilestoneStatus);
+				Ok(())
+			})?;
+			Ok(())
+		}
+
+		/// Approves a delivered milestone, releasing its funds to the
+		/// beneficiary. Called by the payer.
+		///
+		/// The milestone is settled as released once its payment is.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			<T as pallet_payments::Config>::WeightInfo::release()
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn approve(origin: OriginFor<T>, id: EscrowId, milestone: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let escrow = Escrows::<T>::get(id).ok_or(Error::<T>::UnknownMilestone)?;
+			let m = escrow
+				.milestones
+				.get(milestone as usize)
+				.ok_or(Error::<T>::UnknownMilestone)?;
+			ensure!(escrow.payer == who, Error::<T>::UnknownMilestone);
+			ensure!(
+				m.status == MilestoneStatus::Delivered,
+				Error::<T>::InvalidMilestoneStatus
+			);
+			Self::dispatch_as(
+				&who,
+				pallet_payments::Call::<T>::release {
+					payment_id: m.payment_id,
+				},
+			)
+		}
+
+		/// Disputes a delivered milestone, requesting a refund of its funds.
+		/// Called by the payer.
+		///
+		/// The beneficiary can then dispute the refund via `pallet_payments`,
+		/// to have the milestone arbitrated by the `DisputeResolver`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(
+			<T as pallet_payments::Config>::WeightInfo::request_refund()
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn dispute(origin: OriginFor<T>, id: EscrowId, milestone: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let payment_id = Self::update_milestone(id, milestone, MilestoneStatus::Disputed, |escrow, m| {
+				ensure!(escrow.payer == who, Error::<T>::UnknownMilestone);
+				ensure!(
+					m.status == MilestoneStatus::Delivered,
+					Error::<T>::InvalidMilestoneStatus
+				);
+				Ok(())
+			})?;
+			Self::dispatch_as(&who, pallet_payments::Call::<T>::request_refund { payment_id })
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn dispatch_as(who: &T::AccountId, call: pallet_payments::Call<T>) -> DispatchResult {
+			<T as frame_system::Config>::RuntimeCall::from(call)
+				.dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		/// Moves a milestone to `status`, once `check` passes, returning the
+		/// payment that holds its funds.
+		fn update_milestone(
+			id: EscrowId,
+			milestone: u32,
+			status: MilestoneStatus,
+			check: impl FnOnce(&EscrowOf<T>, &MilestoneOf<T>) -> DispatchResult,
+		) -> Result<PaymentId, DispatchError> {
+			let payment_id = Escrows::<T>::try_mutate(id, |maybe_escrow| {
+				let escrow = maybe_escrow.as_mut().ok_or(Error::<T>::UnknownMilestone)?;
+				let m = escrow
+					.milestones
+					.get(milestone as usize)
+					.ok_or(Error::<T>::UnknownMilestone)?;
+				check(escrow, m)?;
+
+				let m = &mut escrow.milestones[milestone as usize];
+				m.status = status;
+				Ok::<_, DispatchError>(m.payment_id)
+			})?;
+			Self::deposit_event(Event::<T>::MilestoneStatusChanged { id, milestone, status });
+			Ok(payment_id)
+		}
+
+		/// The milestones settled so far out of the total of the escrow paying
+		/// for an order, as long as it has milestones left to settle.
+		pub fn order_progress(order: &T::OrderId) -> Option<(u32, u32)> {
+			let escrow = Escrows::<T>::get(OrderEscrow::<T>::get(order)?)?;
+			Some(Self::progress_of(&escrow))
+		}
+
+		fn progress_of(escrow: &EscrowOf<T>) -> (u32, u32) {
+			let settled = escrow
+				.milestones
+				.iter()
+				.filter(|m| matches!(m.status, MilestoneStatus::Released | MilestoneStatus::Refunded))
+				.count();
+			(settled as u32, escrow.milestones.len() as u32)
+		}
+
+		/// Settles the milestone held by a payment, once the payment is
+		/// released or cancelled. Returns whether the payment held a
+		/// milestone.
+		fn settle(payment_id: &PaymentId, status: MilestoneStatus) -> bool {
+			let Some((id, milestone)) = PaymentMilestone::<T>::take(payment_id) else {
+				return false;
+			};
+			let progress = Escrows::<T>::mutate_exists(id, |maybe_escrow| {
+				let escrow = maybe_escrow.as_mut()?;
+				let m = escrow.milestones.get_mut(milestone as usize)?;
+				if m.status == status {
+					return None;
+				}
+				m.status = status;
+
+				let (settled, total) = Self::progress_of(escrow);
+				let order = escrow.order.clone();
+				// The escrow is done once every milestone is settled
+				if settled == total {
+					if let Some(order) = &order {
+						OrderEscrow::<T>::remove(order);
+					}
+					*maybe_escrow = None;
+				}
+				Some((order, settled, total))
+			});
+
+			if let Some((order, settled, total)) = progress {
+				Self::deposit_event(Event::<T>::MilestoneStatusChanged { id, milestone, status });
+				if let Some(order) = order {
+					Self::deposit_event(Event::<T>::OrderProgressed { order, settled, total });
+				}
+			}
+			true
+		}
+	}
+
+	impl<T: Config> OnPaymentStatusChanged<PaymentId, BalanceOf<T>> for Pallet<T> {
+		fn on_payment_charge_success(id: &PaymentId, fees: BalanceOf<T>, resulting_amount: BalanceOf<T>) {
+			T::OnStatusChanged::on_payment_charge_success(id, fees, resulting_amount)
+		}
+
+		fn on_payment_cancelled(id: &PaymentId) {
+			if !Self::settle(id, MilestoneStatus::Refunded) {
+				T::OnStatusChanged::on_payment_cancelled(id)
+			}
+		}
+
+		fn on_payment_released(id: &PaymentId) {
+			if !Self::settle(id, MilestoneStatus::Released) {
+				T::OnStatusChanged::on_payment_released(id)
+			}
+		}
+	}
+}
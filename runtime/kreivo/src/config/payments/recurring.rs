@@ -0,0 +1,423 @@
+//! Recurring payments (a.k.a. standing orders), where a payer authorizes a
+//! beneficiary to be paid on every period, up to a maximum amount of times.
+//!
+//! The payer holds a deposit for as long as a recurring payment is
+//! authorized, which is released once it's completed or cancelled.
+
+#[frame_support::pallet]
+pub mod pallet_recurring_payments {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{v3::Named as ScheduleNamed, DispatchTime},
+			Consideration, Footprint, StorePreimage,
+		},
+		weights::constants::RocksDbWeight,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_payments::{
+		types::{AssetIdOf, BalanceOf},
+		WeightInfo as _,
+	};
+	use pallet_scheduler::WeightInfo as _;
+	use sp_runtime::traits::{Dispatchable, StaticLookup, Zero};
+	use virto_common::PaymentId;
+
+	use super::super::pallet_payment_indices;
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+	pub type RecurringPaymentId = u32;
+
+	/// The priority of the scheduled payments of every cycle.
+	const CHARGE_PRIORITY: u8 = 128;
+
+	pub trait WeightInfo {
+		fn authorize() -> Weight;
+		fn cancel() -> Weight;
+		fn charge() -> Weight;
+	}
+
+	impl WeightInfo for () {
+		fn authorize() -> Weight {
+			Weight::from_parts(50_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(6, 7))
+		}
+		fn cancel() -> Weight {
+			Weight::from_parts(40_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(6, 6))
+		}
+		fn charge() -> Weight {
+			Weight::from_parts(30_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+		}
+	}
+
+	/// A payment the payer authorized to be made on every period.
+	#[derive(Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, Debug, TypeInfo)]
+	pub struct RecurringPayment<AccountId, AssetId, Balance, BlockNumber, Ticket> {
+		pub payer: AccountId,
+		pub beneficiary: AccountId,
+		pub asset: AssetId,
+		pub amount: Balance,
+		/// The blocks between every payment.
+		pub period: BlockNumber,
+		/// The payments left to be made.
+		pub remaining: u32,
+		/// The deposit held from the payer.
+		pub deposit: Ticket,
+	}
+
+	pub type RecurringPaymentOf<T> = RecurringPayment<
+		<T as frame_system::Config>::AccountId,
+		AssetIdOf<T>,
+		BalanceOf<T>,
+		BlockNumberFor<T>,
+		<T as Config>::Consideration,
+	>;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config<RuntimeEvent: From<Event<Self>>, RuntimeCall: From<pallet_payments::Call<Self>>>
+		+ pallet_payment_indices::Config
+		+ pallet_scheduler::Config<RuntimeCall: From<Call<Self>>>
+	{
+		/// The maximum amount of recurring payments an account can authorize.
+		#[pallet::constant]
+		type MaxRecurringPayments: Get<u32>;
+		/// The deposit held from the payer of a recurring payment, given the
+		/// storage it takes.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The reasons the pallet holds funds for.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of an authorized recurring payment.
+		RecurringPayment,
+	}
+
+	#[pallet::storage]
+	pub type NextRecurringPaymentId<T: Config> = StorageValue<_, RecurringPaymentId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type RecurringPayments<T: Config> = StorageMap<_, Blake2_128Concat, RecurringPaymentId, RecurringPaymentOf<T>>;
+
+	/// The recurring payments authorized by every payer.
+	#[pallet::storage]
+	pub type PayerRecurringPayments<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<RecurringPaymentId, T::MaxRecurringPayments>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Authorized {
+			id: RecurringPaymentId,
+			payer: T::AccountId,
+			beneficiary: T::AccountId,
+		},
+		Charged {
+			id: RecurringPaymentId,
+			payment_id: PaymentId,
+		},
+		ChargeFailed {
+			id: RecurringPaymentId,
+			error: DispatchError,
+		},
+		Completed {
+			id: RecurringPaymentId,
+		},
+		Cancelled {
+			id: RecurringPaymentId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The period, amount or count of payments is zero.
+		InvalidRecurringPayment,
+		/// The payer has authorized too many recurring payments.
+		TooManyRecurringPayments,
+		/// The recurring payment doesn't exist, or it isn't authorized by the
+		/// caller.
+		UnknownRecurringPayment,
+		/// The payments of every cycle couldn't be scheduled.
+		SchedulingFailed,
+		/// No more recurring payment ids are available.
+		RecurringPaymentIdsExhausted,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorizes `beneficiary` to be paid `amount` of `asset` on every
+		/// `period`, `max_payments` times. The first payment is made on the
+		/// next block, and every payment is a regular payment of
+		/// `pallet_payments`, released as soon as it's made.
+		///
+		/// A deposit is held from the payer until the recurring payment is
+		/// completed or cancelled.
+		#[pallet::call_index(0)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::authorize().saturating_add(
+				<T as pallet_scheduler::Config>::WeightInfo::schedule_named(T::MaxScheduledPerBlock::get())
+			)
+		)]
+		pub fn authorize(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+			max_payments: u32,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				!period.is_zero() && !amount.is_zero() && max_payments > 0,
+				Error::<T>::InvalidRecurringPayment
+			);
+
+			let id = NextRecurringPaymentId::<T>::try_mutate(|id| {
+				let current = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::RecurringPaymentIdsExhausted)?;
+				Ok::<_, Error<T>>(current)
+			})?;
+			PayerRecurringPayments::<T>::try_mutate(&payer, |ids| ids.try_push(id))
+				.map_err(|_| Error::<T>::TooManyRecurringPayments)?;
+			let deposit = T::Consideration::new(&payer, Self::footprint())?;
+
+			let call = <T as pallet_scheduler::Config>::Preimages::bound(
+				<T as pallet_scheduler::Config>::RuntimeCall::from(Call::<T>::charge { id }),
+			)?;
+			<pallet_scheduler::Pallet<T> as ScheduleNamed<_, _, _>>::schedule_named(
+				Self::task_name(id),
+				DispatchTime::After(Zero::zero()),
+				Some((period, max_payments)).filter(|(_, count)| *count > 1),
+				CHARGE_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				call,
+			)
+			.map_err(|_| Error::<T>::SchedulingFailed)?;
+
+			RecurringPayments::<T>::insert(
+				id,
+				RecurringPayment {
+					payer: payer.clone(),
+					beneficiary: beneficiary.clone(),
+					asset,
+					amount,
+					period,
+					remaining: max_payments,
+					deposit,
+				},
+			);
+			Self::deposit_event(Event::<T>::Authorized { id, payer, beneficiary });
+			Ok(())
+		}
+
+		/// Cancels a recurring payment, so no further payments are made.
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::cancel().saturating_add(
+				<T as pallet_scheduler::Config>::WeightInfo::cancel_named(T::MaxScheduledPerBlock::get())
+			)
+		)]
+		pub fn cancel(origin: OriginFor<T>, id: RecurringPaymentId) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let recurring_payment = RecurringPayments::<T>::get(id).ok_or(Error::<T>::UnknownRecurringPayment)?;
+			ensure!(recurring_payment.payer == payer, Error::<T>::UnknownRecurringPayment);
+
+			// The task is already gone if its last payment is being made
+			let _ = <pallet_scheduler::Pallet<T> as ScheduleNamed<_, _, _>>::cancel_named(Self::task_name(id));
+			Self::remove(id, recurring_payment)?;
+			Self::deposit_event(Event::<T>::Cancelled { id });
+			Ok(())
+		}
+
+		/// Makes and releases the payment of the current cycle of a recurring
+		/// payment. It's dispatched by the scheduler.
+		///
+		/// A failed payment, e.g. because the payer ran out of funds, still
+		/// counts as a cycle.
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::charge()
+				.saturating_add(<T as pallet_payments::Config>::WeightInfo::pay(0))
+				.saturating_add(<T as pallet_payments::Config>::WeightInfo::release())
+		)]
+		pub fn charge(origin: OriginFor<T>, id: RecurringPaymentId) -> DispatchResult {
+			ensure_root(origin)?;
+			let mut recurring_payment = RecurringPayments::<T>::get(id).ok_or(Error::<T>::UnknownRecurringPayment)?;
+
+			match frame_support::storage::with_storage_layer(|| Self::pay_and_release(&recurring_payment)) {
+				Ok(payment_id) => Self::deposit_event(Event::<T>::Charged { id, payment_id }),
+				Err(error) => Self::deposit_event(Event::<T>::ChargeFailed { id, error }),
+			}
+
+			recurring_payment.remaining = recurring_payment.remaining.saturating_sub(1);
+			if recurring_payment.remaining.is_zero() {
+				Self::remove(id, recurring_payment)?;
+				Self::deposit_event(Event::<T>::Completed { id });
+			} else {
+				RecurringPayments::<T>::insert(id, recurring_payment);
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The name of the scheduled task making the payments of a recurring
+		/// payment.
+		pub fn task_name(id: RecurringPaymentId) -> [u8; 32] {
+			(b"recurring_payment", id).using_encoded(sp_core::blake2_256)
+		}
+
+		/// Makes the payment of a cycle and releases it right away, so the
+		/// beneficiary gets paid like with a transfer.
+		fn pay_and_release(recurring_payment: &RecurringPaymentOf<T>) -> Result<PaymentId, DispatchError> {
+			let RecurringPayment {
+				payer,
+				beneficiary,
+				asset,
+				amount,
+				..
+			} = recurring_payment;
+			let dispatch_as_payer = |call: pallet_payments::Call<T>| {
+				<T as frame_system::Config>::RuntimeCall::from(call)
+					.dispatch(frame_system::RawOrigin::Signed(payer.clone()).into())
+					.map_err(|e| e.error)
+			};
+
+			dispatch_as_payer(pallet_payments::Call::<T>::pay {
+				beneficiary: T::Lookup::unlookup(beneficiary.clone()),
+				asset: asset.clone(),
+				amount: *amount,
+				remark: None,
+			})?;
+			let payment_id = pallet_payment_indices::Pallet::<T>::last_payment_id(payer, beneficiary)?;
+			dispatch_as_payer(pallet_payments::Call::<T>::release { payment_id })?;
+			Ok(payment_id)
+		}
+
+		/// The storage taken by a recurring payment, which its deposit is
+		/// based on.
+		fn footprint() -> Footprint {
+			Footprint::from_mel::<(RecurringPaymentId, RecurringPaymentOf<T>)>()
+		}
+
+		/// Forgets a recurring payment, and releases its deposit.
+		fn remove(id: RecurringPaymentId, recurring_payment: RecurringPaymentOf<T>) -> DispatchResult {
+			RecurringPayments::<T>::remove(id);
+			PayerRecurringPayments::<T>::mutate(&recurring_payment.payer, |ids| ids.retain(|i| *i != id));
+			recurring_payment.deposit.drop(&recurring_payment.payer)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarks {
+		use super::*;
+		use frame_benchmarking::v2::*;
+		use frame_system::RawOrigin;
+		use sp_runtime::traits::TrailingZeroInput;
+
+		/// Authorizes a recurring payment from a funded payer, making
+		/// `max_payments` payments.
+		fn authorize_from<T: Config>(
+			payer: &T::AccountId,
+			max_payments: u32,
+		) -> Result<RecurringPaymentId, BenchmarkError> {
+			let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+			let asset =
+				AssetIdOf::<T>::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| BenchmarkError::Weightless)?;
+			let amount =
+				BalanceOf::<T>::decode(&mut TrailingZeroInput::new(&[1])).map_err(|_| BenchmarkError::Weightless)?;
+			T::Consideration::ensure_successful(payer, Pallet::<T>::footprint());
+
+			let id = NextRecurringPaymentId::<T>::get();
+			Pallet::<T>::authorize(
+				RawOrigin::Signed(payer.clone()).into(),
+				T::Lookup::unlookup(beneficiary),
+				asset,
+				amount,
+				1u32.into(),
+				max_payments,
+			)?;
+			Ok(id)
+		}
+
+		#[benchmarks]
+		mod benchmarks {
+			use super::*;
+
+			#[benchmark]
+			fn authorize() -> Result<(), BenchmarkError> {
+				let payer: T::AccountId = whitelisted_caller();
+				let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+				let asset =
+					AssetIdOf::<T>::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| BenchmarkError::Weightless)?;
+				let amount = BalanceOf::<T>::decode(&mut TrailingZeroInput::new(&[1]))
+					.map_err(|_| BenchmarkError::Weightless)?;
+				T::Consideration::ensure_successful(&payer, Pallet::<T>::footprint());
+
+				// The payer already authorized as many recurring payments as allowed
+				// but one
+				for _ in 1..T::MaxRecurringPayments::get() {
+					authorize_from::<T>(&payer, 2)?;
+				}
+				let id = NextRecurringPaymentId::<T>::get();
+
+				#[extrinsic_call]
+				_(
+					RawOrigin::Signed(payer),
+					T::Lookup::unlookup(beneficiary),
+					asset,
+					amount,
+					1u32.into(),
+					2,
+				);
+
+				assert!(RecurringPayments::<T>::contains_key(id));
+				Ok(())
+			}
+
+			#[benchmark]
+			fn cancel() -> Result<(), BenchmarkError> {
+				let payer: T::AccountId = whitelisted_caller();
+				let mut id = 0;
+				for _ in 0..T::MaxRecurringPayments::get() {
+					id = authorize_from::<T>(&payer, 2)?;
+				}
+
+				#[extrinsic_call]
+				_(RawOrigin::Signed(payer), id);
+
+				assert!(!RecurringPayments::<T>::contains_key(id));
+				Ok(())
+			}
+
+			#[benchmark]
+			fn charge() -> Result<(), BenchmarkError> {
+				// The payment of the last cycle, which completes the recurring
+				// payment. The payment itself is charged with the weights of
+				// `pallet_payments`.
+				let payer: T::AccountId = whitelisted_caller();
+				let mut id = 0;
+				for _ in 0..T::MaxRecurringPayments::get() {
+					id = authorize_from::<T>(&payer, 1)?;
+				}
+
+				#[extrinsic_call]
+				_(RawOrigin::Root, id);
+
+				assert!(!RecurringPayments::<T>::contains_key(id));
+				Ok(())
+			}
+		}
+	}
+}
@@ -1,5 +1,9 @@
 use super::*;
 
+use frame_support::traits::Contains;
+use kreivo_apis::{AssetsPrecompile, ListingsPrecompile, MembershipsPrecompile};
+use pallet_revive::AddressMapper;
+
 #[cfg(not(feature = "zombienet"))]
 use frame_system::EnsureRootWithSuccess;
 #[cfg(feature = "zombienet")]
@@ -21,7 +25,11 @@ impl pallet_revive::Config for Runtime {
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_revive::weights::SubstrateWeight<Self>;
-	type Precompiles = ();
+	type Precompiles = (
+		AssetsPrecompile<Self>,
+		ListingsPrecompile<Self>,
+		MembershipsPrecompile<Self>,
+	);
 	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
 	type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 	type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
@@ -42,3 +50,14 @@ impl pallet_revive::Config for Runtime {
 	type FindAuthor = <Runtime as pallet_authorship::Config>::FindAuthor;
 	type AllowEVMBytecode = ConstBool<true>; // Virto accepts EVM Bytecode (?)
 }
+
+/// The accounts of `pallet-revive` contracts.
+pub struct ReviveContracts;
+
+impl Contains<AccountId> for ReviveContracts {
+	fn contains(who: &AccountId) -> bool {
+		pallet_revive::AccountInfo::<Runtime>::is_contract(
+			&<Runtime as pallet_revive::Config>::AddressMapper::to_address(who),
+		)
+	}
+}
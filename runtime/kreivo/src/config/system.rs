@@ -24,6 +24,9 @@ use sp_runtime::{
 	DispatchError,
 };
 
+mod relay_randomness;
+pub use relay_randomness::pallet_relay_randomness;
+
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 
 // #[runtime::pallet_index(0)]
@@ -117,9 +120,19 @@ parameter_types! {
 
 pub type RelaychainData = cumulus_pallet_parachain_system::RelaychainDataProvider<Runtime>;
 
+// #[runtime::pallet_index(5)]
+// pub type RelayRandomness
+parameter_types! {
+	pub const RelayStateProofByteWeight: Weight = Weight::from_parts(1_000, 0);
+}
+
+impl pallet_relay_randomness::Config for Runtime {
+	type ProofByteWeight = RelayStateProofByteWeight;
+}
+
 impl cumulus_pallet_parachain_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnSystemEvent = ();
+	type OnSystemEvent = RelayRandomness;
 	type SelfParaId = parachain_info::Pallet<Runtime>;
 	type OutboundXcmpMessageSource = XcmpQueue;
 	type DmpQueue = frame_support::traits::EnqueueWithOrigin<MessageQueue, RelayOrigin>;
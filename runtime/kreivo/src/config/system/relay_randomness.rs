@@ -0,0 +1,90 @@
+//! A source of randomness derived from the BABE randomness of the relay chain,
+//! as read from the relay chain state proof included in every block.
+//!
+//! The randomness of the _previous_ relay chain epoch is used, since it can't
+//! be influenced by the relay chain block authors anymore. However, it becomes
+//! public as soon as the current epoch starts: the returned block number is the
+//! first block in which this chain observed it, and values must only be used
+//! to resolve outcomes committed to before that block. In practice, this means
+//! a delay of up to one relay chain epoch between committing and revealing.
+
+#[frame_support::pallet]
+pub mod pallet_relay_randomness {
+	use cumulus_pallet_parachain_system::{relay_state_snapshot::RelayChainStateProof, OnSystemEvent, RelayStateProof};
+	use cumulus_primitives_core::{relay_chain::well_known_keys, PersistedValidationData};
+	use frame_support::{dispatch::DispatchClass, pallet_prelude::*, traits::Randomness};
+	use frame_system::pallet_prelude::*;
+	use kreivo_apis::MaybeRandomness;
+	use sp_runtime::traits::{Hash, Zero};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {
+		/// The weight of verifying every byte of the relay chain state proof.
+		#[pallet::constant]
+		type ProofByteWeight: Get<Weight>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The BABE randomness of the previous relay chain epoch, along with the
+	/// block at which it was first observed by this chain.
+	#[pallet::storage]
+	pub type EpochRandomness<T: Config> = StorageValue<_, ([u8; 32], BlockNumberFor<T>)>;
+
+	impl<T: Config> OnSystemEvent for Pallet<T> {
+		fn on_validation_data(data: &PersistedValidationData) {
+			// This runs within the mandatory `set_validation_data` inherent, whose
+			// weight doesn't account for reading the proof once more.
+			let Some(proof) = RelayStateProof::<T>::get() else {
+				Self::register_weight(T::DbWeight::get().reads(1));
+				return;
+			};
+			Self::register_weight(
+				T::DbWeight::get()
+					.reads_writes(2, 1)
+					.saturating_add(T::ProofByteWeight::get().saturating_mul(proof.encoded_size() as u64)),
+			);
+
+			let Ok(relay_state) =
+				RelayChainStateProof::new(T::SelfParaId::get(), data.relay_parent_storage_root, proof)
+			else {
+				return;
+			};
+			let Ok(Some(randomness)) =
+				relay_state.read_optional_entry::<[u8; 32]>(well_known_keys::ONE_EPOCH_AGO_RANDOMNESS)
+			else {
+				return;
+			};
+
+			if !matches!(EpochRandomness::<T>::get(), Some((current, _)) if current == randomness) {
+				EpochRandomness::<T>::put((randomness, frame_system::Pallet::<T>::block_number()));
+			}
+		}
+
+		fn on_validation_code_applied() {}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn register_weight(weight: Weight) {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+		}
+	}
+
+	impl<T: Config> MaybeRandomness<T::Hash, BlockNumberFor<T>> for Pallet<T> {
+		fn maybe_random(subject: &[u8]) -> Option<(T::Hash, BlockNumberFor<T>)> {
+			let (randomness, known_since) = EpochRandomness::<T>::get()?;
+			Some((T::Hashing::hash_of(&(randomness, subject)), known_since))
+		}
+	}
+
+	/// Required by `pallet_contracts`, which can't handle a missing seed. Before
+	/// the first seed, values are derived from an all-zero seed known since
+	/// block zero, which callers must treat as not random. The Kreivo APIs use
+	/// [`MaybeRandomness`] instead, and fail in that case.
+	impl<T: Config> Randomness<T::Hash, BlockNumberFor<T>> for Pallet<T> {
+		fn random(subject: &[u8]) -> (T::Hash, BlockNumberFor<T>) {
+			Self::maybe_random(subject).unwrap_or_else(|| (T::Hashing::hash_of(&([0u8; 32], subject)), Zero::zero()))
+		}
+	}
+}
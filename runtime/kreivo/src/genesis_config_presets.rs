@@ -1,4 +1,5 @@
 use crate::*;
+use pallet_communities_manager::{CommunityToken, GenesisTrack, TrackTemplate};
 use runtime_constants::genesis_presets::*;
 use sp_genesis_builder::PresetId;
 
@@ -57,6 +58,11 @@ mod local {
 		invulnerables: Vec<(AccountId, AuraId)>,
 		endowed_accounts: Vec<AccountId>,
 	) -> serde_json::Value {
+		// The root community account pays for the deposits of its token and
+		// inventory.
+		let root_community = Communities::community_account(&1);
+		let endowed_accounts = endowed_accounts.into_iter().chain([root_community]).collect();
+
 		let mut genesis = dev::genesis(id, invulnerables, endowed_accounts)
 			.as_object()
 			.cloned()
@@ -65,8 +71,24 @@ mod local {
 		let mut community_manager = serde_json::json!({
 			"communitiesManager": CommunitiesManagerConfig {
 				// A community to cover a sudo-ish management.
-				communities: vec![(1, String::from("root"), sudo, None, Some(1))],
+				communities: vec![(1, String::from("root"), sudo.clone(), None, Some(1))],
 				memberships: vec![(1, 10, UNITS, (None, None), None)],
+				tracks: vec![(1, GenesisTrack::Template(TrackTemplate::Basic))],
+				tokens: vec![(
+					1,
+					CommunityToken {
+						name: BoundedVec::truncate_from(b"Root Token".to_vec()),
+						symbol: BoundedVec::truncate_from(b"ROOT".to_vec()),
+						decimals: 12,
+						min_balance: 1,
+						initial_supply: 1_000_000 * UNITS,
+					},
+				)],
+				// The sudo account is the first member of the root community.
+				members: vec![(1, sudo, 1, 1)],
+				gas_tanks: vec![(1, 1, (Some(Weight::from_parts(10_000_000_000, 1_000_000)), Some(DAYS)))],
+				// The root community lists its items in its first inventory.
+				inventories: vec![(1, 1)],
 			},
 		});
 
@@ -139,6 +139,7 @@ pub type Migrations = (
 	cumulus_pallet_aura_ext::migration::MigrateV0ToV1<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v5::MigrateV4ToV5<Runtime>,
+	pallet_communities_manager::migrations::MigrateV0ToV1<Runtime>,
 	// Permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -212,6 +213,8 @@ mod runtime {
 	pub type ParachainInfo = parachain_info;
 	#[runtime::pallet_index(4)]
 	pub type Origins = pallet_custom_origins;
+	#[runtime::pallet_index(5)]
+	pub type RelayRandomness = pallet_relay_randomness;
 	#[runtime::pallet_index(6)]
 	pub type Pass = pallet_pass;
 
@@ -292,6 +295,14 @@ mod runtime {
 	pub type Orders = pallet_orders<Instance1>;
 	#[runtime::pallet_index(64)]
 	pub type PaymentIndices = pallet_payment_indices;
+	#[runtime::pallet_index(65)]
+	pub type PaymentFees = pallet_payment_fees;
+	#[runtime::pallet_index(66)]
+	pub type PaymentDisputes = pallet_payment_disputes;
+	#[runtime::pallet_index(67)]
+	pub type RecurringPayments = pallet_recurring_payments;
+	#[runtime::pallet_index(68)]
+	pub type PaymentMilestones = pallet_payment_milestones;
 
 	// Communities at Kreivo
 	#[runtime::pallet_index(71)]
@@ -1,13 +1,20 @@
 use super::{
-	config::{communities::memberships::CommunityMembershipsInstance, system::CommunityLookup, TreasuryAccount},
+	config::{
+		communities::{memberships::CommunityMembershipsInstance, SettleThroughPayments},
+		payments::KreivoFeeHandler,
+		system::CommunityLookup,
+		TreasuryAccount,
+	},
 	constants::currency::EXISTENTIAL_DEPOSIT,
+	pallet_payment_disputes, pallet_payment_fees, pallet_payment_indices, pallet_payment_milestones,
+	pallet_recurring_payments,
 	xcm_config::*,
-	Balances, Communities, CommunitiesManager, CommunityMemberships, FungibleAssetLocation, Runtime, RuntimeOrigin,
-	CENTS, UNITS,
+	Balances, Communities, CommunitiesManager, CommunityMemberships, FungibleAssetLocation, PaymentFees, Runtime,
+	RuntimeOrigin, System, CENTS, UNITS,
 };
 
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
 	traits::{fungible::Mutate, nonfungibles_v2::Inspect},
 };
 use pallet_communities_manager::TankConfig;
@@ -19,6 +26,11 @@ use sp_io::TestExternalities;
 use sp_runtime::{traits::StaticLookup, BoundedVec};
 use xcm_executor::{WeighedMessage, XcmExecutor};
 
+#[cfg(not(feature = "runtime-benchmarks"))]
+mod chain_extension;
+#[cfg(not(feature = "runtime-benchmarks"))]
+mod precompiles;
+
 macro_rules! assert_call_size {
 	($pallet: ident) => {
 		println!(
@@ -93,6 +105,634 @@ fn runtime_sanity_call_does_not_exceed_1kb() {
 	assert_call_size!(pallet_treasury);
 	// Payments: pallet_payments = 60
 	assert_call_size!(pallet_payments);
+	// PaymentFees: pallet_payment_fees = 65
+	assert_call_size!(pallet_payment_fees);
+	// PaymentDisputes: pallet_payment_disputes = 66
+	assert_call_size!(pallet_payment_disputes);
+	// RecurringPayments: pallet_recurring_payments = 67
+	assert_call_size!(pallet_recurring_payments);
+	// PaymentMilestones: pallet_payment_milestones = 68
+	assert_call_size!(pallet_payment_milestones);
+}
+
+#[test]
+fn payment_ids_are_unique_and_never_wrap() {
+	use pallet_payment_indices::{Error, LastIndex, Pallet as PaymentIndices};
+	use pallet_payments::GeneratePaymentId;
+	use sp_runtime::DispatchError;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		System::set_block_number(10);
+
+		let first = PaymentIndices::<Runtime>::generate(&ALICE, &BOB).expect("ids are available; qed");
+		let second = PaymentIndices::<Runtime>::generate(&ALICE, &BOB).expect("ids are available; qed");
+		assert_eq!((first.block_number(), first.extrinsic_index()), (10, 0));
+		assert_eq!((second.block_number(), second.extrinsic_index()), (10, 1));
+		assert_eq!(
+			PaymentIndices::<Runtime>::next_payment_id(&ALICE, &BOB)
+				.ok()
+				.map(|id| id.extrinsic_index()),
+			Some(2)
+		);
+
+		// Indices start over on every block
+		System::set_block_number(11);
+		let id = PaymentIndices::<Runtime>::generate(&ALICE, &BOB).expect("ids are available; qed");
+		assert_eq!((id.block_number(), id.extrinsic_index()), (11, 0));
+
+		// Ids taken by existing payments are never handed out again
+		let next = PaymentIndices::<Runtime>::next_payment_id(&ALICE, &BOB).expect("ids are available; qed");
+		pallet_payments::PaymentParties::<Runtime>::insert(next, (ALICE, BOB));
+		assert_eq!(
+			PaymentIndices::<Runtime>::next_payment_id(&ALICE, &BOB).map_err(DispatchError::from),
+			Err(Error::<Runtime>::PaymentIdTaken.into())
+		);
+		assert_eq!(PaymentIndices::<Runtime>::generate(&ALICE, &BOB), None);
+
+		// Once the indices of a block are exhausted, no more ids are generated
+		LastIndex::<Runtime>::put((11, u16::MAX));
+		assert_eq!(
+			PaymentIndices::<Runtime>::next_payment_id(&ALICE, &BOB).map_err(DispatchError::from),
+			Err(Error::<Runtime>::IndicesExhausted.into())
+		);
+		assert_eq!(PaymentIndices::<Runtime>::generate(&ALICE, &BOB), None);
+	})
+}
+
+#[test]
+fn memberships_bought_with_assets_are_paid_and_released() {
+	use crate::Assets;
+	use frame_support::traits::fungibles::{Create, Inspect, Mutate as _};
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+		System::set_block_number(1);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 1_000));
+
+		assert_ok!(CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			1,
+			0,
+			CENTS,
+			TankConfig::default(),
+			None,
+		));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Buyers Community".to_vec()),
+			CommunityLookup::unlookup(ALICE),
+			None,
+			None,
+			None,
+		));
+		assert_ok!(CommunitiesManager::set_asset_price(
+			RuntimeOrigin::root(),
+			0,
+			Some((ASSET, 100))
+		));
+
+		assert_ok!(CommunitiesManager::buy_membership(RuntimeOrigin::signed(ALICE), 0, 1));
+		assert_eq!(
+			CommunityMemberships::owner(&0, &0),
+			Some(Communities::community_account(&1))
+		);
+
+		// The payment is released right away: the buyer pays the price plus the
+		// sender fee, and the treasury gets the price along with both fees.
+		assert_eq!(Assets::balance(ASSET, &ALICE), 1_000 - 101);
+		assert_eq!(Assets::balance(ASSET, &TreasuryAccount::get()), 101);
+	})
+}
+
+#[test]
+fn payments_released_to_communities_add_to_their_volume() {
+	use crate::Assets;
+	use frame_support::traits::fungibles::{Create, Mutate as _};
+	use pallet_communities_manager::MembershipPayments;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+		const OTHER_ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(2);
+		System::set_block_number(1);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 1_000));
+
+		assert_ok!(SettleThroughPayments::settle(
+			&ALICE,
+			&Communities::community_account(&1),
+			ASSET,
+			100
+		));
+		assert_eq!(PaymentFees::payments_volume(&1, &ASSET), 100);
+
+		// Payments to anyone else don't count
+		System::set_block_number(2);
+		assert_ok!(SettleThroughPayments::settle(&ALICE, &BOB, ASSET, 100));
+		assert_eq!(PaymentFees::payments_volume(&1, &ASSET), 100);
+
+		// Volumes in other assets are kept apart
+		System::set_block_number(3);
+		assert_ok!(<Assets as Create<_>>::create(OTHER_ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(OTHER_ASSET, &ALICE, 1_000));
+		assert_ok!(SettleThroughPayments::settle(
+			&ALICE,
+			&Communities::community_account(&1),
+			OTHER_ASSET,
+			50
+		));
+		assert_eq!(PaymentFees::payments_volume(&1, &ASSET), 100);
+		assert_eq!(PaymentFees::payments_volume(&1, &OTHER_ASSET), 50);
+	})
+}
+
+#[test]
+fn payment_disputes_are_resolved_by_the_merchant_arbiter() {
+	use super::pallet_custom_origins;
+	use pallet_payment_disputes::{Error, Pallet as PaymentDisputes};
+	use pallet_payments::types::OnPaymentStatusChanged;
+	use virto_common::PaymentId;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		let merchant_community = Communities::community_account(&1);
+		let community_origin = |id| RuntimeOrigin::from(pallet_communities::Origin::<Runtime>::new(id));
+		let arbitration_origin = || RuntimeOrigin::from(pallet_custom_origins::Origin::PaymentArbitration);
+		let payment_id = PaymentId::from(1);
+		pallet_payments::PaymentParties::<Runtime>::insert(payment_id, (ALICE, merchant_community.clone()));
+
+		// Arbitration resolves any dispute, and the treasury gets the fee
+		assert_eq!(
+			PaymentDisputes::<Runtime>::resolver_of(arbitration_origin(), &ALICE, &payment_id),
+			Ok(TreasuryAccount::get())
+		);
+		assert_eq!(
+			PaymentDisputes::<Runtime>::resolver_of(RuntimeOrigin::root(), &merchant_community, &payment_id),
+			Ok(TreasuryAccount::get())
+		);
+
+		// A community only resolves the disputes where it is the merchant
+		assert_eq!(
+			PaymentDisputes::<Runtime>::resolver_of(community_origin(1), &merchant_community, &payment_id),
+			Ok(merchant_community.clone())
+		);
+		assert!(
+			PaymentDisputes::<Runtime>::resolver_of(community_origin(2), &merchant_community, &payment_id).is_err()
+		);
+		assert!(PaymentDisputes::<Runtime>::resolver_of(community_origin(1), &ALICE, &payment_id).is_err());
+		assert!(PaymentDisputes::<Runtime>::resolver_of(RuntimeOrigin::signed(ALICE), &ALICE, &payment_id).is_err());
+
+		// Only the payer escalates a payment, after which only arbitration
+		// resolves its dispute
+		assert_noop!(
+			PaymentDisputes::<Runtime>::escalate_dispute(RuntimeOrigin::signed(merchant_community.clone()), payment_id),
+			Error::<Runtime>::NotPayer
+		);
+		assert_noop!(
+			PaymentDisputes::<Runtime>::escalate_dispute(RuntimeOrigin::signed(ALICE), PaymentId::from(2)),
+			Error::<Runtime>::UnknownPayment
+		);
+		assert_ok!(PaymentDisputes::<Runtime>::escalate_dispute(
+			RuntimeOrigin::signed(ALICE),
+			payment_id
+		));
+		assert!(
+			PaymentDisputes::<Runtime>::resolver_of(community_origin(1), &merchant_community, &payment_id).is_err()
+		);
+		assert_eq!(
+			PaymentDisputes::<Runtime>::resolver_of(arbitration_origin(), &merchant_community, &payment_id),
+			Ok(TreasuryAccount::get())
+		);
+
+		// The escalation is forgotten once the payment is settled
+		<Runtime as pallet_payments::Config>::OnPaymentStatusChanged::on_payment_cancelled(&payment_id);
+		assert!(!pallet_payment_disputes::EscalatedPayments::<Runtime>::contains_key(
+			payment_id
+		));
+	})
+}
+
+#[test]
+fn payment_fees_follow_the_fee_schedules() {
+	use pallet_payment_fees::FeeSchedule;
+	use pallet_payments::types::FeeHandler;
+	use sp_runtime::Percent;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		let asset = FungibleAssetLocation::Here(1);
+		let community_account = Communities::community_account(&1);
+		let fees_of = |sender, beneficiary| {
+			let fees = KreivoFeeHandler::apply_fees(&asset, sender, beneficiary, &1_000, None);
+			(fees.sender_pays.to_vec(), fees.beneficiary_pays.to_vec())
+		};
+
+		// The default schedule charges both parties, and exempts communities
+		assert_eq!(
+			fees_of(&ALICE, &BOB),
+			(
+				vec![(TreasuryAccount::get(), 10, true)],
+				vec![(TreasuryAccount::get(), 30, true)]
+			)
+		);
+		assert_eq!(
+			fees_of(&ALICE, &community_account),
+			(vec![(TreasuryAccount::get(), 10, true)], vec![])
+		);
+
+		// A community can charge its own fee to fund itself, up to a maximum, but
+		// not change the fees paid to the treasury
+		assert_noop!(
+			PaymentFees::set_community_fee(
+				RuntimeOrigin::signed(community_account.clone()),
+				Some(Percent::from_percent(11))
+			),
+			pallet_payment_fees::Error::<Runtime>::CommunityFeeTooHigh
+		);
+		assert_ok!(PaymentFees::set_community_fee(
+			RuntimeOrigin::signed(community_account.clone()),
+			Some(Percent::from_percent(5))
+		));
+		assert_eq!(
+			fees_of(&ALICE, &community_account),
+			(
+				vec![
+					(TreasuryAccount::get(), 10, true),
+					(community_account.clone(), 50, true)
+				],
+				vec![]
+			)
+		);
+
+		// Governance sets the schedule for everyone else
+		assert_ok!(PaymentFees::set_global_schedule(
+			RuntimeOrigin::root(),
+			FeeSchedule {
+				sender_fee: Percent::from_percent(0),
+				beneficiary_fee: Percent::from_percent(2),
+				community_fee: Percent::from_percent(0),
+			}
+		));
+		assert_eq!(
+			fees_of(&ALICE, &BOB),
+			(vec![], vec![(TreasuryAccount::get(), 20, true)])
+		);
+	})
+}
+
+#[test]
+fn recurring_payments_are_scheduled_until_cancelled() {
+	use crate::config::payments::RecurringPaymentHoldReason;
+	use frame_support::traits::fungible::InspectHold;
+	use pallet_recurring_payments::{Error, Pallet as RecurringPayments, PayerRecurringPayments};
+	use sp_runtime::DispatchError;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		let held = || Balances::balance_on_hold(&RecurringPaymentHoldReason::get(), &ALICE);
+		let asset = FungibleAssetLocation::Here(1);
+		let authorize = |amount, period, max_payments| {
+			RecurringPayments::<Runtime>::authorize(
+				RuntimeOrigin::signed(ALICE),
+				CommunityLookup::unlookup(BOB),
+				asset,
+				amount,
+				period,
+				max_payments,
+			)
+		};
+		let is_scheduled =
+			|id| pallet_scheduler::Lookup::<Runtime>::contains_key(RecurringPayments::<Runtime>::task_name(id));
+
+		assert_eq!(
+			authorize(0, WEEKS, 4),
+			Err(Error::<Runtime>::InvalidRecurringPayment.into())
+		);
+		assert_eq!(
+			authorize(CENTS, 0, 4),
+			Err(Error::<Runtime>::InvalidRecurringPayment.into())
+		);
+		assert_eq!(
+			authorize(CENTS, WEEKS, 0),
+			Err(Error::<Runtime>::InvalidRecurringPayment.into())
+		);
+
+		assert_ok!(authorize(CENTS, WEEKS, 2));
+		assert!(is_scheduled(0));
+		assert_eq!(PayerRecurringPayments::<Runtime>::get(&ALICE).to_vec(), vec![0]);
+		// The payer holds a deposit while the recurring payment exists
+		let deposit = held();
+		assert!(deposit > 0);
+
+		// Only the scheduler makes the payment of every cycle, which counts
+		// even when the payer can't afford it
+		assert_eq!(
+			RecurringPayments::<Runtime>::charge(RuntimeOrigin::signed(ALICE), 0),
+			Err(DispatchError::BadOrigin)
+		);
+		assert_ok!(RecurringPayments::<Runtime>::charge(RuntimeOrigin::root(), 0));
+		assert_eq!(
+			pallet_recurring_payments::RecurringPayments::<Runtime>::get(0).map(|p| p.remaining),
+			Some(1)
+		);
+		assert_ok!(RecurringPayments::<Runtime>::charge(RuntimeOrigin::root(), 0));
+		assert!(pallet_recurring_payments::RecurringPayments::<Runtime>::get(0).is_none());
+		assert_eq!(held(), 0);
+
+		// Only the payer can cancel a recurring payment
+		assert_ok!(authorize(CENTS, WEEKS, 4));
+		assert_eq!(held(), deposit);
+		assert_eq!(
+			RecurringPayments::<Runtime>::cancel(RuntimeOrigin::signed(BOB), 1),
+			Err(Error::<Runtime>::UnknownRecurringPayment.into())
+		);
+		assert_ok!(RecurringPayments::<Runtime>::cancel(RuntimeOrigin::signed(ALICE), 1));
+		assert!(!is_scheduled(1));
+		assert!(PayerRecurringPayments::<Runtime>::get(&ALICE).is_empty());
+		assert_eq!(held(), 0);
+	})
+}
+
+#[test]
+fn recurring_payments_pay_the_beneficiary_every_cycle() {
+	use crate::{config::payments::RecurringPaymentHoldReason, Assets, PaymentIndices};
+	use frame_support::traits::{
+		fungible::InspectHold,
+		fungibles::{Create, Mutate as _},
+	};
+	use pallet_recurring_payments::{Event, Pallet as RecurringPayments};
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+		System::set_block_number(1);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 1_000));
+
+		assert_ok!(RecurringPayments::<Runtime>::authorize(
+			RuntimeOrigin::signed(ALICE),
+			CommunityLookup::unlookup(BOB),
+			ASSET,
+			100,
+			WEEKS,
+			2,
+		));
+
+		// Every cycle is paid and released right away: the payer pays the
+		// amount plus the sender fee, and the treasury gets both fees
+		for cycle in 1..=2 {
+			System::set_block_number(cycle);
+			assert_ok!(RecurringPayments::<Runtime>::charge(RuntimeOrigin::root(), 0));
+			let payment_id = PaymentIndices::last_payment_id(&ALICE, &BOB).expect("the payment was made; qed");
+			System::assert_has_event(Event::<Runtime>::Charged { id: 0, payment_id }.into());
+
+			assert_eq!(Assets::balance(ASSET, &ALICE), 1_000 - 101 * cycle as u128);
+			assert_eq!(Assets::balance(ASSET, &BOB), 97 * cycle as u128);
+			assert_eq!(Assets::balance(ASSET, &TreasuryAccount::get()), 4 * cycle as u128);
+		}
+		System::assert_has_event(Event::<Runtime>::Completed { id: 0 }.into());
+		assert_eq!(Balances::balance_on_hold(&RecurringPaymentHoldReason::get(), &ALICE), 0);
+	})
+}
+
+#[test]
+fn payment_milestones_follow_their_payments() {
+	use pallet_payment_milestones::{
+		Error, Escrow, Escrows, Event, Milestone, MilestoneStatus, OrderEscrow, Pallet as PaymentMilestones,
+		PaymentMilestone,
+	};
+	use pallet_payments::types::OnPaymentStatusChanged;
+	use virto_common::PaymentId;
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		const ORDER: u64 = 7;
+		System::set_block_number(1);
+		let milestone = |n: u64| Milestone {
+			name: BoundedVec::truncate_from(b"milestone".to_vec()),
+			amount: CENTS,
+			payment_id: PaymentId::from(n),
+			status: MilestoneStatus::Pending,
+		};
+		let status_of = |m: usize| Escrows::<Runtime>::get(0).map(|e| e.milestones[m].status);
+
+		assert_eq!(
+			PaymentMilestones::<Runtime>::create(
+				RuntimeOrigin::signed(ALICE),
+				CommunityLookup::unlookup(BOB),
+				FungibleAssetLocation::Here(1),
+				BoundedVec::new(),
+				None,
+			),
+			Err(Error::<Runtime>::NoMilestones.into())
+		);
+		// Escrows only pay for existing orders
+		assert_eq!(
+			PaymentMilestones::<Runtime>::create(
+				RuntimeOrigin::signed(ALICE),
+				CommunityLookup::unlookup(BOB),
+				FungibleAssetLocation::Here(1),
+				BoundedVec::truncate_from(vec![(BoundedVec::truncate_from(b"milestone".to_vec()), CENTS)]),
+				Some(ORDER),
+			),
+			Err(Error::<Runtime>::InvalidOrder.into())
+		);
+
+		Escrows::<Runtime>::insert(
+			0,
+			Escrow {
+				payer: ALICE,
+				beneficiary: BOB,
+				asset: FungibleAssetLocation::Here(1),
+				milestones: BoundedVec::truncate_from(vec![milestone(1), milestone(2)]),
+				order: Some(ORDER),
+			},
+		);
+		OrderEscrow::<Runtime>::insert(ORDER, 0);
+		PaymentMilestone::<Runtime>::insert(PaymentId::from(1), (0, 0));
+		PaymentMilestone::<Runtime>::insert(PaymentId::from(2), (0, 1));
+
+		// Only the beneficiary delivers, and only the payer approves deliveries
+		assert_eq!(
+			PaymentMilestones::<Runtime>::approve(RuntimeOrigin::signed(ALICE), 0, 0),
+			Err(Error::<Runtime>::InvalidMilestoneStatus.into())
+		);
+		assert_eq!(
+			PaymentMilestones::<Runtime>::deliver(RuntimeOrigin::signed(ALICE), 0, 0),
+			Err(Error::<Runtime>::UnknownMilestone.into())
+		);
+		assert_ok!(PaymentMilestones::<Runtime>::deliver(RuntimeOrigin::signed(BOB), 0, 0));
+		assert_eq!(status_of(0), Some(MilestoneStatus::Delivered));
+		assert_eq!(
+			PaymentMilestones::<Runtime>::deliver(RuntimeOrigin::signed(BOB), 0, 2),
+			Err(Error::<Runtime>::UnknownMilestone.into())
+		);
+
+		// Milestones are settled by the payments holding their funds, e.g.
+		// once a dispute is resolved, and the order follows their progress
+		PaymentMilestones::<Runtime>::on_payment_released(&PaymentId::from(1));
+		assert_eq!(status_of(0), Some(MilestoneStatus::Released));
+		assert_eq!(status_of(1), Some(MilestoneStatus::Pending));
+		assert_eq!(PaymentMilestones::<Runtime>::order_progress(&ORDER), Some((1, 2)));
+		System::assert_has_event(
+			Event::<Runtime>::OrderProgressed {
+				order: ORDER,
+				settled: 1,
+				total: 2,
+			}
+			.into(),
+		);
+
+		PaymentMilestones::<Runtime>::on_payment_cancelled(&PaymentId::from(2));
+		assert!(Escrows::<Runtime>::get(0).is_none());
+		assert!(PaymentMilestone::<Runtime>::iter().next().is_none());
+		assert_eq!(PaymentMilestones::<Runtime>::order_progress(&ORDER), None);
+		System::assert_last_event(
+			Event::<Runtime>::OrderProgressed {
+				order: ORDER,
+				settled: 2,
+				total: 2,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn payment_milestones_are_paid_through_payments() {
+	use crate::{Assets, Payments};
+	use frame_support::traits::fungibles::{Create, Mutate as _};
+	use pallet_payment_milestones::{Escrows, MilestoneStatus, Pallet as PaymentMilestones, PaymentMilestone};
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+		System::set_block_number(1);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 1_000));
+		let name = |name: &[u8]| BoundedVec::truncate_from(name.to_vec());
+
+		// The funds of every milestone are held in a payment of its own
+		assert_ok!(PaymentMilestones::<Runtime>::create(
+			RuntimeOrigin::signed(ALICE),
+			CommunityLookup::unlookup(BOB),
+			ASSET,
+			BoundedVec::truncate_from(vec![(name(b"design"), 100), (name(b"build"), 200)]),
+			None,
+		));
+		let escrow = Escrows::<Runtime>::get(0).expect("the escrow was created; qed");
+		let (design, build) = (escrow.milestones[0].payment_id, escrow.milestones[1].payment_id);
+		assert_ne!(design, build);
+		assert_eq!(PaymentMilestone::<Runtime>::get(design), Some((0, 0)));
+		assert_eq!(PaymentMilestone::<Runtime>::get(build), Some((0, 1)));
+		assert_eq!(
+			pallet_payments::PaymentParties::<Runtime>::try_get(build).ok(),
+			Some((ALICE, BOB))
+		);
+		assert_eq!(Assets::balance(ASSET, &BOB), 0);
+		let status_of = |m: usize| Escrows::<Runtime>::get(0).map(|e| e.milestones[m].status);
+
+		// Approving a delivered milestone releases its payment
+		assert_ok!(PaymentMilestones::<Runtime>::deliver(RuntimeOrigin::signed(BOB), 0, 0));
+		assert_ok!(PaymentMilestones::<Runtime>::approve(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			0
+		));
+		assert_eq!(status_of(0), Some(MilestoneStatus::Released));
+		assert_eq!(Assets::balance(ASSET, &BOB), 97);
+
+		// Disputing a delivered milestone requests the refund of its payment,
+		// which the beneficiary can accept
+		assert_ok!(PaymentMilestones::<Runtime>::deliver(RuntimeOrigin::signed(BOB), 0, 1));
+		assert_ok!(PaymentMilestones::<Runtime>::dispute(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			1
+		));
+		assert_eq!(status_of(1), Some(MilestoneStatus::Disputed));
+		assert_eq!(Assets::balance(ASSET, &BOB), 97);
+
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(BOB), build));
+		assert!(Escrows::<Runtime>::get(0).is_none());
+		assert_eq!(Assets::balance(ASSET, &ALICE), 1_000 - 101);
+		assert_eq!(Assets::balance(ASSET, &BOB), 97);
+	})
+}
+
+#[test]
+fn payment_milestones_pay_for_orders_from_the_buyer_to_the_merchant() {
+	use crate::Assets;
+	use frame_support::traits::fungibles::{Create, Mutate as _};
+	use pallet_listings::{Instance1 as ListingsInstance, InventoryId};
+	use pallet_payment_milestones::{Error, Escrows, OrderEscrow, Pallet as PaymentMilestones, PaymentMilestone};
+
+	TestExternalities::default().execute_with(|| {
+		const ALICE: AccountId32 = AccountId32::new([1; 32]);
+		const BOB: AccountId32 = AccountId32::new([2; 32]);
+		const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+		const ORDER: u64 = 7;
+		System::set_block_number(1);
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 1_000));
+		let merchant = Communities::community_account(&1);
+		pallet_orders::Order::<Runtime, ListingsInstance>::insert(
+			ORDER,
+			pallet_orders::types::OrderDetails {
+				owner: ALICE,
+				status: Default::default(),
+				items: BoundedVec::truncate_from(vec![(InventoryId(1, 0), 0)]),
+			},
+		);
+		let create = |payer: &AccountId32, beneficiary: &AccountId32| {
+			PaymentMilestones::<Runtime>::create(
+				RuntimeOrigin::signed(payer.clone()),
+				CommunityLookup::unlookup(beneficiary.clone()),
+				ASSET,
+				BoundedVec::truncate_from(vec![(BoundedVec::truncate_from(b"milestone".to_vec()), 100)]),
+				Some(ORDER),
+			)
+		};
+
+		// Only the buyer pays for an order, and only to its merchant
+		assert_noop!(create(&ALICE, &BOB), Error::<Runtime>::NotOrderParty);
+		assert_noop!(create(&BOB, &merchant), Error::<Runtime>::NotOrderParty);
+		assert_ok!(create(&ALICE, &merchant));
+		assert_eq!(OrderEscrow::<Runtime>::get(ORDER), Some(0));
+
+		// Approving the last milestone settles the escrow through its payment
+		assert_ok!(PaymentMilestones::<Runtime>::deliver(
+			RuntimeOrigin::signed(merchant.clone()),
+			0,
+			0
+		));
+		assert_ok!(PaymentMilestones::<Runtime>::approve(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			0
+		));
+		assert!(Escrows::<Runtime>::get(0).is_none());
+		assert!(OrderEscrow::<Runtime>::get(ORDER).is_none());
+		assert!(PaymentMilestone::<Runtime>::iter().next().is_none());
+		assert_eq!(Assets::balance(ASSET, &merchant), 97);
+	})
 }
 
 #[test]
@@ -126,7 +766,8 @@ fn ensure_copying_membership_attributes_works() {
 			1,
 			BoundedVec::try_from(b"First Community".to_vec()).expect("meets max length; qed"),
 			CommunityLookup::unlookup(ALICE),
-			// Use default values for decision method and track info
+			// Use default values for decision method and track info, without a token
+			None,
 			None,
 			None,
 		));
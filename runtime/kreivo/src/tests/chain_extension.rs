@@ -0,0 +1,568 @@
+//! Integration tests for the Kreivo APIs chain extension, using the
+//! `kreivo_apis` fixture: a contract that forwards its input to the chain
+//! extension, and returns the status code along with the output of the call.
+
+use super::*;
+
+use crate::{config::communities::Memberships, Assets, Contracts, ContractsStore, Listings};
+use frame_contrib_traits::{
+	listings::{item::Item, InspectInventory, InspectItem},
+	memberships::Inspect as _,
+};
+use frame_support::{
+	traits::fungibles::{Create, Inspect as _, Mutate as _},
+	weights::Weight,
+};
+use kreivo_apis::apis::{
+	AssetsApiError, KreivoApisError, KreivoApisErrorCode, ListingsApiError, MembershipsApiError, RandomnessApiError,
+};
+use pallet_contracts::{Code, CollectEvents, DebugInfo, Determinism};
+use pallet_contracts_fixtures::compile_module;
+use pallet_contracts_store::ContractAccount;
+use sp_runtime::traits::Zero;
+use virto_common::{listings::*, CommunityId};
+
+const ALICE: AccountId32 = AccountId32::new([1; 32]);
+const BOB: AccountId32 = AccountId32::new([2; 32]);
+
+const COMMUNITY: CommunityId = 1;
+const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+const APP_ID: InventoryId = 0;
+const LICENSE_ID: ItemId = 0;
+const INVENTORY_ID: InventoryId = 1;
+const ITEM_ID: ItemId = 1;
+
+const FIXTURE: &str = "kreivo_apis";
+
+fn community_origin() -> RuntimeOrigin {
+	pallet_communities::Origin::<Runtime>::new(COMMUNITY).into()
+}
+
+fn new_test_ext() -> TestExternalities {
+	let mut t = TestExternalities::default();
+	t.execute_with(|| {
+		assert_ok!(Balances::mint_into(&ALICE, UNITS));
+		assert_ok!(Balances::mint_into(&BOB, UNITS));
+		assert_ok!(Balances::mint_into(
+			&Communities::community_account(&COMMUNITY),
+			100 * UNITS
+		));
+
+		// Create some memberships, and register a community that owns one.
+		assert_ok!(CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			10,
+			0,
+			CENTS,
+			TankConfig::default(),
+			Some(8 * WEEKS),
+		));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			COMMUNITY,
+			BoundedVec::try_from(b"Contracts Community".to_vec()).expect("meets max length; qed"),
+			CommunityLookup::unlookup(ALICE),
+			None,
+			None,
+			None,
+		));
+		assert_ok!(Communities::dispatch_as_account(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(
+				pallet_nfts::Call::<Runtime, CommunityMembershipsInstance>::buy_item {
+					collection: 0,
+					item: 0,
+					bid_price: CENTS
+				}
+				.into()
+			)
+		));
+
+		// Create an asset, so the contract can hold some balance of it.
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 100));
+
+		// Publish the fixture, and instantiate it as the community.
+		let (code, _) = compile_module::<Runtime>(FIXTURE).expect("fixture is compiled; qed");
+		assert_ok!(ContractsStore::publish(community_origin(), code, None, None));
+		assert_ok!(ContractsStore::request_license(community_origin(), APP_ID));
+		assert_ok!(ContractsStore::instantiate(
+			community_origin(),
+			APP_ID,
+			LICENSE_ID,
+			Zero::zero(),
+			vec![],
+			vec![]
+		));
+
+		// The contract covers the deposits of the inventories and items it creates.
+		assert_ok!(Balances::mint_into(&contract(), 100 * UNITS));
+	});
+	t
+}
+
+fn contract() -> AccountId {
+	ContractAccount::<Runtime>::get((APP_ID, LICENSE_ID)).expect("the contract was instantiated; qed")
+}
+
+/// Calls `func_id` on the given contract, returning the status code and the
+/// raw output of the call.
+fn call_raw(contract: &AccountId, func_id: u16, input: Vec<u8>) -> (u32, Vec<u8>) {
+	let data = [(func_id as u32).to_le_bytes().to_vec(), input].concat();
+	let result = Contracts::bare_call(
+		ALICE,
+		contract.clone(),
+		Zero::zero(),
+		Weight::MAX,
+		None,
+		data,
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+		Determinism::Enforced,
+	)
+	.result
+	.expect("the chain extension does not trap; qed");
+
+	let (status, output) = result.data.split_at(4);
+	(
+		u32::from_le_bytes(status.try_into().expect("status is 4 bytes long; qed")),
+		output.to_vec(),
+	)
+}
+
+/// The weight consumed by calling `func_id` on the community contract.
+fn gas_consumed(func_id: u16, input: impl Encode) -> Weight {
+	let data = [(func_id as u32).to_le_bytes().to_vec(), input.encode()].concat();
+	Contracts::bare_call(
+		ALICE,
+		contract(),
+		Zero::zero(),
+		Weight::MAX,
+		None,
+		data,
+		DebugInfo::Skip,
+		CollectEvents::Skip,
+		Determinism::Enforced,
+	)
+	.gas_consumed
+}
+
+fn call(func_id: u16, input: impl Encode) -> (u32, Vec<u8>) {
+	call_raw(&contract(), func_id, input.encode())
+}
+
+/// Asserts the call succeeds, returning its output.
+fn call_ok(func_id: u16, input: impl Encode) -> Vec<u8> {
+	let (status, output) = call(func_id, input);
+	assert_eq!(status, 0, "call to {func_id:#06x} failed");
+	output
+}
+
+fn error_code(error: impl Into<KreivoApisError>) -> u32 {
+	KreivoApisErrorCode::from(error.into()).into()
+}
+
+mod assets {
+	use super::*;
+
+	#[test]
+	fn balance_works() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(call_ok(0x0000, (ASSET, ALICE)), 100u128.encode());
+			assert_eq!(call_ok(0x0000, (ASSET, BOB)), 0u128.encode());
+		})
+	}
+
+	#[test]
+	fn deposit_works() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(call_ok(0x0001, (ASSET, 10u128)), 10u128.encode());
+			assert_eq!(Assets::balance(ASSET, ALICE), 90);
+			assert_eq!(Assets::balance(ASSET, contract()), 10);
+
+			assert_eq!(
+				call(0x0001, (ASSET, 1_000u128)).0,
+				error_code(AssetsApiError::CannotDeposit)
+			);
+		})
+	}
+
+	#[test]
+	fn transfer_works() {
+		new_test_ext().execute_with(|| {
+			call_ok(0x0001, (ASSET, 10u128));
+
+			assert_eq!(call_ok(0x0002, (ASSET, 4u128, BOB)), 4u128.encode());
+			assert_eq!(Assets::balance(ASSET, contract()), 6);
+			assert_eq!(Assets::balance(ASSET, BOB), 4);
+
+			assert_eq!(
+				call(0x0002, (ASSET, 1_000u128, BOB)).0,
+				error_code(AssetsApiError::CannotTransfer)
+			);
+		})
+	}
+}
+
+mod listings {
+	use super::*;
+
+	fn inventory() -> (CommunityId, InventoryId) {
+		(COMMUNITY, INVENTORY_ID)
+	}
+
+	#[test]
+	fn inventories_lifecycle_works() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(call_ok(0x0100, INVENTORY_ID), false.encode());
+
+			call_ok(0x0103, INVENTORY_ID);
+			assert!(Listings::exists(&inventory()));
+			assert_eq!(call_ok(0x0100, INVENTORY_ID), true.encode());
+			assert_eq!(call_ok(0x0101, INVENTORY_ID), true.encode());
+
+			call_ok(0x0105, (INVENTORY_ID, b"key".to_vec(), b"value".to_vec()));
+			assert_eq!(
+				call_ok(0x0102, (INVENTORY_ID, b"key".to_vec())),
+				Some(b"value".to_vec()).encode()
+			);
+			call_ok(0x0106, (INVENTORY_ID, b"key".to_vec()));
+			assert_eq!(
+				call_ok(0x0102, (INVENTORY_ID, b"key".to_vec())),
+				None::<Vec<u8>>.encode()
+			);
+
+			call_ok(0x0107, (INVENTORY_ID, b"metadata".to_vec()));
+			call_ok(0x0108, INVENTORY_ID);
+
+			call_ok(0x0104, INVENTORY_ID);
+			assert!(!Listings::is_active(&inventory()));
+			assert_eq!(call_ok(0x0101, INVENTORY_ID), false.encode());
+			assert_eq!(
+				call(0x0104, INVENTORY_ID).0,
+				error_code(ListingsApiError::ArchivedInventory)
+			);
+		})
+	}
+
+	#[test]
+	fn items_lifecycle_works() {
+		new_test_ext().execute_with(|| {
+			call_ok(0x0103, INVENTORY_ID);
+			call_ok(
+				0x0114,
+				(
+					INVENTORY_ID,
+					ITEM_ID,
+					b"Item".to_vec(),
+					None::<(FungibleAssetLocation, u128)>,
+				),
+			);
+			assert_eq!(
+				call_ok(0x0110, (INVENTORY_ID, ITEM_ID)),
+				Listings::item(&inventory(), &ITEM_ID).encode()
+			);
+
+			call_ok(0x0117, (INVENTORY_ID, ITEM_ID));
+			assert!(Listings::can_resell(&inventory(), &ITEM_ID));
+			assert_eq!(call_ok(0x0113, (INVENTORY_ID, ITEM_ID)), true.encode());
+			call_ok(0x0118, (INVENTORY_ID, ITEM_ID));
+			assert!(!Listings::can_resell(&inventory(), &ITEM_ID));
+
+			call_ok(0x011a, (INVENTORY_ID, ITEM_ID));
+			assert!(!Listings::transferable(&inventory(), &ITEM_ID));
+			assert_eq!(call_ok(0x0112, (INVENTORY_ID, ITEM_ID)), false.encode());
+			call_ok(0x0119, (INVENTORY_ID, ITEM_ID));
+			assert!(Listings::transferable(&inventory(), &ITEM_ID));
+
+			call_ok(0x011b, (INVENTORY_ID, ITEM_ID, b"key".to_vec(), b"value".to_vec()));
+			assert_eq!(
+				call_ok(0x0111, (INVENTORY_ID, ITEM_ID, b"key".to_vec())),
+				Some(b"value".to_vec()).encode()
+			);
+			call_ok(0x011c, (INVENTORY_ID, ITEM_ID, b"key".to_vec()));
+			assert_eq!(
+				call_ok(0x0111, (INVENTORY_ID, ITEM_ID, b"key".to_vec())),
+				None::<Vec<u8>>.encode()
+			);
+
+			call_ok(0x011f, (INVENTORY_ID, ITEM_ID, b"metadata".to_vec()));
+			call_ok(0x0120, (INVENTORY_ID, ITEM_ID));
+
+			call_ok(0x0115, (INVENTORY_ID, ITEM_ID, (ASSET, 10u128)));
+			call_ok(0x0116, (INVENTORY_ID, ITEM_ID));
+
+			call_ok(0x011d, (INVENTORY_ID, ITEM_ID, BOB));
+			let Item { owner, .. } = Listings::item(&inventory(), &ITEM_ID).expect("item exists; qed");
+			assert_eq!(owner, BOB);
+
+			call_ok(0x011e, (INVENTORY_ID, ITEM_ID, ALICE));
+			let Item { owner, .. } = Listings::item(&inventory(), &ITEM_ID).expect("item exists; qed");
+			assert_eq!(owner, ALICE);
+		})
+	}
+
+	#[test]
+	fn unknown_items_return_error_codes() {
+		new_test_ext().execute_with(|| {
+			call_ok(0x0103, INVENTORY_ID);
+
+			assert_eq!(call_ok(0x0110, (INVENTORY_ID, ITEM_ID)), None::<()>.encode());
+			assert_eq!(
+				call(0x011d, (INVENTORY_ID, ITEM_ID, BOB)).0,
+				error_code(ListingsApiError::CannotTransfer)
+			);
+		})
+	}
+}
+
+mod memberships {
+	use super::*;
+
+	#[test]
+	fn memberships_work() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(call_ok(0x0201, BOB), None::<()>.encode());
+
+			call_ok(0x0200, BOB);
+			assert!(Memberships::is_member_of(&COMMUNITY, &BOB));
+			let membership = call_ok(0x0201, BOB);
+			assert_eq!(membership, Some(0u32).encode());
+			assert_ne!(call_ok(0x0202, 0u32), None::<()>.encode());
+
+			call_ok(0x0204, (0u32, b"key".to_vec(), b"value".to_vec()));
+			assert_eq!(
+				call_ok(0x0203, (0u32, b"key".to_vec())),
+				Some(b"value".to_vec()).encode()
+			);
+			assert_eq!(call_ok(0x0206, (BOB, b"key".to_vec(), b"value".to_vec())), membership);
+
+			call_ok(0x0205, (0u32, b"key".to_vec()));
+			assert_eq!(call_ok(0x0203, (0u32, b"key".to_vec())), None::<Vec<u8>>.encode());
+			assert_eq!(
+				call_ok(0x0206, (BOB, b"key".to_vec(), b"value".to_vec())),
+				None::<()>.encode()
+			);
+
+			// There are no more available memberships.
+			assert_eq!(call(0x0200, ALICE).0, error_code(MembershipsApiError::CannotAddMember));
+		})
+	}
+}
+
+mod randomness {
+	use super::*;
+	use crate::{config::pallet_relay_randomness::EpochRandomness, RelayRandomness};
+	use cumulus_pallet_parachain_system::{OnSystemEvent, RelayStateProof};
+	use cumulus_primitives_core::{relay_chain::well_known_keys, PersistedValidationData};
+	use frame_support::traits::Randomness;
+	use sp_core::{storage::StateVersion, H256};
+
+	/// Includes a relay chain state proof holding the randomness of the
+	/// previous epoch, as the `set_validation_data` inherent does.
+	fn include_relay_randomness(randomness: [u8; 32]) {
+		let mut relay_state = TestExternalities::default();
+		relay_state.execute_with(|| sp_io::storage::set(well_known_keys::ONE_EPOCH_AGO_RANDOMNESS, &randomness));
+		relay_state.commit_all().expect("relay state is committed; qed");
+		let root = relay_state.execute_with(|| sp_io::storage::root(StateVersion::V1));
+		let (_, proof) =
+			relay_state.execute_and_prove(|| sp_io::storage::get(well_known_keys::ONE_EPOCH_AGO_RANDOMNESS));
+
+		RelayStateProof::<Runtime>::put(proof);
+		<Runtime as cumulus_pallet_parachain_system::Config>::OnSystemEvent::on_validation_data(
+			&PersistedValidationData {
+				relay_parent_storage_root: H256::from_slice(&root),
+				..Default::default()
+			},
+		);
+	}
+
+	#[test]
+	fn random_fails_before_the_first_seed() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				call(0x0300, b"subject".to_vec()).0,
+				error_code(RandomnessApiError::NotAvailableYet)
+			);
+		})
+	}
+
+	#[test]
+	fn random_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(10);
+			include_relay_randomness([7u8; 32]);
+			assert_eq!(EpochRandomness::<Runtime>::get(), Some(([7u8; 32], 10)));
+
+			let (random, known_since) = RelayRandomness::random(b"subject");
+			assert_eq!(known_since, 10);
+			assert_eq!(call_ok(0x0300, b"subject".to_vec()), (random, known_since).encode());
+			assert_ne!(
+				call_ok(0x0300, b"other subject".to_vec()),
+				(random, known_since).encode()
+			);
+
+			// The seed is only updated when the relay chain epoch changes.
+			System::set_block_number(11);
+			include_relay_randomness([7u8; 32]);
+			assert_eq!(EpochRandomness::<Runtime>::get(), Some(([7u8; 32], 10)));
+			include_relay_randomness([8u8; 32]);
+			assert_eq!(EpochRandomness::<Runtime>::get(), Some(([8u8; 32], 11)));
+		})
+	}
+
+	#[test]
+	fn long_subjects_are_rejected() {
+		new_test_ext().execute_with(|| {
+			include_relay_randomness([7u8; 32]);
+			assert_eq!(
+				call(0x0300, vec![0u8; 257]).0,
+				error_code(KreivoApisError::InvalidInput)
+			);
+		})
+	}
+}
+
+mod batch {
+	use super::*;
+
+	#[test]
+	fn batch_works() {
+		new_test_ext().execute_with(|| {
+			let calls = vec![
+				(0x0001u16, (ASSET, 10u128).encode()),
+				(0x0002u16, (ASSET, 4u128, BOB).encode()),
+			];
+			assert_eq!(call_ok(0xff00, calls), vec![10u128.encode(), 4u128.encode()].encode());
+			assert_eq!(Assets::balance(ASSET, contract()), 6);
+			assert_eq!(Assets::balance(ASSET, BOB), 4);
+		})
+	}
+
+	#[test]
+	fn failed_batch_reverts_every_call() {
+		new_test_ext().execute_with(|| {
+			let calls = vec![
+				(0x0001u16, (ASSET, 10u128).encode()),
+				(0x0002u16, (ASSET, 1_000u128, BOB).encode()),
+			];
+			assert_eq!(call(0xff00, calls).0, error_code(AssetsApiError::CannotTransfer));
+			assert_eq!(Assets::balance(ASSET, ALICE), 100);
+			assert!(Assets::balance(ASSET, contract()).is_zero());
+		})
+	}
+
+	#[test]
+	fn batched_calls_are_charged_per_call() {
+		use crate::config::contracts::KreivoApisCallWeight;
+		use sp_runtime::traits::Convert;
+
+		new_test_ext().execute_with(|| {
+			let balance = (0x0000u16, (ASSET, ALICE).encode());
+			let single = gas_consumed(0x0000, (ASSET, ALICE));
+			let batch = gas_consumed(0xff00, vec![balance.clone(), balance]);
+
+			let call_weight = KreivoApisCallWeight::convert(0x0000);
+			assert!(single.ref_time() >= call_weight.ref_time());
+			assert!(batch.ref_time() >= single.ref_time() + call_weight.ref_time());
+		})
+	}
+
+	#[test]
+	fn batched_calls_are_charged_by_their_dispatch() {
+		use crate::config::contracts::KreivoApisCallWeight;
+		use sp_runtime::traits::Convert;
+
+		new_test_ext().execute_with(|| {
+			let balance = (0x0000u16, (ASSET, ALICE).encode());
+			let transfer = (0x0002u16, (ASSET, 1u128, BOB).encode());
+			let balances = gas_consumed(0xff00, vec![balance.clone(), balance.clone()]);
+			let with_transfer = gas_consumed(0xff00, vec![balance, transfer]);
+
+			let (balance_weight, transfer_weight) = (
+				KreivoApisCallWeight::convert(0x0000),
+				KreivoApisCallWeight::convert(0x0002),
+			);
+			assert!(transfer_weight.ref_time() > balance_weight.ref_time());
+			assert!(
+				with_transfer.ref_time()
+					>= balances.ref_time() + transfer_weight.ref_time() - balance_weight.ref_time()
+			);
+		})
+	}
+
+	#[test]
+	fn oversized_batches_are_rejected() {
+		use crate::config::contracts::MaxBatchedCalls;
+
+		new_test_ext().execute_with(|| {
+			let calls = vec![(0x0000u16, (ASSET, ALICE).encode()); MaxBatchedCalls::get() as usize + 1];
+			assert_eq!(call(0xff00, calls).0, error_code(KreivoApisError::InvalidInput));
+		})
+	}
+}
+
+mod errors {
+	use super::*;
+
+	#[test]
+	fn bad_encodings_return_error_codes() {
+		new_test_ext().execute_with(|| {
+			// Truncated input.
+			assert_eq!(
+				call_raw(&contract(), 0x0002, ASSET.encode()).0,
+				error_code(KreivoApisError::InvalidInput)
+			);
+			// Unknown `func_id`.
+			assert_eq!(
+				call_raw(&contract(), 0x0400, vec![]).0,
+				error_code(KreivoApisError::InvalidInput)
+			);
+			// A batch containing an invalid call.
+			assert_eq!(
+				call(0xff00, vec![(0x0103u16, vec![0u8])]).0,
+				error_code(KreivoApisError::InvalidInput)
+			);
+		})
+	}
+
+	#[test]
+	fn contracts_without_merchant_id_return_error_codes() {
+		new_test_ext().execute_with(|| {
+			// Instantiating the app outside of the `ContractsStore` leaves the contract
+			// without a `MerchantId`.
+			let (_, code_hash) = compile_module::<Runtime>(FIXTURE).expect("fixture is compiled; qed");
+			let unbound = Contracts::bare_instantiate(
+				ALICE,
+				Zero::zero(),
+				Weight::MAX,
+				None,
+				Code::Existing(code_hash),
+				vec![],
+				vec![1],
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			)
+			.result
+			.expect("instantiation works; qed")
+			.account_id;
+
+			assert_eq!(
+				call_raw(&unbound, 0x0103, INVENTORY_ID.encode()).0,
+				error_code(ListingsApiError::NoMerchantId)
+			);
+			assert_eq!(call_raw(&unbound, 0x0100, INVENTORY_ID.encode()).1, false.encode());
+			assert_eq!(
+				call_raw(&unbound, 0x0200, BOB.encode()).0,
+				error_code(MembershipsApiError::NoGroup)
+			);
+
+			// The contract bound to the community can't see the inventories of a
+			// different merchant.
+			call_ok(0x0103, INVENTORY_ID);
+			assert!(Listings::exists(&(COMMUNITY, INVENTORY_ID)));
+			assert!(!Listings::exists(&(COMMUNITY + 1, INVENTORY_ID)));
+			assert_eq!(call_raw(&unbound, 0x0101, INVENTORY_ID.encode()).1, false.encode());
+		})
+	}
+}
@@ -0,0 +1,423 @@
+//! Integration tests for the Kreivo APIs precompiles. Accounts call into the
+//! precompiles directly, which makes them take the place of the contract (i.e.
+//! the merchant or group) calling the precompile, or through a contract bound
+//! to the community.
+
+use super::*;
+
+use crate::{config::communities::Memberships, Assets, ContractsStore, Listings, Revive};
+use frame_contrib_traits::{
+	listings::{item::ItemPrice, InspectItem},
+	memberships::Inspect as _,
+};
+use frame_support::{
+	traits::fungibles::{Create, Inspect as _, Mutate as _},
+	weights::Weight,
+};
+use kreivo_apis::{IKreivoAssets, IKreivoListings, IKreivoMemberships};
+use pallet_revive::{
+	precompiles::alloy::{
+		primitives::{Address, Bytes, U256},
+		sol_types::{SolCall, SolValue},
+	},
+	AddressMapper, DepositLimit, H160,
+};
+use virto_common::{listings::*, CommunityId};
+
+const ALICE: AccountId32 = AccountId32::new([1; 32]);
+const BOB: AccountId32 = AccountId32::new([2; 32]);
+
+const COMMUNITY: CommunityId = 1;
+const ASSET: FungibleAssetLocation = FungibleAssetLocation::Here(1);
+const INVENTORY_ID: InventoryId = 1;
+const ITEM_ID: ItemId = 1;
+
+const ASSETS: u64 = 0x5000;
+const LISTINGS: u64 = 0x5001;
+const MEMBERSHIPS: u64 = 0x5002;
+
+fn new_test_ext() -> TestExternalities {
+	let mut t = TestExternalities::default();
+	t.execute_with(|| {
+		assert_ok!(Balances::mint_into(&ALICE, 100 * UNITS));
+		assert_ok!(Balances::mint_into(&BOB, UNITS));
+		assert_ok!(Balances::mint_into(
+			&Communities::community_account(&COMMUNITY),
+			100 * UNITS
+		));
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(Revive::map_account(RuntimeOrigin::signed(BOB)));
+
+		// Register a community that owns one of the memberships.
+		assert_ok!(CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			10,
+			0,
+			CENTS,
+			TankConfig::default(),
+			Some(8 * WEEKS),
+		));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			COMMUNITY,
+			BoundedVec::try_from(b"Precompiles Community".to_vec()).expect("meets max length; qed"),
+			CommunityLookup::unlookup(ALICE),
+			None,
+			None,
+			None,
+		));
+		assert_ok!(Communities::dispatch_as_account(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(
+				pallet_nfts::Call::<Runtime, CommunityMembershipsInstance>::buy_item {
+					collection: 0,
+					item: 0,
+					bid_price: CENTS
+				}
+				.into()
+			)
+		));
+
+		assert_ok!(<Assets as Create<_>>::create(ASSET, ALICE, true, 1));
+		assert_ok!(Assets::mint_into(ASSET, &ALICE, 100));
+	});
+	t
+}
+
+/// The init code of an EVM contract that calls the address found in the first
+/// word of its input with the rest of it, and returns (or reverts with) the
+/// output of that call.
+const FORWARDER: [u8; 52] = [
+	// Init code: returns the 41 bytes of code that follow it.
+	0x60, 0x29, 0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3,
+	// Code: `call(gas(), calldataload(0), 0, 0, sub(calldatasize(), 32), 0, 0)`,
+	// with the input past the first word copied to memory.
+	0x60, 0x20, 0x36, 0x03, 0x80, 0x60, 0x20, 0x60, 0x00, 0x37, 0x60, 0x00, 0x60, 0x00, 0x82, 0x60, 0x00, 0x60, 0x00,
+	0x60, 0x00, 0x35, 0x5a, 0xf1,
+	// Copies the output of the call, and either reverts with it or returns it.
+	0x3d, 0x60, 0x00, 0x60, 0x00, 0x3e, 0x3d, 0x90, 0x60, 0x00, 0x90, 0x60, 0x27, 0x57, 0xfd, 0x5b, 0xf3,
+];
+const SALT: [u8; 32] = [0; 32];
+
+fn community_origin() -> RuntimeOrigin {
+	pallet_communities::Origin::<Runtime>::new(COMMUNITY).into()
+}
+
+fn h160(who: &AccountId) -> H160 {
+	<Runtime as pallet_revive::Config>::AddressMapper::to_address(who)
+}
+
+fn address(who: &AccountId) -> Address {
+	Address::from(h160(who).0)
+}
+
+/// Deploys a [`FORWARDER`] contract, and binds it to the community.
+fn bound_contract() -> AccountId {
+	assert_ok!(Revive::instantiate_with_code(
+		RuntimeOrigin::signed(ALICE),
+		0,
+		Weight::MAX,
+		10 * UNITS,
+		FORWARDER.to_vec(),
+		vec![],
+		Some(SALT),
+	));
+	let contract = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&pallet_revive::create2(
+		&h160(&ALICE),
+		&FORWARDER,
+		&[],
+		&SALT,
+	));
+	assert_ok!(Balances::mint_into(&contract, 50 * UNITS));
+
+	assert_ok!(ContractsStore::bind_contract(
+		community_origin(),
+		<Runtime as frame_system::Config>::Lookup::unlookup(contract.clone()),
+	));
+	contract
+}
+
+/// Calls `dest` as `ALICE`, returning whether the call reverted, along with
+/// its output.
+fn bare_call(dest: H160, data: Vec<u8>) -> (bool, Vec<u8>) {
+	let result = Revive::bare_call(
+		RuntimeOrigin::signed(ALICE),
+		dest,
+		Default::default(),
+		Weight::MAX,
+		DepositLimit::UnsafeOnlyForDryRun,
+		data,
+	)
+	.result
+	.expect("neither precompiles nor the forwarder trap; qed");
+	(result.did_revert(), result.data)
+}
+
+/// Calls the precompile at `precompile` as `ALICE`.
+fn call_ok(precompile: u64, call: impl SolCall) -> Vec<u8> {
+	let (reverted, output) = bare_call(H160::from_low_u64_be(precompile), call.abi_encode());
+	assert!(!reverted, "the call succeeds");
+	output
+}
+
+fn reverts(precompile: u64, call: impl SolCall) -> bool {
+	bare_call(H160::from_low_u64_be(precompile), call.abi_encode()).0
+}
+
+/// Calls the precompile at `precompile` through `contract`.
+fn contract_call(contract: &AccountId, precompile: u64, call: impl SolCall) -> (bool, Vec<u8>) {
+	let target = H160::from_low_u64_be(precompile);
+	let data = [&[0u8; 12][..], &target.0[..], &call.abi_encode()[..]].concat();
+	bare_call(h160(contract), data)
+}
+
+fn contract_call_ok(contract: &AccountId, precompile: u64, call: impl SolCall) -> Vec<u8> {
+	let (reverted, output) = contract_call(contract, precompile, call);
+	assert!(!reverted, "the call succeeds");
+	output
+}
+
+mod assets {
+	use super::*;
+
+	#[test]
+	fn balance_works() {
+		new_test_ext().execute_with(|| {
+			let balance = |who| {
+				call_ok(
+					ASSETS,
+					IKreivoAssets::balanceCall {
+						asset: Bytes::from(ASSET.encode()),
+						who: address(&who),
+					},
+				)
+			};
+
+			assert_eq!(balance(ALICE), U256::from(100).abi_encode());
+			assert_eq!(balance(BOB), U256::from(0).abi_encode());
+		})
+	}
+
+	#[test]
+	fn transfer_moves_funds_of_the_calling_account() {
+		new_test_ext().execute_with(|| {
+			let transfer = |amount: u32| IKreivoAssets::transferCall {
+				asset: Bytes::from(ASSET.encode()),
+				amount: U256::from(amount),
+				beneficiary: address(&BOB),
+			};
+
+			assert_eq!(call_ok(ASSETS, transfer(10)), U256::from(10).abi_encode());
+			assert_eq!(Assets::balance(ASSET, ALICE), 90);
+			assert_eq!(Assets::balance(ASSET, BOB), 10);
+
+			assert!(reverts(ASSETS, transfer(1_000)));
+		})
+	}
+
+	#[test]
+	fn deposit_is_not_exposed() {
+		new_test_ext().execute_with(|| {
+			// `deposit(bytes,uint256)`, as it was declared before being removed.
+			let selector = sp_core::keccak_256(b"deposit(bytes,uint256)");
+			let data = [
+				&selector[..4],
+				&(Bytes::from(ASSET.encode()), U256::from(10)).abi_encode_params(),
+			]
+			.concat();
+
+			let result = Revive::bare_call(
+				RuntimeOrigin::signed(ALICE),
+				H160::from_low_u64_be(ASSETS),
+				Default::default(),
+				Weight::MAX,
+				DepositLimit::UnsafeOnlyForDryRun,
+				data,
+			)
+			.result;
+
+			assert!(result.map_or(true, |r| r.did_revert()));
+			assert_eq!(Assets::balance(ASSET, ALICE), 100);
+		})
+	}
+}
+
+mod listings {
+	use super::*;
+
+	#[test]
+	fn calls_without_merchant_id_revert() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				call_ok(LISTINGS, IKreivoListings::inventoryExistsCall { id: INVENTORY_ID }),
+				false.abi_encode()
+			);
+			assert!(reverts(
+				LISTINGS,
+				IKreivoListings::createInventoryCall { id: INVENTORY_ID }
+			));
+		})
+	}
+
+	#[test]
+	fn only_contracts_can_be_bound() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				ContractsStore::bind_contract(
+					community_origin(),
+					<Runtime as frame_system::Config>::Lookup::unlookup(ALICE)
+				),
+				pallet_contracts_store::Error::<Runtime>::NotAContract
+			);
+
+			let contract = bound_contract();
+			assert_eq!(ContractsStore::maybe_merchant_id(&contract), Some(COMMUNITY));
+		})
+	}
+
+	#[test]
+	fn items_are_published_with_a_price() {
+		new_test_ext().execute_with(|| {
+			let contract = bound_contract();
+			let inventory = (COMMUNITY, INVENTORY_ID);
+
+			contract_call_ok(
+				&contract,
+				LISTINGS,
+				IKreivoListings::createInventoryCall { id: INVENTORY_ID },
+			);
+			assert_eq!(
+				contract_call_ok(
+					&contract,
+					LISTINGS,
+					IKreivoListings::inventoryIsActiveCall { id: INVENTORY_ID }
+				),
+				true.abi_encode()
+			);
+
+			contract_call_ok(
+				&contract,
+				LISTINGS,
+				IKreivoListings::publishItemCall {
+					inventoryId: INVENTORY_ID,
+					id: ITEM_ID,
+					name: Bytes::from(b"Item".to_vec()),
+					asset: Bytes::from(ASSET.encode()),
+					amount: U256::from(10),
+				},
+			);
+			let item = Listings::item(&inventory, &ITEM_ID).expect("item was published; qed");
+			assert_eq!(
+				item.price,
+				Some(ItemPrice {
+					asset: ASSET,
+					amount: 10
+				})
+			);
+			assert_eq!(
+				contract_call_ok(
+					&contract,
+					LISTINGS,
+					IKreivoListings::itemCall {
+						inventoryId: INVENTORY_ID,
+						id: ITEM_ID
+					}
+				),
+				Bytes::from(item.encode()).abi_encode()
+			);
+
+			// An empty asset publishes the item without a price.
+			contract_call_ok(
+				&contract,
+				LISTINGS,
+				IKreivoListings::publishItemCall {
+					inventoryId: INVENTORY_ID,
+					id: ITEM_ID + 1,
+					name: Bytes::from(b"Unpriced".to_vec()),
+					asset: Bytes::new(),
+					amount: U256::ZERO,
+				},
+			);
+			let item = Listings::item(&inventory, &(ITEM_ID + 1)).expect("item was published; qed");
+			assert_eq!(item.price, None);
+
+			contract_call_ok(
+				&contract,
+				LISTINGS,
+				IKreivoListings::transferItemCall {
+					inventoryId: INVENTORY_ID,
+					id: ITEM_ID,
+					beneficiary: address(&BOB),
+				},
+			);
+			let item = Listings::item(&inventory, &ITEM_ID).expect("item exists; qed");
+			assert_eq!(item.owner, BOB);
+		})
+	}
+}
+
+mod memberships {
+	use super::*;
+
+	#[test]
+	fn memberships_work() {
+		new_test_ext().execute_with(|| {
+			let not_found = (false, 0u32).abi_encode_params();
+			assert_eq!(
+				call_ok(MEMBERSHIPS, IKreivoMemberships::membershipOfCall { who: address(&BOB) }),
+				not_found
+			);
+			assert!(reverts(
+				MEMBERSHIPS,
+				IKreivoMemberships::assignMembershipCall { who: address(&BOB) }
+			));
+
+			let contract = bound_contract();
+			contract_call_ok(
+				&contract,
+				MEMBERSHIPS,
+				IKreivoMemberships::assignMembershipCall { who: address(&BOB) },
+			);
+			assert!(Memberships::is_member_of(&COMMUNITY, &BOB));
+			assert_eq!(
+				contract_call_ok(
+					&contract,
+					MEMBERSHIPS,
+					IKreivoMemberships::membershipOfCall { who: address(&BOB) }
+				),
+				(true, 0u32).abi_encode_params()
+			);
+
+			contract_call_ok(
+				&contract,
+				MEMBERSHIPS,
+				IKreivoMemberships::setAttributeCall {
+					id: 0,
+					key: Bytes::from(b"key".to_vec()),
+					value: Bytes::from(b"value".to_vec()),
+				},
+			);
+			assert_eq!(
+				contract_call_ok(
+					&contract,
+					MEMBERSHIPS,
+					IKreivoMemberships::attributeCall {
+						id: 0,
+						key: Bytes::from(b"key".to_vec())
+					}
+				),
+				Bytes::from(b"value".to_vec()).abi_encode()
+			);
+
+			// There are no more available memberships.
+			assert!(
+				contract_call(
+					&contract,
+					MEMBERSHIPS,
+					IKreivoMemberships::assignMembershipCall { who: address(&ALICE) }
+				)
+				.0
+			);
+		})
+	}
+}
@@ -36,10 +36,13 @@ pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_orders;
 pub mod pallet_pass;
+pub mod pallet_payment_disputes;
+pub mod pallet_payment_fees;
 pub mod pallet_payments;
 pub mod pallet_preimage;
 pub mod pallet_proxy;
 pub mod pallet_ranked_collective;
+pub mod pallet_recurring_payments;
 pub mod pallet_referenda;
 pub mod pallet_referenda_tracks;
 pub mod pallet_scheduler;
@@ -114,4 +114,19 @@ impl<T: frame_system::Config> pallet_contracts_store::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(10))
 			.saturating_add(T::DbWeight::get().writes(12))
 	}
+	/// Storage: `Revive::AccountInfoOf` (r:1 w:0)
+	/// Storage: `ContractsStore::ContractMerchantId` (r:1 w:1)
+	fn bind_contract() -> Weight {
+		Weight::from_parts(17_572_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ContractsStore::ContractMerchantId` (r:1 w:1)
+	fn unbind_contract() -> Weight {
+		Weight::from_parts(14_800_000, 0)
+			.saturating_add(Weight::from_parts(0, 3605))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -143,8 +143,8 @@ impl<T: frame_system::Config> pallet_orders::WeightInfo for WeightInfo<T> {
 	/// Proof: `ListingsCatalog::Item` (`max_values`: None, `max_size`: Some(164), added: 2639, mode: `MaxEncodedLen`)
 	/// Storage: `ListingsCatalog::Attribute` (r:128 w:64)
 	/// Proof: `ListingsCatalog::Attribute` (`max_values`: None, `max_size`: Some(485), added: 2960, mode: `MaxEncodedLen`)
-	/// Storage: `PaymentIndices::Index` (r:1 w:1)
-	/// Proof: `PaymentIndices::Index` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `PaymentIndices::LastIndex` (r:1 w:1)
+	/// Proof: `PaymentIndices::LastIndex` (`max_values`: Some(1), `max_size`: Some(6), added: 501, mode: `MaxEncodedLen`)
 	/// Storage: `Payments::Payment` (r:64 w:64)
 	/// Proof: `Payments::Payment` (`max_values`: None, `max_size`: Some(5053), added: 7528, mode: `MaxEncodedLen`)
 	/// Storage: `Assets::Asset` (r:1 w:1)
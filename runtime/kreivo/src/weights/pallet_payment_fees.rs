@@ -0,0 +1,33 @@
+//! Weights for `pallet_payment_fees`
+//!
+//! These follow the storage accessed by each call, and are meant to be
+//! replaced by the output of `frame-omni-bencher` for `pallet_payment_fees`,
+//! whose benchmarks live along the pallet in `config/payments/fees.rs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_payment_fees`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::pallet_payment_fees::WeightInfo for WeightInfo<T> {
+	/// Storage: `PaymentFees::GlobalSchedule` (r:0 w:1)
+	fn set_global_schedule() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PaymentFees::CommunityFees` (r:0 w:1)
+	fn set_community_fee() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PaymentFees::MemberDiscounts` (r:0 w:1)
+	fn set_member_discounts() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
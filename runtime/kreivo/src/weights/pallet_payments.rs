@@ -36,8 +36,8 @@ use core::marker::PhantomData;
 /// Weight functions for `pallet_payments`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_payments::WeightInfo for WeightInfo<T> {
-	/// Storage: `PaymentIndices::Index` (r:1 w:1)
-	/// Proof: `PaymentIndices::Index` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `PaymentIndices::LastIndex` (r:1 w:1)
+	/// Proof: `PaymentIndices::LastIndex` (`max_values`: Some(1), `max_size`: Some(6), added: 501, mode: `MaxEncodedLen`)
 	/// Storage: `Payments::Payment` (r:1 w:1)
 	/// Proof: `Payments::Payment` (`max_values`: None, `max_size`: Some(5053), added: 7528, mode: `MaxEncodedLen`)
 	/// Storage: `Assets::Asset` (r:1 w:1)
@@ -189,8 +189,8 @@ impl<T: frame_system::Config> pallet_payments::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(13))
 			.saturating_add(T::DbWeight::get().writes(10))
 	}
-	/// Storage: `PaymentIndices::Index` (r:1 w:1)
-	/// Proof: `PaymentIndices::Index` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `PaymentIndices::LastIndex` (r:1 w:1)
+	/// Proof: `PaymentIndices::LastIndex` (`max_values`: Some(1), `max_size`: Some(6), added: 501, mode: `MaxEncodedLen`)
 	/// Storage: `Payments::Payment` (r:1 w:1)
 	/// Proof: `Payments::Payment` (`max_values`: None, `max_size`: Some(5053), added: 7528, mode: `MaxEncodedLen`)
 	/// Storage: `Assets::Asset` (r:1 w:0)
@@ -0,0 +1,51 @@
+//! Weights for `pallet_recurring_payments`
+//!
+//! These follow the storage accessed by each call, and are meant to be
+//! replaced by the output of `frame-omni-bencher` for
+//! `pallet_recurring_payments`, whose benchmarks live along the pallet in
+//! `config/payments/recurring.rs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_recurring_payments`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> crate::pallet_recurring_payments::WeightInfo for WeightInfo<T> {
+	/// Storage: `RecurringPayments::NextRecurringPaymentId` (r:1 w:1)
+	/// Storage: `RecurringPayments::PayerRecurringPayments` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Storage: `RecurringPayments::RecurringPayments` (r:0 w:1)
+	fn authorize() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: `RecurringPayments::RecurringPayments` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Storage: `RecurringPayments::PayerRecurringPayments` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn cancel() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: `RecurringPayments::RecurringPayments` (r:1 w:1)
+	/// Storage: `RecurringPayments::PayerRecurringPayments` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn charge() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}